@@ -1,4 +1,4 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use rolldown_core::{Asset, BuildResult, BundlerCore};
 use rolldown_plugin::BuildPlugin;
@@ -20,10 +20,9 @@ impl Bundler {
     rolldown_tracing::enable_tracing_on_demand();
     let cwd = input_opts.cwd.clone();
 
-    let mut builtin_post_plugins = vec![];
-
+    // Builtin plugins declare their own order, so it doesn't matter where they are pushed.
     if let Some(node_resolve) = input_opts.builtins.node_resolve {
-      builtin_post_plugins.push(rolldown_plugin_node_resolve::NodeResolvePlugin::new_boxed(
+      plugins.push(rolldown_plugin_node_resolve::NodeResolvePlugin::new_boxed(
         rolldown_plugin_node_resolve::ResolverOptions {
           extensions: node_resolve.extensions,
           symlinks: !input_opts.preserve_symlinks,
@@ -33,8 +32,6 @@ impl Bundler {
       ))
     }
 
-    plugins.extend(builtin_post_plugins);
-
     let bundler = BundlerCore::with_plugins(
       rolldown_core::BuildInputOptions {
         input: input_opts.input,
@@ -43,8 +40,11 @@ impl Bundler {
         is_external: input_opts.is_external,
         on_warn: input_opts.on_warn,
         shim_missing_exports: input_opts.shim_missing_exports,
+        strict_execution_order: input_opts.strict_execution_order,
         builtins: rolldown_core::BuiltinsOptions {
           tsconfig: input_opts.builtins.tsconfig.unwrap_or_default(),
+          jsx: input_opts.builtins.jsx,
+          loaders: input_opts.builtins.loaders.into_iter().collect(),
           ..Default::default()
         },
       },
@@ -67,33 +67,44 @@ impl Bundler {
       .build(rolldown_core::BuildOutputOptions {
         entry_file_names: output_options.entry_file_names,
         chunk_file_names: output_options.chunk_file_names,
+        asset_file_names: output_options.asset_file_names,
         format: output_options.format,
         export_mode: output_options.export_mode,
+        dir: Some(dir.clone()),
+        sourcemap: output_options.sourcemap,
+        sourcemap_exclude_sources: output_options.sourcemap_exclude_sources,
+        name: output_options.name,
+        globals: output_options.globals.into_iter().collect(),
+        amd: output_options.amd,
+        interop: output_options.interop,
       })
-      .await?;
+      .await
+      .and_then(|output| {
+        self.write_assets(dir.as_path(), &output)?;
+        Ok(output)
+      });
+    self.close(output).await
+  }
 
-    std::fs::create_dir_all(&dir).unwrap_or_else(|_| {
+  fn write_assets(&self, dir: &Path, output: &[Asset]) -> BuildResult<()> {
+    std::fs::create_dir_all(dir).unwrap_or_else(|_| {
       panic!(
         "Could not create directory for output chunks: {:?} \ncwd: {}",
-        dir.as_path(),
+        dir,
         self.cwd.display()
       )
     });
-    for chunk in &output {
-      let dest = dir.as_path().join(&chunk.filename);
+    for chunk in output {
+      let dest = dir.join(&chunk.filename);
       if let Some(p) = dest.parent() {
         if !p.exists() {
           std::fs::create_dir_all(p)?;
         }
       };
-      std::fs::write(dest, &chunk.content).unwrap_or_else(|_| {
-        panic!(
-          "Failed to write file in {:?}",
-          dir.as_path().join(&chunk.filename)
-        )
-      });
+      std::fs::write(dest, chunk.content.as_bytes())
+        .unwrap_or_else(|_| panic!("Failed to write file in {:?}", dir.join(&chunk.filename)));
     }
-    Ok(output)
+    Ok(())
   }
 
   pub async fn generate(
@@ -105,11 +116,31 @@ impl Bundler {
       .build(rolldown_core::BuildOutputOptions {
         entry_file_names: output_options.entry_file_names,
         chunk_file_names: output_options.chunk_file_names,
+        asset_file_names: output_options.asset_file_names,
         format: output_options.format,
         export_mode: output_options.export_mode,
+        dir: output_options.dir,
+        sourcemap: output_options.sourcemap,
+        sourcemap_exclude_sources: output_options.sourcemap_exclude_sources,
+        name: output_options.name,
+        globals: output_options.globals.into_iter().collect(),
+        amd: output_options.amd,
+        interop: output_options.interop,
       })
-      .await?;
+      .await;
+    self.close(output).await
+  }
 
+  /// Call `close_bundle` of plugins no matter whether the build succeeded.
+  async fn close(&mut self, output: BuildResult<Vec<Asset>>) -> BuildResult<Vec<Asset>> {
+    let close_result = self.core.close().await;
+    let output = output?;
+    close_result?;
     Ok(output)
   }
+
+  /// Files that plugins asked to watch during the last build.
+  pub async fn watch_files(&self) -> Vec<PathBuf> {
+    self.core.watch_files().await
+  }
 }
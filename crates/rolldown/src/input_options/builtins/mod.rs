@@ -1,7 +1,10 @@
 mod node_resolve;
+use std::collections::HashMap;
+
 use derivative::Derivative;
 pub use node_resolve::*;
-pub use rolldown_core::TsConfig;
+pub use rolldown_core::{JsxOptions, JsxRuntime, TsConfig};
+use rolldown_plugin::Loader;
 
 #[derive(Derivative)]
 #[derivative(Debug)]
@@ -10,6 +13,9 @@ pub struct BuiltinsOptions {
   pub node_resolve: Option<NodeResolveOptions>,
   /// None means default
   pub tsconfig: Option<TsConfig>,
+  pub jsx: JsxOptions,
+  /// Loaders of extensions without the leading dot, such as `glsl` -> `Loader::Text`.
+  pub loaders: HashMap<String, Loader>,
 }
 
 impl Default for BuiltinsOptions {
@@ -17,6 +23,8 @@ impl Default for BuiltinsOptions {
     Self {
       node_resolve: Some(Default::default()),
       tsconfig: Some(Default::default()),
+      jsx: Default::default(),
+      loaders: Default::default(),
     }
   }
 }
@@ -18,6 +18,7 @@ pub struct InputOptions {
   #[derivative(Debug = "ignore")]
   pub on_warn: WarningHandler,
   pub shim_missing_exports: bool,
+  pub strict_execution_order: bool,
   pub builtins: BuiltinsOptions,
 }
 
@@ -37,6 +38,7 @@ impl Default for InputOptions {
       is_external: Arc::new(|_, _, _| future::ready(Ok(false)).boxed()),
       on_warn: default_warning_handler(),
       shim_missing_exports: false,
+      strict_execution_order: false,
       builtins: Default::default(),
     }
   }
@@ -5,8 +5,12 @@ pub use {
   bundler::Bundler,
   input_options::{
     default_warning_handler, BuiltinsOptions, InputItem, InputOptions, IsExternal,
-    NodeResolveOptions, TsConfig,
+    JsxOptions, JsxRuntime, NodeResolveOptions, TsConfig,
   },
-  output_options::{ExportMode, FileNameTemplate, ModuleFormat, OutputOptions},
-  rolldown_core::{Asset, BuildResult},
+  output_options::{
+    interop_of_mode, AmdOptions, ExportMode, FileNameTemplate, InteropMode, ModuleFormat,
+    OutputOptions, ResolveInterop, SourceMapType,
+  },
+  rolldown_core::{Asset, AssetContent, BuildResult},
+  rolldown_plugin::Loader,
 };
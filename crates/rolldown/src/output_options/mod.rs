@@ -1,5 +1,10 @@
+use std::collections::HashMap;
+
 use derivative::Derivative;
-pub use rolldown_core::{file_name::FileNameTemplate, ExportMode, ModuleFormat};
+pub use rolldown_core::{
+  file_name::FileNameTemplate, interop_of_mode, AmdOptions, ExportMode, InteropMode, ModuleFormat,
+  ResolveInterop, SourceMapType,
+};
 
 #[derive(Derivative)]
 #[derivative(Debug)]
@@ -7,8 +12,21 @@ pub struct OutputOptions {
   pub dir: Option<String>,
   pub entry_file_names: FileNameTemplate,
   pub chunk_file_names: FileNameTemplate,
+  pub asset_file_names: FileNameTemplate,
   pub format: ModuleFormat,
   pub export_mode: ExportMode,
+  /// `None` means no source maps are generated.
+  pub sourcemap: Option<SourceMapType>,
+  pub sourcemap_exclude_sources: bool,
+  /// The global variable assigned with exports of IIFE and UMD bundles, such as `MyLib` or `a.b.c`.
+  pub name: Option<String>,
+  /// Global variables of external modules in IIFE and UMD bundles, keyed by external ids.
+  pub globals: HashMap<String, String>,
+  pub amd: AmdOptions,
+  /// How `default` and namespace imports of external modules are interpreted in CommonJS, IIFE, AMD and
+  /// UMD output.
+  #[derivative(Debug = "ignore")]
+  pub interop: ResolveInterop,
 }
 
 impl Default for OutputOptions {
@@ -16,9 +34,16 @@ impl Default for OutputOptions {
     Self {
       entry_file_names: FileNameTemplate::from("[name].js".to_string()),
       chunk_file_names: FileNameTemplate::from("[name]-[hash].js".to_string()),
+      asset_file_names: FileNameTemplate::from("assets/[name]-[hash][extname]".to_string()),
       dir: None,
       format: ModuleFormat::Esm,
       export_mode: ExportMode::Auto,
+      sourcemap: None,
+      sourcemap_exclude_sources: false,
+      name: None,
+      globals: Default::default(),
+      amd: Default::default(),
+      interop: interop_of_mode(InteropMode::Auto),
     }
   }
 }
@@ -4,7 +4,10 @@ use std::{
 };
 
 use rolldown::Bundler;
-use rolldown::{Asset, BuildResult, ExportMode, ModuleFormat, OutputOptions};
+use rolldown::{
+  interop_of_mode, AmdOptions, Asset, AssetContent, BuildResult, ExportMode, InteropMode,
+  ModuleFormat, OutputOptions,
+};
 use rolldown_test_utils::tester::Tester;
 
 pub struct CompiledFixture {
@@ -24,7 +27,11 @@ impl CompiledFixture {
       .flat_map(|asset| {
         [
           format!("---------- {} ----------", asset.filename),
-          asset.content.trim().to_string(),
+          match &asset.content {
+            AssetContent::Code(code) => code.trim().to_string(),
+            // Show bytes as they are, since binary assets might not be valid UTF-8
+            AssetContent::Binary(bytes) => format!("{bytes:02x?}"),
+          },
         ]
       })
       .chain(if self.tester.warnings.lock().unwrap().is_empty() {
@@ -68,6 +75,13 @@ pub async fn compile_fixture(test_config_path: &Path) -> CompiledFixture {
       // dir: Some(fixture_path.join("dist").to_string_lossy().to_string()),
       format: ModuleFormat::from_str(&tester.config.output.format).unwrap(),
       export_mode: ExportMode::from_str(&tester.config.output.export_mode).unwrap(),
+      name: tester.config.output.name.clone(),
+      globals: tester.config.output.globals.clone(),
+      amd: AmdOptions {
+        id: tester.config.output.amd.id.clone(),
+        define: tester.config.output.amd.define.clone(),
+      },
+      interop: interop_of_mode(InteropMode::from_str(&tester.config.output.interop).unwrap()),
       ..Default::default()
     })
     .await;
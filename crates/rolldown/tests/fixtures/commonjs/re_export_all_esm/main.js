@@ -0,0 +1,2 @@
+export * from './foo.js'
+export const own = 'own'
@@ -0,0 +1,8 @@
+import { ajax } from 'jquery'
+import 'polyfill'
+
+export const answer = 42
+
+export function load(url) {
+  return ajax(url)
+}
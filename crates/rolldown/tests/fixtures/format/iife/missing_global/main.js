@@ -0,0 +1,3 @@
+import { debounce } from 'lodash-es'
+
+export default debounce(function () {}, 100)
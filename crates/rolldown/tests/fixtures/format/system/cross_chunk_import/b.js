@@ -0,0 +1,3 @@
+import { answer } from './shared'
+
+console.log('b', answer)
@@ -0,0 +1,7 @@
+export let count = 0
+
+export function increment() {
+  count++
+  count += 1
+  count = count * 2
+}
@@ -0,0 +1,7 @@
+import { ajax } from 'jquery'
+
+export const answer = 42
+
+export function load(url) {
+  return ajax(url)
+}
@@ -0,0 +1,5 @@
+const define = 'define'
+const factory = 'factory'
+const global = 'global'
+
+export default define + factory + global
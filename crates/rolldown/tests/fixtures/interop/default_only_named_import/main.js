@@ -0,0 +1,3 @@
+import { named } from 'ext'
+
+console.log(named)
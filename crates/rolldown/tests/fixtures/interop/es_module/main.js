@@ -0,0 +1,4 @@
+import ext from 'ext'
+import * as ns from 'other'
+
+console.log(ext, ns)
@@ -0,0 +1,3 @@
+import './foo.js?a'
+import './foo.js?b'
+console.log('main')
@@ -0,0 +1,180 @@
+use std::sync::{Arc, Mutex};
+
+use rolldown_error::Error as BuildError;
+use rolldown_plugin::{
+  async_trait, BuildPlugin, HookNoopReturn, InputOptions, ModuleInfo, PluginContext, PluginName,
+  RenderChunkArgs, RenderChunkOutput, ResolveArgs, ResolveOutput, ResolvedId,
+};
+
+use crate::utils::{bundle, fixture_path};
+
+#[derive(Debug)]
+struct WarnPlugin;
+
+#[async_trait::async_trait]
+impl BuildPlugin for WarnPlugin {
+  fn name(&self) -> PluginName {
+    "test:warn".into()
+  }
+
+  async fn build_start(&self, ctx: &mut PluginContext, _options: &InputOptions) -> HookNoopReturn {
+    ctx.warn(BuildError::panic("something is off"));
+    Ok(())
+  }
+}
+
+#[tokio::test]
+async fn warn_goes_to_on_warn_with_the_plugin_name() {
+  let bundle = bundle("context/watch_file", vec![Box::new(WarnPlugin)]).await;
+  bundle.assets();
+  assert_eq!(bundle.warnings.len(), 1);
+  let warning = bundle.warnings[0].to_string();
+  assert!(warning.contains("plugin: test:warn"), "{warning}");
+  assert!(warning.contains("something is off"), "{warning}");
+}
+
+/// Resolves `virtual:self` by itself. For `./foo.js`, it records what `ctx.resolve` returns with and without skipping itself.
+#[derive(Debug, Default)]
+struct ResolvePlugin {
+  resolved: Arc<Mutex<Vec<Option<ResolvedId>>>>,
+}
+
+#[async_trait::async_trait]
+impl BuildPlugin for ResolvePlugin {
+  fn name(&self) -> PluginName {
+    "test:resolve".into()
+  }
+
+  async fn resolve(&self, ctx: &mut PluginContext, args: &mut ResolveArgs) -> ResolveOutput {
+    match args.specifier {
+      "virtual:self" => Ok(Some(ResolvedId {
+        id: "\0virtual:self".to_string(),
+        external: false,
+      })),
+      "./foo.js" => {
+        // Calling `resolve` with the same specifier would recurse forever without skipping itself.
+        let skipped = ctx.resolve(args.specifier, args.importer, true).await?;
+        let not_skipped = ctx.resolve("virtual:self", args.importer, false).await?;
+        self.resolved.lock().unwrap().extend([skipped, not_skipped]);
+        Ok(None)
+      }
+      _ => Ok(None),
+    }
+  }
+}
+
+#[tokio::test]
+async fn resolve_could_skip_the_calling_plugin() {
+  let resolved = Arc::new(Mutex::new(vec![]));
+  let bundle = bundle(
+    "context/resolve",
+    vec![Box::new(ResolvePlugin {
+      resolved: resolved.clone(),
+    })],
+  )
+  .await;
+  assert!(bundle.code_of("main.js").contains("'foo'"));
+
+  let resolved = resolved.lock().unwrap();
+  assert_eq!(resolved.len(), 2);
+  let skipped = resolved[0].as_ref().expect("./foo.js should be resolved");
+  assert_eq!(
+    skipped.id,
+    fixture_path("context/resolve")
+      .join("foo.js")
+      .to_string_lossy()
+  );
+  assert!(!skipped.external);
+  let not_skipped = resolved[1]
+    .as_ref()
+    .expect("virtual:self should be resolved");
+  assert_eq!(not_skipped.id, "\0virtual:self");
+}
+
+#[derive(Debug, Default)]
+struct ModuleInfoPlugin {
+  infos: Arc<Mutex<Vec<ModuleInfo>>>,
+}
+
+#[async_trait::async_trait]
+impl BuildPlugin for ModuleInfoPlugin {
+  fn name(&self) -> PluginName {
+    "test:module-info".into()
+  }
+
+  async fn render_chunk(
+    &self,
+    ctx: &mut PluginContext,
+    args: &mut RenderChunkArgs,
+  ) -> RenderChunkOutput {
+    let mut infos = self.infos.lock().unwrap();
+    for id in &args.chunk.module_ids {
+      infos.push(
+        ctx
+          .get_module_info(id)
+          .unwrap_or_else(|| panic!("{id} should be loaded")),
+      );
+    }
+    Ok(None)
+  }
+}
+
+#[tokio::test]
+async fn get_module_info_of_loaded_modules() {
+  let infos = Arc::new(Mutex::new(vec![]));
+  let bundle = bundle(
+    "context/module_info",
+    vec![Box::new(ModuleInfoPlugin {
+      infos: infos.clone(),
+    })],
+  )
+  .await;
+  bundle.assets();
+
+  let infos = infos.lock().unwrap();
+  let info_of = |name: &str| {
+    infos
+      .iter()
+      .find(|info| info.id.as_ref().ends_with(name))
+      .unwrap_or_else(|| panic!("No module info of {name}"))
+  };
+  let main = info_of("main.js");
+  assert!(main.is_entry);
+  assert!(!main.is_external);
+  assert_eq!(main.imported_ids.len(), 1);
+  assert!(main.imported_ids[0].as_ref().ends_with("foo.js"));
+  assert_eq!(main.dynamically_imported_ids.len(), 1);
+  assert!(main.dynamically_imported_ids[0]
+    .as_ref()
+    .ends_with("lazy.js"));
+
+  let foo = info_of("foo.js");
+  assert!(!foo.is_entry);
+  assert!(foo.imported_ids.is_empty());
+  info_of("lazy.js");
+}
+
+#[derive(Debug)]
+struct WatchFilePlugin;
+
+#[async_trait::async_trait]
+impl BuildPlugin for WatchFilePlugin {
+  fn name(&self) -> PluginName {
+    "test:watch-file".into()
+  }
+
+  async fn build_start(&self, ctx: &mut PluginContext, _options: &InputOptions) -> HookNoopReturn {
+    ctx.add_watch_file(fixture_path("context/watch_file").join("extra.txt"));
+    Ok(())
+  }
+}
+
+#[tokio::test]
+async fn add_watch_file_is_reported_by_the_bundler() {
+  let bundle = bundle("context/watch_file", vec![Box::new(WatchFilePlugin)]).await;
+  bundle.assets();
+  assert_eq!(
+    bundle.watch_files,
+    vec![fixture_path("context/watch_file").join("extra.txt")]
+  );
+}
@@ -0,0 +1,240 @@
+use std::{
+  sync::{Arc, Mutex},
+  time::Duration,
+};
+
+use rolldown_plugin::{
+  async_trait, BuildPlugin, HookNoopReturn, InputOptions, LoadArgs, LoadOutput, LoadedSource,
+  PluginContext, PluginName, PluginOrder, ResolveArgs, ResolveOutput, ResolvedId, TransformArgs,
+  TransformOutput, TransformedSource,
+};
+use tokio::sync::Barrier;
+
+use crate::utils::bundle;
+
+type Calls = Arc<Mutex<Vec<String>>>;
+
+/// Records `resolve` and `transform` calls, and appends `console.log('<name>')` to the code in `transform`.
+#[derive(Debug)]
+struct RecordPlugin {
+  name: &'static str,
+  order: PluginOrder,
+  calls: Calls,
+}
+
+impl RecordPlugin {
+  fn boxed(name: &'static str, order: PluginOrder, calls: &Calls) -> Box<dyn BuildPlugin> {
+    Box::new(Self {
+      name,
+      order,
+      calls: calls.clone(),
+    })
+  }
+}
+
+#[async_trait::async_trait]
+impl BuildPlugin for RecordPlugin {
+  fn name(&self) -> PluginName {
+    self.name.into()
+  }
+
+  fn order(&self) -> PluginOrder {
+    self.order
+  }
+
+  async fn resolve(&self, _ctx: &mut PluginContext, args: &mut ResolveArgs) -> ResolveOutput {
+    self
+      .calls
+      .lock()
+      .unwrap()
+      .push(format!("{}: resolve {}", self.name, args.specifier));
+    Ok(None)
+  }
+
+  async fn transform(&self, _ctx: &mut PluginContext, args: &mut TransformArgs) -> TransformOutput {
+    self
+      .calls
+      .lock()
+      .unwrap()
+      .push(format!("{}: transform", self.name));
+    Ok(Some(TransformedSource {
+      code: format!("{}\nconsole.log('{}')", args.code, self.name),
+      map: None,
+    }))
+  }
+}
+
+#[tokio::test]
+async fn plugins_are_sorted_by_order_stably() {
+  let calls = Calls::default();
+  let bundle = bundle(
+    "driver/basic",
+    vec![
+      RecordPlugin::boxed("post-1", PluginOrder::Post, &calls),
+      RecordPlugin::boxed("normal-1", PluginOrder::Normal, &calls),
+      RecordPlugin::boxed("pre-1", PluginOrder::Pre, &calls),
+      RecordPlugin::boxed("normal-2", PluginOrder::Normal, &calls),
+      RecordPlugin::boxed("pre-2", PluginOrder::Pre, &calls),
+    ],
+  )
+  .await;
+  bundle.assets();
+  let transform_calls = calls
+    .lock()
+    .unwrap()
+    .iter()
+    .filter(|call| call.ends_with(": transform"))
+    .cloned()
+    .collect::<Vec<_>>();
+  assert_eq!(
+    transform_calls,
+    [
+      "pre-1: transform",
+      "pre-2: transform",
+      "normal-1: transform",
+      "normal-2: transform",
+      "post-1: transform",
+    ]
+  );
+}
+
+/// Serves `pkg` from a virtual module.
+#[derive(Debug)]
+struct VirtualPkgPlugin;
+
+#[async_trait::async_trait]
+impl BuildPlugin for VirtualPkgPlugin {
+  fn name(&self) -> PluginName {
+    "test:virtual-pkg".into()
+  }
+
+  async fn resolve(&self, _ctx: &mut PluginContext, args: &mut ResolveArgs) -> ResolveOutput {
+    Ok((args.specifier == "pkg").then(|| ResolvedId {
+      id: "\0pkg".to_string(),
+      external: false,
+    }))
+  }
+
+  async fn load(&self, _ctx: &mut PluginContext, args: &mut LoadArgs) -> LoadOutput {
+    Ok((args.id.as_ref() == "\0pkg").then(|| LoadedSource {
+      code: "export const pkg = 'from plugin'".to_string(),
+      loader: None,
+    }))
+  }
+}
+
+#[tokio::test]
+async fn builtin_node_resolve_runs_after_normal_plugins() {
+  // The builtin plugin is pushed after user plugins, but it's sorted by its `Post` order.
+  // So the post plugin passed before the normal plugin still runs after the normal one.
+  let calls = Calls::default();
+  let bundle = bundle(
+    "driver/node_resolve",
+    vec![
+      RecordPlugin::boxed("post", PluginOrder::Post, &calls),
+      Box::new(VirtualPkgPlugin),
+    ],
+  )
+  .await;
+  let main = bundle.code_of("main.js");
+  assert!(main.contains("from plugin"), "{main}");
+  assert!(!main.contains("from node_modules"), "{main}");
+  assert!(!calls
+    .lock()
+    .unwrap()
+    .iter()
+    .any(|call| call == "post: resolve pkg"));
+}
+
+#[tokio::test]
+async fn builtin_node_resolve_runs_after_post_plugins_passed_by_users() {
+  let calls = Calls::default();
+  let bundle = bundle(
+    "driver/node_resolve",
+    vec![RecordPlugin::boxed("post", PluginOrder::Post, &calls)],
+  )
+  .await;
+  assert!(bundle.code_of("main.js").contains("from node_modules"));
+  assert!(calls
+    .lock()
+    .unwrap()
+    .iter()
+    .any(|call| call == "post: resolve pkg"));
+}
+
+#[tokio::test]
+async fn sequential_hooks_pass_outputs_to_the_next_plugin() {
+  let calls = Calls::default();
+  let bundle = bundle(
+    "driver/basic",
+    vec![
+      RecordPlugin::boxed("first", PluginOrder::Normal, &calls),
+      RecordPlugin::boxed("second", PluginOrder::Normal, &calls),
+    ],
+  )
+  .await;
+  let main = bundle.code_of("main.js");
+  let first = main
+    .find("console.log('first')")
+    .expect("first should transform the code");
+  let second = main
+    .find("console.log('second')")
+    .expect("second should transform the code");
+  assert!(first < second, "{main}");
+}
+
+#[tokio::test]
+async fn first_hooks_stop_at_the_first_result() {
+  let calls = Calls::default();
+  let bundle = bundle(
+    "driver/node_resolve",
+    vec![
+      Box::new(VirtualPkgPlugin),
+      RecordPlugin::boxed("after", PluginOrder::Normal, &calls),
+    ],
+  )
+  .await;
+  bundle.assets();
+  let calls = calls.lock().unwrap();
+  assert!(calls.iter().any(|call| call == "after: resolve ./main.js"));
+  assert!(!calls.iter().any(|call| call == "after: resolve pkg"));
+}
+
+/// Waits in `build_start` until all plugins sharing the barrier reach it.
+#[derive(Debug)]
+struct BarrierPlugin {
+  barrier: Arc<Barrier>,
+}
+
+#[async_trait::async_trait]
+impl BuildPlugin for BarrierPlugin {
+  fn name(&self) -> PluginName {
+    "test:barrier".into()
+  }
+
+  async fn build_start(&self, _ctx: &mut PluginContext, _options: &InputOptions) -> HookNoopReturn {
+    self.barrier.wait().await;
+    Ok(())
+  }
+}
+
+#[tokio::test]
+async fn parallel_hooks_are_called_concurrently() {
+  // This would never finish if `build_start` of the second plugin waited for the first one.
+  let barrier = Arc::new(Barrier::new(2));
+  let bundle = tokio::time::timeout(
+    Duration::from_secs(10),
+    bundle(
+      "driver/basic",
+      vec![
+        Box::new(BarrierPlugin {
+          barrier: barrier.clone(),
+        }),
+        Box::new(BarrierPlugin { barrier }),
+      ],
+    ),
+  )
+  .await
+  .expect("build_start should be called concurrently");
+  bundle.assets();
+}
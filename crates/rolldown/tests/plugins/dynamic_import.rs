@@ -0,0 +1,138 @@
+use std::sync::{Arc, Mutex};
+
+use rolldown::ModuleFormat;
+use rolldown_plugin::{
+  async_trait, BuildPlugin, DynamicImportSpecifier, PluginContext, PluginName,
+  RenderDynamicImportArgs, RenderDynamicImportOutput, ResolveDynamicImportArgs, ResolveOutput,
+  ResolvedId,
+};
+
+use crate::utils::{bundle, fixture_path};
+
+/// Resolves `` import(`./locales/${lang}.js`) `` to `locales/en.js`.
+#[derive(Debug)]
+struct LocalesPlugin;
+
+#[async_trait::async_trait]
+impl BuildPlugin for LocalesPlugin {
+  fn name(&self) -> PluginName {
+    "test:locales".into()
+  }
+
+  async fn resolve_dynamic_import(
+    &self,
+    _ctx: &mut PluginContext,
+    args: &mut ResolveDynamicImportArgs,
+  ) -> ResolveOutput {
+    match args.specifier {
+      DynamicImportSpecifier::Expression(code) if code.contains("./locales/") => {
+        Ok(Some(ResolvedId {
+          id: fixture_path("dynamic_import/non_literal")
+            .join("locales/en.js")
+            .to_string_lossy()
+            .into_owned(),
+          external: false,
+        }))
+      }
+      _ => Ok(None),
+    }
+  }
+}
+
+#[tokio::test]
+async fn non_literal_import_resolved_by_plugins() {
+  let bundle = bundle("dynamic_import/non_literal", vec![Box::new(LocalesPlugin)]).await;
+  assert!(bundle.code_of("en.js").contains("'hello'"));
+  let main = bundle.code_of("main.js");
+  assert!(main.contains(r#"import("./en.js")"#), "{main}");
+  // Expressions that no plugin resolves are kept as they are
+  assert!(main.contains("`./unknown/${lang}.js`"), "{main}");
+}
+
+/// Resolves `import('lib')` to an URL.
+#[derive(Debug)]
+struct CdnPlugin;
+
+#[async_trait::async_trait]
+impl BuildPlugin for CdnPlugin {
+  fn name(&self) -> PluginName {
+    "test:cdn".into()
+  }
+
+  async fn resolve_dynamic_import(
+    &self,
+    _ctx: &mut PluginContext,
+    args: &mut ResolveDynamicImportArgs,
+  ) -> ResolveOutput {
+    match args.specifier {
+      DynamicImportSpecifier::Literal("lib") => Ok(Some(ResolvedId {
+        id: "https://cdn.example.com/lib.js".to_string(),
+        external: true,
+      })),
+      _ => Ok(None),
+    }
+  }
+}
+
+#[tokio::test]
+async fn external_dynamic_import() {
+  let bundle = bundle("dynamic_import/external", vec![Box::new(CdnPlugin)]).await;
+  assert_eq!(bundle.assets().len(), 1);
+  let main = bundle.code_of("main.js");
+  assert!(
+    main.contains(r#"import("https://cdn.example.com/lib.js")"#),
+    "{main}"
+  );
+}
+
+/// Wraps `import()` with a preload helper and records the arguments.
+#[derive(Debug, Default)]
+struct PreloadPlugin {
+  rendered: Arc<Mutex<Vec<(String, String, Option<String>, ModuleFormat)>>>,
+}
+
+#[async_trait::async_trait]
+impl BuildPlugin for PreloadPlugin {
+  fn name(&self) -> PluginName {
+    "test:preload".into()
+  }
+
+  async fn render_dynamic_import(
+    &self,
+    _ctx: &mut PluginContext,
+    args: &mut RenderDynamicImportArgs,
+  ) -> RenderDynamicImportOutput {
+    self.rendered.lock().unwrap().push((
+      args.specifier.to_string(),
+      args.resolved_id.to_string(),
+      args.target_filename.map(|s| s.to_string()),
+      args.format,
+    ));
+    let target = args.target_filename.unwrap_or(args.specifier);
+    Ok(Some(format!("__preload(() => import('{target}'))")))
+  }
+}
+
+#[tokio::test]
+async fn dynamic_import_rendered_by_plugins() {
+  let rendered = Arc::new(Mutex::new(vec![]));
+  let bundle = bundle(
+    "dynamic_import/rendered",
+    vec![Box::new(PreloadPlugin {
+      rendered: rendered.clone(),
+    })],
+  )
+  .await;
+  let main = bundle.code_of("main.js");
+  // The rendered code is reprinted, so only check the parts of it
+  assert!(main.contains("__preload("), "{main}");
+  assert!(main.contains("import('./lazy.js')"), "{main}");
+
+  let rendered = rendered.lock().unwrap();
+  assert_eq!(rendered.len(), 1);
+  let (specifier, resolved_id, target_filename, format) = &rendered[0];
+  assert_eq!(specifier, "./lazy.js");
+  assert!(resolved_id.ends_with("lazy.js"));
+  assert_eq!(target_filename.as_deref(), Some("./lazy.js"));
+  assert_eq!(*format, ModuleFormat::Esm);
+}
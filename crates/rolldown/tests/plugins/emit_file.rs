@@ -0,0 +1,226 @@
+use rolldown::{AssetContent, FileNameTemplate, OutputOptions};
+use rolldown_error::ErrorKind;
+use rolldown_plugin::{
+  async_trait, BuildPlugin, EmittedFile, HookKind, HookNoopReturn, InputOptions, LoadArgs,
+  LoadOutput, LoadedSource, Loader, OutputBundle, PluginContext, PluginName, RenderChunkArgs,
+  RenderChunkOutput,
+};
+
+use crate::utils::{bundle, bundle_with};
+
+// Not valid UTF-8, so it would be corrupted if it's converted to a string anywhere.
+const BINARY: &[u8] = &[0xff, 0x00, 0xfe, 0x80, 0x0a];
+
+/// Emits `files` in the hook of `at`.
+#[derive(Debug)]
+struct EmitFilePlugin {
+  at: HookKind,
+  files: Vec<EmittedFile>,
+}
+
+impl EmitFilePlugin {
+  fn emit_at(&self, ctx: &PluginContext, hook: HookKind) -> HookNoopReturn {
+    if self.at == hook {
+      for file in &self.files {
+        ctx.emit_file(file.clone())?;
+      }
+    }
+    Ok(())
+  }
+}
+
+#[async_trait::async_trait]
+impl BuildPlugin for EmitFilePlugin {
+  fn name(&self) -> PluginName {
+    "test:emit-file".into()
+  }
+
+  async fn build_start(&self, ctx: &mut PluginContext, _options: &InputOptions) -> HookNoopReturn {
+    self.emit_at(ctx, HookKind::BuildStart)
+  }
+
+  async fn render_chunk(
+    &self,
+    ctx: &mut PluginContext,
+    _args: &mut RenderChunkArgs,
+  ) -> RenderChunkOutput {
+    self.emit_at(ctx, HookKind::RenderChunk)?;
+    Ok(None)
+  }
+
+  async fn generate_bundle(
+    &self,
+    ctx: &mut PluginContext,
+    _bundle: &mut OutputBundle,
+  ) -> HookNoopReturn {
+    self.emit_at(ctx, HookKind::GenerateBundle)
+  }
+}
+
+/// Loads `.png` files with `Loader::File`.
+#[derive(Debug)]
+struct FileLoaderPlugin;
+
+#[async_trait::async_trait]
+impl BuildPlugin for FileLoaderPlugin {
+  fn name(&self) -> PluginName {
+    "test:file-loader".into()
+  }
+
+  async fn load(&self, _ctx: &mut PluginContext, args: &mut LoadArgs) -> LoadOutput {
+    let id = args.id.as_ref();
+    Ok(id.ends_with(".png").then(|| LoadedSource {
+      code: std::fs::read_to_string(id).expect("The fixture should be readable"),
+      loader: Some(Loader::File),
+    }))
+  }
+}
+
+fn binary_asset(name: &str, source: &[u8]) -> EmittedFile {
+  EmittedFile::Asset {
+    name: name.to_string(),
+    source: source.to_vec(),
+  }
+}
+
+#[tokio::test]
+async fn binary_assets_are_kept_as_bytes() {
+  let bundle = bundle(
+    "emit_file",
+    vec![Box::new(EmitFilePlugin {
+      at: HookKind::BuildStart,
+      files: vec![binary_asset("logo.bin", BINARY)],
+    })],
+  )
+  .await;
+  let asset = bundle
+    .assets()
+    .iter()
+    .find(|asset| asset.filename.starts_with("assets/logo-"))
+    .expect("The asset should be emitted");
+  assert!(asset.filename.ends_with(".bin"), "{}", asset.filename);
+  assert!(matches!(&asset.content, AssetContent::Binary(bytes) if bytes == BINARY));
+}
+
+#[tokio::test]
+async fn assets_with_the_same_name_are_told_apart_by_the_hash() {
+  let bundle = bundle(
+    "emit_file",
+    vec![Box::new(EmitFilePlugin {
+      at: HookKind::BuildStart,
+      files: vec![
+        binary_asset("logo.bin", BINARY),
+        binary_asset("logo.bin", b"another logo"),
+        // Emitting the same file twice only outputs it once
+        binary_asset("logo.bin", BINARY),
+      ],
+    })],
+  )
+  .await;
+  let logos = bundle
+    .assets()
+    .iter()
+    .filter(|asset| asset.filename.starts_with("assets/logo-"))
+    .collect::<Vec<_>>();
+  assert_eq!(logos.len(), 2);
+  assert_ne!(logos[0].filename, logos[1].filename);
+}
+
+#[tokio::test]
+async fn conflicting_asset_filenames_are_an_error() {
+  let bundle = bundle_with(
+    "emit_file",
+    vec![Box::new(EmitFilePlugin {
+      at: HookKind::BuildStart,
+      files: vec![
+        binary_asset("logo.bin", BINARY),
+        binary_asset("logo.bin", b"another logo"),
+      ],
+    })],
+    OutputOptions {
+      asset_file_names: FileNameTemplate::from("assets/[name][extname]".to_string()),
+      ..Default::default()
+    },
+  )
+  .await;
+  let error = bundle.error();
+  assert!(
+    matches!(&error.kind, ErrorKind::FileNameConflict { file_name } if file_name == "assets/logo.bin"),
+    "{error}"
+  );
+}
+
+#[tokio::test]
+async fn assets_emitted_in_generate_bundle_are_in_the_output() {
+  let bundle = bundle(
+    "emit_file",
+    vec![Box::new(EmitFilePlugin {
+      at: HookKind::GenerateBundle,
+      files: vec![binary_asset("stats.bin", BINARY)],
+    })],
+  )
+  .await;
+  assert!(bundle
+    .assets()
+    .iter()
+    .any(|asset| asset.filename.starts_with("assets/stats-")));
+}
+
+#[tokio::test]
+async fn chunks_emitted_in_build_start_are_entries() {
+  let bundle = bundle(
+    "emit_file",
+    vec![Box::new(EmitFilePlugin {
+      at: HookKind::BuildStart,
+      files: vec![EmittedFile::Chunk {
+        id: "./extra.js".to_string(),
+        name: None,
+      }],
+    })],
+  )
+  .await;
+  assert!(bundle.code_of("extra.js").contains("console.log('extra')"));
+}
+
+#[tokio::test]
+async fn chunks_can_not_be_emitted_after_module_loading() {
+  let bundle = bundle(
+    "emit_file",
+    vec![Box::new(EmitFilePlugin {
+      at: HookKind::RenderChunk,
+      files: vec![EmittedFile::Chunk {
+        id: "./extra.js".to_string(),
+        name: None,
+      }],
+    })],
+  )
+  .await;
+  let error = bundle.error();
+  assert!(
+    matches!(error.kind, ErrorKind::EmitChunkAfterModuleLoading),
+    "{error}"
+  );
+}
+
+#[tokio::test]
+async fn files_are_named_by_asset_file_names_and_referenced_relative_to_the_chunk() {
+  let bundle = bundle_with(
+    "emit_file/file_loader",
+    vec![Box::new(FileLoaderPlugin)],
+    OutputOptions {
+      entry_file_names: FileNameTemplate::from("js/[name].js".to_string()),
+      asset_file_names: FileNameTemplate::from("static/[name]-[hash][extname]".to_string()),
+      ..Default::default()
+    },
+  )
+  .await;
+  let icon = bundle
+    .assets()
+    .iter()
+    .find(|asset| asset.filename.starts_with("static/icon-"))
+    .expect("The file should be emitted");
+  assert!(icon.filename.ends_with(".png"), "{}", icon.filename);
+  let code = bundle.code_of("js/main.js");
+  assert!(code.contains(&format!("../{}", icon.filename)), "{code}");
+  assert!(!code.contains("__ROLLDOWN_ASSET_URL_"), "{code}");
+}
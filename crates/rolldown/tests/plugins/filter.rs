@@ -0,0 +1,102 @@
+use std::sync::{Arc, Mutex};
+
+use rolldown_plugin::{
+  async_trait, BuildPlugin, FilterPattern, HookFilter, HookKind, LoadArgs, LoadOutput,
+  PluginContext, PluginName, ResolveArgs, ResolveOutput, TransformArgs, TransformOutput,
+};
+
+use crate::utils::bundle;
+
+/// Only cares about `included.js` and records ids passed to its hooks.
+#[derive(Debug)]
+struct FilteredPlugin {
+  filter: HookFilter,
+  calls: Arc<Mutex<Vec<String>>>,
+}
+
+impl FilteredPlugin {
+  fn record(&self, hook: &str, id: &str) {
+    let name = id.rsplit('/').next().unwrap_or(id);
+    self.calls.lock().unwrap().push(format!("{hook} {name}"));
+  }
+}
+
+#[async_trait::async_trait]
+impl BuildPlugin for FilteredPlugin {
+  fn name(&self) -> PluginName {
+    "test:filtered".into()
+  }
+
+  fn filter(&self, hook: HookKind) -> Option<&HookFilter> {
+    matches!(
+      hook,
+      HookKind::Resolve | HookKind::Load | HookKind::Transform
+    )
+    .then_some(&self.filter)
+  }
+
+  async fn resolve(&self, _ctx: &mut PluginContext, args: &mut ResolveArgs) -> ResolveOutput {
+    self.record("resolve", args.specifier);
+    Ok(None)
+  }
+
+  async fn load(&self, _ctx: &mut PluginContext, args: &mut LoadArgs) -> LoadOutput {
+    self.record("load", args.id.as_ref());
+    Ok(None)
+  }
+
+  async fn transform(&self, _ctx: &mut PluginContext, args: &mut TransformArgs) -> TransformOutput {
+    self.record("transform", args.id.as_ref());
+    Ok(None)
+  }
+}
+
+#[tokio::test]
+async fn hooks_are_not_called_for_filtered_out_ids() {
+  let calls = Arc::new(Mutex::new(vec![]));
+  let bundle = bundle(
+    "filter",
+    vec![Box::new(FilteredPlugin {
+      filter: HookFilter {
+        include: vec![FilterPattern::regex(r"included\.js$").unwrap()],
+        exclude: vec![],
+      },
+      calls: calls.clone(),
+    })],
+  )
+  .await;
+  bundle.assets();
+  let mut calls = calls.lock().unwrap().clone();
+  calls.sort();
+  assert_eq!(
+    calls,
+    [
+      "load included.js",
+      "resolve included.js",
+      "transform included.js"
+    ]
+  );
+}
+
+#[tokio::test]
+async fn exclude_takes_precedence_over_include() {
+  let calls = Arc::new(Mutex::new(vec![]));
+  let bundle = bundle(
+    "filter",
+    vec![Box::new(FilteredPlugin {
+      filter: HookFilter {
+        include: vec![FilterPattern::glob("**/*.js").unwrap()],
+        exclude: vec![FilterPattern::regex("excluded").unwrap()],
+      },
+      calls: calls.clone(),
+    })],
+  )
+  .await;
+  bundle.assets();
+  let calls = calls.lock().unwrap();
+  assert!(!calls.is_empty());
+  assert!(
+    calls.iter().all(|call| !call.ends_with("excluded.js")),
+    "{calls:?}"
+  );
+}
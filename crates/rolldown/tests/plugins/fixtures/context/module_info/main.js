@@ -0,0 +1,3 @@
+import { foo } from './foo.js'
+import('./lazy.js')
+console.log(foo)
@@ -0,0 +1,2 @@
+import { pkg } from 'pkg'
+console.log(pkg)
@@ -0,0 +1 @@
+import('lib').then((m) => console.log(m))
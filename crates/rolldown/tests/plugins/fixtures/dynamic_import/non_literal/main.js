@@ -0,0 +1,3 @@
+const lang = 'en'
+import(`./locales/${lang}.js`).then((m) => console.log(m.default))
+import(`./unknown/${lang}.js`).then((m) => console.log(m.default))
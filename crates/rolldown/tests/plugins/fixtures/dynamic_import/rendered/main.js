@@ -0,0 +1 @@
+import('./lazy.js').then((m) => console.log(m.default))
@@ -0,0 +1,3 @@
+import icon from './icon.png'
+
+console.log(icon)
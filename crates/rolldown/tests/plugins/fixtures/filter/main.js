@@ -0,0 +1,2 @@
+import './included.js'
+import './excluded.js'
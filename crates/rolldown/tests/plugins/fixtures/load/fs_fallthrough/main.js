@@ -0,0 +1,3 @@
+import { foo } from './foo'
+import { bar } from 'virtual:bar'
+console.log(foo, bar)
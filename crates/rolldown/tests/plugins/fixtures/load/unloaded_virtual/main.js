@@ -0,0 +1,2 @@
+import { missing } from 'virtual:missing'
+console.log(missing)
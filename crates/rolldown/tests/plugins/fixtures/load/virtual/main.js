@@ -0,0 +1,2 @@
+import { greeting } from 'virtual:greeting'
+console.log(greeting)
@@ -0,0 +1,3 @@
+import { greeting } from 'virtual:greeting'
+console.log(greeting)
+import('virtual:lazy')
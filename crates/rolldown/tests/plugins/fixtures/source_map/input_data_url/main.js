@@ -0,0 +1,3 @@
+const answer = 42;
+console.log(answer);
+//# sourceMappingURL=data:application/json;charset=utf-8;base64,eyJ2ZXJzaW9uIjozLCJmaWxlIjoibWFpbi5qcyIsInNvdXJjZXMiOlsic3JjL21haW4udHMiXSwic291cmNlc0NvbnRlbnQiOlsiY29uc3QgYW5zd2VyOiBudW1iZXIgPSA0MlxuY29uc29sZS5sb2coYW5zd2VyKVxuIl0sIm5hbWVzIjpbXSwibWFwcGluZ3MiOiJBQUFBO0FBQ0EifQ==
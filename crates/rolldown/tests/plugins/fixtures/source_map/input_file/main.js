@@ -0,0 +1,3 @@
+const answer = 42;
+console.log(answer);
+//# sourceMappingURL=main.js.map
@@ -0,0 +1,143 @@
+use std::sync::{Arc, Mutex};
+
+use rolldown_error::{Error as BuildError, Errors};
+use rolldown_plugin::{
+  async_trait, BuildPlugin, HookKind, HookNoopReturn, InputOptions, OutputBundle, PluginContext,
+  PluginName, RenderChunkArgs, RenderChunkOutput,
+};
+
+use crate::utils::bundle;
+
+/// Records calls of lifecycle hooks and fails at `fail_at`.
+#[derive(Debug, Default)]
+struct LifecyclePlugin {
+  calls: Arc<Mutex<Vec<&'static str>>>,
+  fail_at: Option<HookKind>,
+}
+
+impl LifecyclePlugin {
+  fn record(&self, hook: HookKind, call: &'static str) -> HookNoopReturn {
+    self.calls.lock().unwrap().push(call);
+    if self.fail_at == Some(hook) {
+      Err(BuildError::panic(format!("{call} failed")))
+    } else {
+      Ok(())
+    }
+  }
+}
+
+#[async_trait::async_trait]
+impl BuildPlugin for LifecyclePlugin {
+  fn name(&self) -> PluginName {
+    "test:lifecycle".into()
+  }
+
+  async fn build_start(&self, _ctx: &mut PluginContext, _options: &InputOptions) -> HookNoopReturn {
+    self.record(HookKind::BuildStart, "build_start")
+  }
+
+  async fn build_end(&self, _ctx: &mut PluginContext, errors: Option<&Errors>) -> HookNoopReturn {
+    match errors {
+      Some(_) => self.record(HookKind::BuildEnd, "build_end with errors"),
+      None => self.record(HookKind::BuildEnd, "build_end"),
+    }
+  }
+
+  async fn render_chunk(
+    &self,
+    _ctx: &mut PluginContext,
+    _args: &mut RenderChunkArgs,
+  ) -> RenderChunkOutput {
+    self
+      .record(HookKind::RenderChunk, "render_chunk")
+      .map(|_| None)
+  }
+
+  async fn generate_bundle(
+    &self,
+    _ctx: &mut PluginContext,
+    _bundle: &mut OutputBundle,
+  ) -> HookNoopReturn {
+    self.record(HookKind::GenerateBundle, "generate_bundle")
+  }
+
+  async fn close_bundle(&self, _ctx: &mut PluginContext) -> HookNoopReturn {
+    self.record(HookKind::CloseBundle, "close_bundle")
+  }
+}
+
+async fn calls_of(fixture: &str, fail_at: Option<HookKind>) -> Vec<&'static str> {
+  let calls = Arc::new(Mutex::new(vec![]));
+  let bundle = bundle(
+    fixture,
+    vec![Box::new(LifecyclePlugin {
+      calls: calls.clone(),
+      fail_at,
+    })],
+  )
+  .await;
+  assert_eq!(bundle.output.is_ok(), fail_at.is_none());
+  let calls = calls.lock().unwrap();
+  calls.clone()
+}
+
+#[tokio::test]
+async fn hooks_are_called_in_order() {
+  assert_eq!(
+    calls_of("lifecycle/basic", None).await,
+    [
+      "build_start",
+      "build_end",
+      "render_chunk",
+      "generate_bundle",
+      "close_bundle"
+    ]
+  );
+}
+
+#[tokio::test]
+async fn build_end_is_called_if_build_start_fails() {
+  assert_eq!(
+    calls_of("lifecycle/basic", Some(HookKind::BuildStart)).await,
+    ["build_start", "build_end with errors", "close_bundle"]
+  );
+}
+
+#[tokio::test]
+async fn build_end_is_called_if_module_graph_fails() {
+  let calls = Arc::new(Mutex::new(vec![]));
+  let bundle = bundle(
+    "lifecycle/missing_module",
+    vec![Box::new(LifecyclePlugin {
+      calls: calls.clone(),
+      fail_at: None,
+    })],
+  )
+  .await;
+  assert!(bundle.output.is_err());
+  assert_eq!(
+    *calls.lock().unwrap(),
+    ["build_start", "build_end with errors", "close_bundle"]
+  );
+}
+
+#[tokio::test]
+async fn close_bundle_is_called_if_output_fails() {
+  assert_eq!(
+    calls_of("lifecycle/basic", Some(HookKind::RenderChunk)).await,
+    ["build_start", "build_end", "render_chunk", "close_bundle"]
+  );
+}
+
+#[tokio::test]
+async fn error_of_build_start_is_returned() {
+  let bundle = bundle(
+    "lifecycle/basic",
+    vec![Box::new(LifecyclePlugin {
+      fail_at: Some(HookKind::BuildStart),
+      ..Default::default()
+    })],
+  )
+  .await;
+  assert_eq!(bundle.error().kind.to_string(), "build_start failed");
+}
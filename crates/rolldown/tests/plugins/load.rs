@@ -0,0 +1,109 @@
+use std::sync::{Arc, Mutex};
+
+use rolldown_error::ErrorKind;
+use rolldown_plugin::{
+  async_trait, BuildPlugin, LoadArgs, LoadOutput, LoadedSource, PluginContext, PluginName,
+  ResolveArgs, ResolveOutput, ResolvedId,
+};
+
+use crate::utils::bundle;
+
+/// Resolves `virtual:*` to `\0virtual:*` and loads the ones in `modules`.
+#[derive(Debug, Default)]
+pub(crate) struct VirtualModulesPlugin {
+  pub(crate) modules: Vec<(&'static str, &'static str)>,
+  pub(crate) loaded_ids: Arc<Mutex<Vec<String>>>,
+}
+
+#[async_trait::async_trait]
+impl BuildPlugin for VirtualModulesPlugin {
+  fn name(&self) -> PluginName {
+    "test:virtual-modules".into()
+  }
+
+  async fn resolve(&self, _ctx: &mut PluginContext, args: &mut ResolveArgs) -> ResolveOutput {
+    Ok(args.specifier.starts_with("virtual:").then(|| ResolvedId {
+      id: format!("\0{}", args.specifier),
+      external: false,
+    }))
+  }
+
+  async fn load(&self, _ctx: &mut PluginContext, args: &mut LoadArgs) -> LoadOutput {
+    let id = args.id.as_ref();
+    self.loaded_ids.lock().unwrap().push(id.to_string());
+    Ok(
+      self
+        .modules
+        .iter()
+        .find(|(virtual_id, _)| id.strip_prefix('\0') == Some(*virtual_id))
+        .map(|(_, code)| LoadedSource {
+          code: code.to_string(),
+          loader: None,
+        }),
+    )
+  }
+}
+
+#[tokio::test]
+async fn load_serves_virtual_modules() {
+  let bundle = bundle(
+    "load/virtual",
+    vec![Box::new(VirtualModulesPlugin {
+      modules: vec![(
+        "virtual:greeting",
+        "export const greeting = 'hello from load'",
+      )],
+      ..Default::default()
+    })],
+  )
+  .await;
+
+  let code = bundle.code_of("main.js");
+  assert!(
+    code.contains("const greeting = 'hello from load';"),
+    "{code}"
+  );
+  assert!(code.contains("console.log(greeting);"), "{code}");
+  assert!(bundle.warnings.is_empty());
+}
+
+#[tokio::test]
+async fn load_falls_through_to_fs() {
+  let loaded_ids = Arc::new(Mutex::new(vec![]));
+  let bundle = bundle(
+    "load/fs_fallthrough",
+    vec![Box::new(VirtualModulesPlugin {
+      modules: vec![("virtual:bar", "export const bar = 'bar'")],
+      loaded_ids: loaded_ids.clone(),
+    })],
+  )
+  .await;
+
+  let code = bundle.code_of("main.js");
+  assert!(code.contains("const foo = 'read from disk';"), "{code}");
+  assert!(code.contains("const bar = 'bar';"), "{code}");
+
+  // The plugin is asked for every module, including the ones read from the disk.
+  let mut loaded_ids = loaded_ids.lock().unwrap().clone();
+  loaded_ids.sort();
+  assert_eq!(loaded_ids.len(), 3);
+  assert_eq!(loaded_ids[0], "\0virtual:bar");
+  assert!(loaded_ids[1].ends_with("foo.js"), "{loaded_ids:?}");
+  assert!(loaded_ids[2].ends_with("main.js"), "{loaded_ids:?}");
+}
+
+#[tokio::test]
+async fn unloaded_virtual_module_is_an_error() {
+  // `virtual:missing` is resolved but not loaded, and `\0`-prefixed ids are never read from the disk.
+  let bundle = bundle(
+    "load/unloaded_virtual",
+    vec![Box::new(VirtualModulesPlugin::default())],
+  )
+  .await;
+
+  let error = bundle.error();
+  assert!(
+    matches!(&error.kind, ErrorKind::UnloadedVirtualModule { id } if id.to_string_lossy() == "\0virtual:missing"),
+    "{error:?}"
+  );
+}
@@ -0,0 +1,13 @@
+// Plugins can't be declared in `test.config.json`, so hooks are tested by bundling fixtures with plugins written in Rust.
+
+mod context;
+mod driver;
+mod dynamic_import;
+mod emit_file;
+mod filter;
+mod lifecycle;
+mod load;
+mod module_id;
+mod output_hooks;
+mod source_map;
+mod utils;
@@ -0,0 +1,26 @@
+use crate::{load::VirtualModulesPlugin, utils::bundle};
+
+#[tokio::test]
+async fn virtual_ids_are_kept_in_output() {
+  let bundle = bundle(
+    "module_id/virtual",
+    vec![Box::new(VirtualModulesPlugin {
+      modules: vec![
+        ("virtual:greeting", "export const greeting = 'hello'"),
+        ("virtual:lazy", "export const lazy = true"),
+      ],
+      ..Default::default()
+    })],
+  )
+  .await;
+
+  // The `\0` prefix is escaped in the comment header of the module
+  let main = bundle.code_of("main.js");
+  assert!(main.contains("// \\0virtual:greeting\n"), "{main}");
+  assert!(main.contains("\"./virtual_lazy.js\""), "{main}");
+
+  // `\0virtual:lazy` is named as `virtual_lazy` instead of being treated as a path
+  let lazy = bundle.code_of("virtual_lazy.js");
+  assert!(lazy.contains("// \\0virtual:lazy\n"), "{lazy}");
+  assert!(lazy.contains("const lazy = true;"), "{lazy}");
+}
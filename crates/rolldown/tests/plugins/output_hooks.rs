@@ -0,0 +1,105 @@
+use std::sync::{Arc, Mutex};
+
+use rolldown::Asset;
+use rolldown_plugin::{
+  async_trait, BuildPlugin, ChunkInfo, HookNoopReturn, OutputBundle, PluginContext, PluginName,
+  RenderChunkArgs, RenderChunkOutput,
+};
+
+use crate::utils::bundle;
+
+/// Prepends the name of the chunk and records `ChunkInfo`s.
+#[derive(Debug, Default)]
+struct BannerPlugin {
+  chunks: Arc<Mutex<Vec<ChunkInfo>>>,
+}
+
+#[async_trait::async_trait]
+impl BuildPlugin for BannerPlugin {
+  fn name(&self) -> PluginName {
+    "test:banner".into()
+  }
+
+  async fn render_chunk(
+    &self,
+    _ctx: &mut PluginContext,
+    args: &mut RenderChunkArgs,
+  ) -> RenderChunkOutput {
+    self.chunks.lock().unwrap().push(args.chunk.clone());
+    Ok(Some(
+      format!("/* {} */\n{}", args.chunk.name, args.code).into(),
+    ))
+  }
+}
+
+/// Adds `manifest.json` listing the outputs.
+#[derive(Debug)]
+struct ManifestPlugin;
+
+#[async_trait::async_trait]
+impl BuildPlugin for ManifestPlugin {
+  fn name(&self) -> PluginName {
+    "test:manifest".into()
+  }
+
+  async fn generate_bundle(
+    &self,
+    _ctx: &mut PluginContext,
+    bundle: &mut OutputBundle,
+  ) -> HookNoopReturn {
+    let mut filenames = bundle
+      .iter()
+      .map(|asset| {
+        // `render_chunk` has been called for chunks in the bundle
+        assert!(asset.content.to_string_lossy().starts_with("/* "));
+        format!("{:?}", asset.filename)
+      })
+      .collect::<Vec<_>>();
+    filenames.sort();
+    bundle.push(Asset {
+      filename: "manifest.json".to_string(),
+      content: format!("[{}]", filenames.join(",")).into(),
+    });
+    Ok(())
+  }
+}
+
+#[tokio::test]
+async fn render_chunk_and_generate_bundle() {
+  let chunks = Arc::new(Mutex::new(vec![]));
+  let bundle = bundle(
+    "output_hooks",
+    vec![
+      Box::new(BannerPlugin {
+        chunks: chunks.clone(),
+      }),
+      Box::new(ManifestPlugin),
+    ],
+  )
+  .await;
+
+  assert!(bundle.code_of("main.js").starts_with("/* main */\n"));
+  assert!(bundle.code_of("lazy.js").starts_with("/* lazy */\n"));
+  assert_eq!(bundle.code_of("manifest.json"), r#"["lazy.js","main.js"]"#);
+
+  let mut chunks = chunks.lock().unwrap().clone();
+  chunks.sort_by(|a, b| a.name.cmp(&b.name));
+  assert_eq!(chunks.len(), 2, "{chunks:#?}");
+  let (lazy, main) = (&chunks[0], &chunks[1]);
+
+  assert_eq!(main.filename, "main.js");
+  assert!(main.is_entry);
+  assert!(!main.is_dynamic_entry);
+  let facade_module_id = main.facade_module_id.as_ref().unwrap();
+  assert!(facade_module_id.id().ends_with("main.js"));
+  assert_eq!(main.module_ids.len(), 1);
+  assert_eq!(main.exports, ["foo"]);
+
+  assert_eq!(lazy.filename, "lazy.js");
+  assert!(!lazy.is_entry);
+  assert!(lazy.is_dynamic_entry);
+  let facade_module_id = lazy.facade_module_id.as_ref().unwrap();
+  assert!(facade_module_id.id().ends_with("lazy.js"));
+  assert_eq!(lazy.module_ids.len(), 1);
+  assert_eq!(lazy.exports, ["default"]);
+}
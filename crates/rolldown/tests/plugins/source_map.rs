@@ -0,0 +1,268 @@
+use rolldown::{OutputOptions, SourceMapType};
+use rolldown_plugin::{
+  async_trait, BuildPlugin, PluginContext, PluginName, RenderChunkArgs, RenderChunkOutput,
+  SourceMap, TransformArgs, TransformOutput, TransformedSource,
+};
+
+use crate::utils::{bundle_with, TestBundle};
+
+/// Prepends `console.log('<name>')` and returns a source map that maps the original lines to one line below.
+#[derive(Debug)]
+struct PrependLinePlugin {
+  name: &'static str,
+}
+
+#[async_trait::async_trait]
+impl BuildPlugin for PrependLinePlugin {
+  fn name(&self) -> PluginName {
+    self.name.into()
+  }
+
+  async fn transform(&self, _ctx: &mut PluginContext, args: &mut TransformArgs) -> TransformOutput {
+    // The first line is new. The second and third lines map to the first and second lines of the input.
+    let map = format!(
+      r#"{{"version":3,"sources":[{:?}],"names":[],"mappings":";AAAA;AACA"}}"#,
+      args.id.as_ref()
+    );
+    Ok(Some(TransformedSource {
+      code: format!("console.log('{}')\n{}", self.name, args.code),
+      map: Some(SourceMap::from_slice(map.as_bytes()).unwrap()),
+    }))
+  }
+}
+
+#[tokio::test]
+async fn maps_of_chained_transforms_point_at_the_original_source() {
+  let bundle = bundle_with(
+    "source_map/transform",
+    vec![
+      Box::new(PrependLinePlugin { name: "first" }),
+      Box::new(PrependLinePlugin { name: "second" }),
+    ],
+    OutputOptions {
+      sourcemap: Some(SourceMapType::File),
+      ..Default::default()
+    },
+  )
+  .await;
+  let code = bundle.code_of("main.js");
+  let map = bundle.source_map_of("main.js");
+  assert_answer_is_mapped(&code, &map);
+
+  // Lines added by transforms have no original positions
+  let (added_line, _) = code
+    .lines()
+    .enumerate()
+    .find(|(_, line)| line.contains("console.log('second')"))
+    .expect("The line added by the second plugin should be in the output");
+  assert!(map
+    .tokens()
+    .all(|token| token.get_dst_line() != added_line as u32));
+}
+
+/// `console.log(answer)` should map to the second line of the original `main.js`.
+fn assert_answer_is_mapped(code: &str, map: &SourceMap) {
+  assert_answer_is_mapped_to(code, map, "main.js")
+}
+
+/// `console.log(answer)` should map to the second line of the source ending with `source`.
+fn assert_answer_is_mapped_to(code: &str, map: &SourceMap, source: &str) {
+  let (line, line_code) = code
+    .lines()
+    .enumerate()
+    .find(|(_, line)| line.contains("console.log(answer)"))
+    .expect("console.log(answer) should be in the output");
+  let col = line_code.find("console.log(answer)").unwrap();
+  let token = map
+    .lookup_token(line as u32, col as u32)
+    .expect("The statement should be mapped");
+  assert_eq!(token.get_dst_line(), line as u32);
+  let token_source = token.get_source().unwrap();
+  assert!(token_source.ends_with(source), "{token_source}");
+  assert_eq!((token.get_src_line(), token.get_src_col()), (1, 0));
+}
+
+async fn bundle_with_sourcemap(
+  sourcemap: SourceMapType,
+  plugins: Vec<Box<dyn BuildPlugin>>,
+) -> TestBundle {
+  bundle_fixture_with_sourcemap("source_map/transform", sourcemap, plugins).await
+}
+
+async fn bundle_fixture_with_sourcemap(
+  fixture: &str,
+  sourcemap: SourceMapType,
+  plugins: Vec<Box<dyn BuildPlugin>>,
+) -> TestBundle {
+  bundle_with(
+    fixture,
+    plugins,
+    OutputOptions {
+      sourcemap: Some(sourcemap),
+      ..Default::default()
+    },
+  )
+  .await
+}
+
+#[tokio::test]
+async fn sourcemap_file() {
+  let bundle = bundle_with_sourcemap(SourceMapType::File, vec![]).await;
+  let code = bundle.code_of("main.js");
+  assert!(
+    code.ends_with("//# sourceMappingURL=main.js.map\n"),
+    "{code}"
+  );
+  assert_answer_is_mapped(&code, &bundle.source_map_of("main.js"));
+}
+
+#[tokio::test]
+async fn sourcemap_inline() {
+  let bundle = bundle_with_sourcemap(SourceMapType::Inline, vec![]).await;
+  let code = bundle.code_of("main.js");
+  assert!(bundle
+    .assets()
+    .iter()
+    .all(|asset| asset.filename != "main.js.map"));
+
+  let prefix = "//# sourceMappingURL=data:application/json;charset=utf-8;base64,";
+  let url = code
+    .lines()
+    .last()
+    .and_then(|line| line.strip_prefix(prefix))
+    .unwrap_or_else(|| panic!("The last line should be an inline source map:\n{code}"));
+  let json = base64::decode(url).expect("The source map should be encoded in base64");
+  let map = SourceMap::from_slice(&json).expect("The source map should be valid");
+  assert_answer_is_mapped(&code, &map);
+}
+
+#[tokio::test]
+async fn sourcemap_hidden() {
+  let bundle = bundle_with_sourcemap(SourceMapType::Hidden, vec![]).await;
+  let code = bundle.code_of("main.js");
+  assert!(!code.contains("sourceMappingURL"), "{code}");
+  assert_answer_is_mapped(&code, &bundle.source_map_of("main.js"));
+}
+
+/// Prepends `console.log('banner')` to chunks, optionally with a source map for the new line.
+#[derive(Debug)]
+struct BannerPlugin {
+  with_map: bool,
+}
+
+#[async_trait::async_trait]
+impl BuildPlugin for BannerPlugin {
+  fn name(&self) -> PluginName {
+    "test:banner".into()
+  }
+
+  async fn render_chunk(
+    &self,
+    _ctx: &mut PluginContext,
+    args: &mut RenderChunkArgs,
+  ) -> RenderChunkOutput {
+    let code = format!("console.log('banner')\n{}", args.code);
+    let map = self.with_map.then(|| {
+      // The first line is new and every other line moves one line down
+      let mappings = format!(
+        ";AAAA{}",
+        ";AACA".repeat(args.code.lines().count().saturating_sub(1))
+      );
+      let map = format!(
+        r#"{{"version":3,"sources":[{:?}],"names":[],"mappings":"{mappings}"}}"#,
+        args.chunk.filename
+      );
+      SourceMap::from_slice(map.as_bytes()).unwrap()
+    });
+    Ok(Some(TransformedSource { code, map }))
+  }
+}
+
+#[tokio::test]
+async fn render_chunk_map_is_collapsed_onto_the_chunk_map() {
+  let bundle = bundle_with_sourcemap(
+    SourceMapType::File,
+    vec![Box::new(BannerPlugin { with_map: true })],
+  )
+  .await;
+  let code = bundle.code_of("main.js");
+  assert!(code.starts_with("console.log('banner')\n"), "{code}");
+  assert!(bundle.warnings.is_empty());
+  assert_answer_is_mapped(&code, &bundle.source_map_of("main.js"));
+}
+
+#[tokio::test]
+async fn render_chunk_without_map_drops_the_chunk_map() {
+  let bundle = bundle_with_sourcemap(
+    SourceMapType::File,
+    vec![Box::new(BannerPlugin { with_map: false })],
+  )
+  .await;
+  let code = bundle.code_of("main.js");
+  assert!(!code.contains("sourceMappingURL"), "{code}");
+  assert!(bundle
+    .assets()
+    .iter()
+    .all(|asset| asset.filename != "main.js.map"));
+  assert_eq!(bundle.warnings.len(), 1);
+  let warning = &bundle.warnings[0];
+  assert_eq!(warning.kind.code(), "SOURCEMAP_BROKEN");
+  assert!(
+    warning.to_string().contains("plugin: test:banner"),
+    "{warning}"
+  );
+}
+
+#[tokio::test]
+async fn input_map_from_data_url() {
+  let bundle =
+    bundle_fixture_with_sourcemap("source_map/input_data_url", SourceMapType::File, vec![]).await;
+  assert!(bundle.warnings.is_empty());
+  let code = bundle.code_of("main.js");
+  assert_answer_is_mapped_to(&code, &bundle.source_map_of("main.js"), "src/main.ts");
+}
+
+#[tokio::test]
+async fn input_map_from_sibling_file() {
+  let bundle =
+    bundle_fixture_with_sourcemap("source_map/input_file", SourceMapType::File, vec![]).await;
+  assert!(bundle.warnings.is_empty());
+  let code = bundle.code_of("main.js");
+  assert_answer_is_mapped_to(&code, &bundle.source_map_of("main.js"), "src/main.ts");
+}
+
+#[tokio::test]
+async fn input_map_through_transforms() {
+  let bundle = bundle_fixture_with_sourcemap(
+    "source_map/input_file",
+    SourceMapType::File,
+    vec![Box::new(PrependLinePlugin { name: "first" })],
+  )
+  .await;
+  let code = bundle.code_of("main.js");
+  assert!(code.contains("console.log('first')"), "{code}");
+  assert_answer_is_mapped_to(&code, &bundle.source_map_of("main.js"), "src/main.ts");
+}
+
+#[tokio::test]
+async fn missing_input_map_is_a_warning() {
+  let bundle =
+    bundle_fixture_with_sourcemap("source_map/input_missing", SourceMapType::File, vec![]).await;
+  // The build still succeeds and maps to the loaded code
+  let code = bundle.code_of("main.js");
+  assert_answer_is_mapped(&code, &bundle.source_map_of("main.js"));
+  assert_eq!(bundle.warnings.len(), 1);
+  let warning = &bundle.warnings[0];
+  assert_eq!(warning.kind.code(), "SOURCEMAP_ERROR");
+  assert!(warning.to_string().contains("missing.js.map"), "{warning}");
+}
+
+#[tokio::test]
+async fn invalid_input_map_is_a_warning() {
+  let bundle =
+    bundle_fixture_with_sourcemap("source_map/input_invalid", SourceMapType::File, vec![]).await;
+  let code = bundle.code_of("main.js");
+  assert_answer_is_mapped(&code, &bundle.source_map_of("main.js"));
+  assert_eq!(bundle.warnings.len(), 1);
+  assert_eq!(bundle.warnings[0].kind.code(), "SOURCEMAP_ERROR");
+}
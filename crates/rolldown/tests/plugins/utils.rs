@@ -0,0 +1,86 @@
+use std::{
+  path::PathBuf,
+  sync::{Arc, Mutex},
+};
+
+use rolldown::{Asset, BuildResult, Bundler, InputItem, InputOptions, OutputOptions};
+use rolldown_error::Error as BuildError;
+use rolldown_plugin::{BuildPlugin, SourceMap};
+
+pub fn fixture_path(name: &str) -> PathBuf {
+  PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+    .join("tests/plugins/fixtures")
+    .join(name)
+}
+
+pub struct TestBundle {
+  pub output: BuildResult<Vec<Asset>>,
+  pub warnings: Vec<BuildError>,
+  /// Files that plugins asked to watch.
+  pub watch_files: Vec<PathBuf>,
+}
+
+impl TestBundle {
+  pub fn assets(&self) -> &[Asset] {
+    self.output.as_ref().expect("The build should succeed")
+  }
+
+  pub fn asset(&self, filename: &str) -> &Asset {
+    self
+      .assets()
+      .iter()
+      .find(|asset| asset.filename == filename)
+      .unwrap_or_else(|| panic!("{filename} is not in the output"))
+  }
+
+  pub fn code_of(&self, filename: &str) -> String {
+    self.asset(filename).content.to_string_lossy().into_owned()
+  }
+
+  /// Parse `<filename>.map` in the output.
+  pub fn source_map_of(&self, filename: &str) -> SourceMap {
+    let map = self.asset(&format!("{filename}.map"));
+    SourceMap::from_slice(map.content.as_bytes()).expect("The source map should be valid")
+  }
+
+  /// The only error of the failed build.
+  pub fn error(self) -> BuildError {
+    let mut errors = self.output.expect_err("The build should fail").into_vec();
+    assert_eq!(errors.len(), 1);
+    errors.remove(0)
+  }
+}
+
+/// Bundle `main.js` of the fixture into ESM.
+pub async fn bundle(fixture: &str, plugins: Vec<Box<dyn BuildPlugin>>) -> TestBundle {
+  bundle_with(fixture, plugins, OutputOptions::default()).await
+}
+
+pub async fn bundle_with(
+  fixture: &str,
+  plugins: Vec<Box<dyn BuildPlugin>>,
+  output_options: OutputOptions,
+) -> TestBundle {
+  let warnings = Arc::new(Mutex::new(vec![]));
+  let warning_collector = warnings.clone();
+  let mut bundler = Bundler::with_plugins(
+    InputOptions {
+      input: vec![InputItem {
+        name: "main".to_string(),
+        import: "./main.js".to_string(),
+      }],
+      cwd: fixture_path(fixture),
+      on_warn: Arc::new(move |err| warning_collector.lock().unwrap().push(err)),
+      ..Default::default()
+    },
+    plugins,
+  );
+  let output = bundler.generate(output_options).await;
+  let warnings = std::mem::take(&mut *warnings.lock().unwrap());
+  let watch_files = bundler.watch_files().await;
+  TestBundle {
+    output,
+    warnings,
+    watch_files,
+  }
+}
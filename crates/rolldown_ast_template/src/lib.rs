@@ -27,10 +27,13 @@ pub fn build_exports_stmt(mut exports: Vec<(JsWord, ast::Id)>) -> ast::ModuleIte
   }))
 }
 
+/// Names of CommonJS modules re-exported by `export *` are only known at runtime, so they're copied to the namespace
+/// by `__reExport({ ... }, import_foo)` before the namespace is frozen.
 pub fn build_namespace_export_stmt(
   var_name: ast::Id,
   exports: Vec<(JsWord, ast::Id)>,
   external_module_ids: Vec<ast::Id>,
+  commonjs_module_ids: Vec<ast::Id>,
 ) -> ast::ModuleItem {
   use ast::*;
   let mut exported_name_and_local_id_list = exports.into_iter().collect::<Vec<_>>();
@@ -61,6 +64,13 @@ pub fn build_namespace_export_stmt(
       }),
   )
   .collect::<Vec<_>>();
+  let exports_obj = commonjs_module_ids.into_iter().fold(
+    Expr::Object(ObjectLit {
+      span: Default::default(),
+      props: exports_props,
+    }),
+    |target, id| build_re_export_call(target, id),
+  );
 
   if external_module_ids.is_empty() {
     ModuleItem::ModuleDecl(ast::ModuleDecl::ExportDecl(ast::ExportDecl {
@@ -80,10 +90,7 @@ pub fn build_namespace_export_stmt(
               ..MemberExpr::dummy()
             }))),
             args: vec![ExprOrSpread {
-              expr: Box::new(Expr::Object(ObjectLit {
-                span: Default::default(),
-                props: exports_props,
-              })),
+              expr: Box::new(exports_obj),
               spread: None,
             }],
             ..CallExpr::dummy()
@@ -96,10 +103,7 @@ pub fn build_namespace_export_stmt(
       callee: Callee::Expr(quote_ident!("_mergeNamespaces").into()),
       args: vec![
         ExprOrSpread {
-          expr: Box::new(Expr::Object(ObjectLit {
-            span: Default::default(),
-            props: exports_props,
-          })),
+          expr: Box::new(exports_obj),
           spread: None,
         },
         ExprOrSpread {
@@ -136,3 +140,185 @@ pub fn build_namespace_export_stmt(
     }))
   }
 }
+
+/// `var name = init;`
+pub fn build_var_decl_stmt(name: ast::Id, init: ast::Expr) -> ast::ModuleItem {
+  use ast::{Decl, ModuleItem, Stmt, VarDecl, VarDeclKind, VarDeclarator};
+  ModuleItem::Stmt(Stmt::Decl(Decl::Var(Box::new(VarDecl {
+    span: Default::default(),
+    kind: VarDeclKind::Var,
+    declare: false,
+    decls: vec![VarDeclarator {
+      span: Default::default(),
+      definite: false,
+      name: name.into(),
+      init: Some(Box::new(init)),
+    }],
+  }))))
+}
+
+/// `var require_foo = __commonJS(function (exports, module) { ...body });`
+pub fn build_commonjs_wrapper_stmt(wrapper: ast::Id, body: Vec<ast::Stmt>) -> ast::ModuleItem {
+  use ast::*;
+  let callback = Expr::Fn(FnExpr {
+    ident: None,
+    function: Box::new(Function {
+      params: ["exports", "module"]
+        .into_iter()
+        .map(|name| Param {
+          span: Default::default(),
+          decorators: vec![],
+          pat: quote_ident!(name).into(),
+        })
+        .collect(),
+      body: Some(BlockStmt {
+        span: Default::default(),
+        stmts: body,
+      }),
+      ..Function::dummy()
+    }),
+  });
+  build_var_decl_stmt(
+    wrapper,
+    Expr::Call(CallExpr {
+      callee: Callee::Expr(quote_ident!("__commonJS").into()),
+      args: vec![ExprOrSpread {
+        expr: Box::new(callback),
+        spread: None,
+      }],
+      ..CallExpr::dummy()
+    }),
+  )
+}
+
+/// `function require_foo() { return __toCommonJS(foo_exports); }`, or
+/// `function require_foo() { return init_foo(), __toCommonJS(foo_exports); }` if the module is wrapped by `__esm`.
+pub fn build_esm_require_wrapper_stmt(
+  wrapper: ast::Id,
+  namespace: ast::Id,
+  init: Option<ast::Id>,
+) -> ast::ModuleItem {
+  use ast::*;
+  let to_common_js_call = Expr::Call(CallExpr {
+    callee: Callee::Expr(quote_ident!("__toCommonJS").into()),
+    args: vec![ExprOrSpread {
+      expr: namespace.into(),
+      spread: None,
+    }],
+    ..CallExpr::dummy()
+  });
+  let returned = match init {
+    Some(init) => Expr::Seq(SeqExpr {
+      span: Default::default(),
+      exprs: vec![
+        Box::new(build_require_call(init)),
+        Box::new(to_common_js_call),
+      ],
+    }),
+    None => to_common_js_call,
+  };
+  ModuleItem::Stmt(Stmt::Decl(Decl::Fn(FnDecl {
+    ident: wrapper.into(),
+    declare: false,
+    function: Box::new(Function {
+      body: Some(BlockStmt {
+        span: Default::default(),
+        stmts: vec![Stmt::Return(ReturnStmt {
+          span: Default::default(),
+          arg: Some(Box::new(returned)),
+        })],
+      }),
+      ..Function::dummy()
+    }),
+  })))
+}
+
+/// `var init_foo = __esm(function () { ...body });`
+pub fn build_esm_wrapper_stmt(init: ast::Id, body: Vec<ast::Stmt>) -> ast::ModuleItem {
+  use ast::*;
+  let callback = Expr::Fn(FnExpr {
+    ident: None,
+    function: Box::new(Function {
+      body: Some(BlockStmt {
+        span: Default::default(),
+        stmts: body,
+      }),
+      ..Function::dummy()
+    }),
+  });
+  build_var_decl_stmt(
+    init,
+    Expr::Call(CallExpr {
+      callee: Callee::Expr(quote_ident!("__esm").into()),
+      args: vec![ExprOrSpread {
+        expr: Box::new(callback),
+        spread: None,
+      }],
+      ..CallExpr::dummy()
+    }),
+  )
+}
+
+/// `init_foo();`
+pub fn build_call_stmt(callee: ast::Id) -> ast::ModuleItem {
+  ast::ModuleItem::Stmt(ast::Stmt::Expr(ast::ExprStmt {
+    span: Default::default(),
+    expr: Box::new(build_require_call(callee)),
+  }))
+}
+
+/// `require_foo()`
+pub fn build_require_call(wrapper: ast::Id) -> ast::Expr {
+  ast::Expr::Call(ast::CallExpr {
+    callee: ast::Callee::Expr(Box::new(ast::Expr::Ident(wrapper.into()))),
+    ..ast::CallExpr::dummy()
+  })
+}
+
+/// `__toESM(require_foo())`
+pub fn build_to_esm_call(wrapper: ast::Id) -> ast::Expr {
+  ast::Expr::Call(ast::CallExpr {
+    callee: ast::Callee::Expr(quote_ident!("__toESM").into()),
+    args: vec![ast::ExprOrSpread {
+      expr: Box::new(build_require_call(wrapper)),
+      spread: None,
+    }],
+    ..ast::CallExpr::dummy()
+  })
+}
+
+/// `__reExport(target, import_foo)`
+pub fn build_re_export_call(target: ast::Expr, module: ast::Id) -> ast::Expr {
+  ast::Expr::Call(ast::CallExpr {
+    callee: ast::Callee::Expr(quote_ident!("__reExport").into()),
+    args: vec![
+      ast::ExprOrSpread {
+        expr: Box::new(target),
+        spread: None,
+      },
+      ast::ExprOrSpread {
+        expr: Box::new(ast::Expr::Ident(module.into())),
+        spread: None,
+      },
+    ],
+    ..ast::CallExpr::dummy()
+  })
+}
+
+/// `obj.prop`, or `obj["prop"]` if `prop` isn't a valid identifier.
+pub fn build_member_expr(obj: ast::Id, prop: JsWord) -> ast::Expr {
+  use ast::*;
+  let prop = if Ident::verify_symbol(&prop).is_ok() {
+    MemberProp::Ident(quote_ident!(prop))
+  } else {
+    MemberProp::Computed(ComputedPropName {
+      span: Default::default(),
+      expr: Box::new(Expr::Lit(Lit::Str(prop.into()))),
+    })
+  };
+  Expr::Member(MemberExpr {
+    span: Default::default(),
+    obj: Box::new(Expr::Ident(obj.into())),
+    prop,
+  })
+}
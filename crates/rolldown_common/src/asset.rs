@@ -0,0 +1,49 @@
+use std::borrow::Cow;
+
+#[derive(Debug)]
+pub struct Asset {
+  pub filename: String,
+  pub content: AssetContent,
+}
+
+/// Chunks are always rendered to code, while assets emitted by plugins could be arbitrary bytes.
+#[derive(Debug, Clone)]
+pub enum AssetContent {
+  Code(String),
+  Binary(Vec<u8>),
+}
+
+impl AssetContent {
+  pub fn as_bytes(&self) -> &[u8] {
+    match self {
+      AssetContent::Code(code) => code.as_bytes(),
+      AssetContent::Binary(bytes) => bytes,
+    }
+  }
+
+  pub fn as_code(&self) -> Option<&str> {
+    match self {
+      AssetContent::Code(code) => Some(code),
+      AssetContent::Binary(_) => None,
+    }
+  }
+
+  pub fn to_string_lossy(&self) -> Cow<str> {
+    match self {
+      AssetContent::Code(code) => Cow::Borrowed(code),
+      AssetContent::Binary(bytes) => String::from_utf8_lossy(bytes),
+    }
+  }
+}
+
+impl From<String> for AssetContent {
+  fn from(code: String) -> Self {
+    AssetContent::Code(code)
+  }
+}
+
+impl From<Vec<u8>> for AssetContent {
+  fn from(bytes: Vec<u8>) -> Self {
+    AssetContent::Binary(bytes)
+  }
+}
@@ -0,0 +1,32 @@
+use std::str::FromStr;
+
+/// How `default` and namespace imports of an external module are interpreted when it's `require`d.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum InteropMode {
+  /// Modules with `__esModule` are treated as transpiled ES modules. Otherwise `module.exports` is the default
+  /// export, and its properties are named exports.
+  Auto,
+  /// Required modules are always treated as transpiled ES modules.
+  EsModule,
+  /// `module.exports` is always the default export, and its properties are named exports.
+  Default,
+  /// Like `Default`, but named imports are not allowed.
+  DefaultOnly,
+  /// Like `Auto`, but modules with a `default` property are treated as transpiled ES modules instead.
+  Compat,
+}
+
+impl FromStr for InteropMode {
+  type Err = String;
+
+  fn from_str(value: &str) -> Result<Self, Self::Err> {
+    match value {
+      "auto" => Ok(InteropMode::Auto),
+      "esModule" => Ok(InteropMode::EsModule),
+      "default" => Ok(InteropMode::Default),
+      "defaultOnly" => Ok(InteropMode::DefaultOnly),
+      "compat" => Ok(InteropMode::Compat),
+      _ => Err(format!("Invalid interop mode: {value}")),
+    }
+  }
+}
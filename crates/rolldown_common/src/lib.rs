@@ -9,6 +9,18 @@ mod symbol;
 pub use symbol::*;
 mod loader;
 pub use loader::*;
+mod module_path;
+pub use module_path::*;
+mod input_item;
+pub use input_item::*;
+mod asset;
+pub use asset::*;
+mod module_format;
+pub use module_format::*;
+mod interop_mode;
+pub use interop_mode::*;
+mod source_map;
+pub use source_map::*;
 
 #[derive(Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Clone)]
 pub struct ChunkId(JsWord);
@@ -66,6 +78,15 @@ impl ModuleId {
   pub fn id(&self) -> &JsWord {
     &self.value
   }
+
+  pub fn is_virtual(&self) -> bool {
+    is_virtual_id(&self.value)
+  }
+
+  /// The id without the query part. This is the path used to read the module from the disk.
+  pub fn path_without_query(&self) -> &str {
+    split_query(&self.value).0
+  }
 }
 
 impl AsRef<str> for ModuleId {
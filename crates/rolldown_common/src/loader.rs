@@ -1,12 +1,34 @@
 use std::str::FromStr;
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Loader {
   Js,
   Jsx,
   Ts,
   Tsx,
   Json,
+  /// Default-exports the content as a string.
+  Text,
+  /// Default-exports the content as a base64-encoded string.
+  Base64,
+  /// Default-exports the content as a `data:` URL.
+  DataUrl,
+  /// Default-exports the content as a `Uint8Array`.
+  Binary,
+  /// Emits the file as an asset named by `asset_file_names` and default-exports its URL relative to the chunk.
+  File,
+  /// Ignores the content.
+  Empty,
+}
+
+impl Loader {
+  /// Content of these modules isn't necessarily UTF-8, so they are read as bytes and skip `transform` hooks.
+  pub fn is_binary(&self) -> bool {
+    matches!(
+      self,
+      Self::Base64 | Self::DataUrl | Self::Binary | Self::File | Self::Empty
+    )
+  }
 }
 
 impl FromStr for Loader {
@@ -18,6 +40,13 @@ impl FromStr for Loader {
       "jsx" => Ok(Self::Jsx),
       "ts" => Ok(Self::Ts),
       "tsx" => Ok(Self::Tsx),
+      "json" => Ok(Self::Json),
+      "text" => Ok(Self::Text),
+      "base64" => Ok(Self::Base64),
+      "dataurl" => Ok(Self::DataUrl),
+      "binary" => Ok(Self::Binary),
+      "file" => Ok(Self::File),
+      "empty" => Ok(Self::Empty),
       _ => Err(format!("Unknown loader value \"{}\"", s)),
     }
   }
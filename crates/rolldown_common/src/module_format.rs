@@ -0,0 +1,50 @@
+use std::str::FromStr;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ModuleFormat {
+  Esm,
+  Cjs,
+  /// Wraps the chunk in a function expression, which assigns exports to the global `output.name`.
+  Iife,
+  /// Defines the chunk with `define(['dep'], factory)` for AMD loaders like RequireJS.
+  Amd,
+  /// Works as AMD, CommonJS or IIFE depending on the environment.
+  Umd,
+  /// Registers the chunk with `System.register` for SystemJS, which supports code splitting.
+  System,
+}
+
+impl ModuleFormat {
+  pub fn is_es(self) -> bool {
+    self == ModuleFormat::Esm
+  }
+
+  pub fn is_cjs(self) -> bool {
+    self == ModuleFormat::Cjs
+  }
+
+  /// Imports and exports are linked by `require` and `exports`, either directly or through parameters of a
+  /// function wrapping the chunk.
+  pub fn is_cjs_based(self) -> bool {
+    matches!(
+      self,
+      ModuleFormat::Cjs | ModuleFormat::Iife | ModuleFormat::Amd | ModuleFormat::Umd
+    )
+  }
+}
+
+impl FromStr for ModuleFormat {
+  type Err = String;
+
+  fn from_str(value: &str) -> Result<Self, Self::Err> {
+    match value {
+      "esm" => Ok(ModuleFormat::Esm),
+      "cjs" => Ok(ModuleFormat::Cjs),
+      "iife" => Ok(ModuleFormat::Iife),
+      "amd" => Ok(ModuleFormat::Amd),
+      "umd" => Ok(ModuleFormat::Umd),
+      "system" | "systemjs" => Ok(ModuleFormat::System),
+      _ => Err(format!("Invalid module format: {value}")),
+    }
+  }
+}
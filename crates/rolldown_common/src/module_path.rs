@@ -0,0 +1,20 @@
+/// By the convention of rollup, ids prefixed with `\0` are virtual modules. They are not
+/// backed by the file system and should only be handled by plugins.
+pub const VIRTUAL_ID_PREFIX: char = '\0';
+
+pub fn is_virtual_id(id: &str) -> bool {
+  id.starts_with(VIRTUAL_ID_PREFIX)
+}
+
+/// Split `./foo.svg?raw` into `./foo.svg` and `?raw`.
+///
+/// Virtual ids are opaque to rolldown, so they are never splitted.
+pub fn split_query(id: &str) -> (&str, &str) {
+  if is_virtual_id(id) {
+    return (id, "");
+  }
+  match id.find('?') {
+    Some(idx) => id.split_at(idx),
+    None => (id, ""),
+  }
+}
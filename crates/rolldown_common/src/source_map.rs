@@ -0,0 +1,77 @@
+use rustc_hash::{FxHashMap, FxHashSet};
+pub use sourcemap::SourceMap;
+use sourcemap::SourceMapBuilder;
+
+/// Chain two source maps. `older` maps the intermediate code to the original code and `newer` maps the final code to
+/// the intermediate code. The result maps the final code to the original code.
+///
+/// Segments of `newer` that don't point to any segment of `older` are dropped.
+pub fn collapse_source_maps(older: &SourceMap, newer: &SourceMap) -> SourceMap {
+  let mut builder = SourceMapBuilder::new(newer.get_file().or_else(|| older.get_file()));
+  let mut sources_with_contents = FxHashSet::default();
+
+  for token in newer.tokens() {
+    let Some(original) = older.lookup_token(token.get_src_line(), token.get_src_col()) else {
+      continue;
+    };
+    // `lookup_token` falls back to the closest segment before the position, which might be on previous lines.
+    if original.get_dst_line() != token.get_src_line() {
+      continue;
+    }
+
+    let raw = builder.add(
+      token.get_dst_line(),
+      token.get_dst_col(),
+      original.get_src_line(),
+      original.get_src_col(),
+      original.get_source(),
+      original.get_name().or_else(|| token.get_name()),
+    );
+
+    if original.has_source() && sources_with_contents.insert(raw.src_id) {
+      let contents = older.get_source_contents(original.get_src_id());
+      builder.set_source_contents(raw.src_id, contents);
+    }
+  }
+
+  builder.into_sourcemap()
+}
+
+/// Concatenate source maps of code pieces that are joined into one file. Each map comes with the line where its piece
+/// starts in the joined code. `rewrite_source` is called once per source, such as for making paths relative.
+pub fn concat_source_maps<'a>(
+  file: Option<&str>,
+  maps: impl IntoIterator<Item = (u32, &'a SourceMap)>,
+  include_sources_content: bool,
+  mut rewrite_source: impl FnMut(&str) -> String,
+) -> SourceMap {
+  let mut builder = SourceMapBuilder::new(file);
+  let mut rewritten_sources: FxHashMap<&'a str, String> = FxHashMap::default();
+  let mut sources_with_contents = FxHashSet::default();
+
+  for (line_offset, map) in maps {
+    for token in map.tokens() {
+      let source = token.get_source().map(|source| {
+        rewritten_sources
+          .entry(source)
+          .or_insert_with(|| rewrite_source(source))
+          .as_str()
+      });
+
+      let raw = builder.add(
+        token.get_dst_line() + line_offset,
+        token.get_dst_col(),
+        token.get_src_line(),
+        token.get_src_col(),
+        source,
+        token.get_name(),
+      );
+
+      if include_sources_content && token.has_source() && sources_with_contents.insert(raw.src_id) {
+        builder.set_source_contents(raw.src_id, map.get_source_contents(token.get_src_id()));
+      }
+    }
+  }
+
+  builder.into_sourcemap()
+}
@@ -4,7 +4,8 @@ use ast::EsVersion;
 use swc_common::{
   comments::Comments,
   errors::{ColorConfig, Handler},
-  FileName, SourceMap,
+  source_map::SourceMapGenConfig,
+  BytePos, FileName, LineCol, SourceMap,
 };
 use swc_core::{
   common::{self as swc_common, SourceFile},
@@ -52,6 +53,44 @@ impl Compiler {
     String::from_utf8(output).map_err(Into::into)
   }
 
+  /// Print the module along with mappings from positions of the output to positions of the source files.
+  pub fn print_with_mappings(
+    &self,
+    ast: &ast::Module,
+    comments: Option<&dyn Comments>,
+  ) -> anyhow::Result<(String, Vec<(BytePos, LineCol)>)> {
+    let mut output = Vec::new();
+    let mut mappings = Vec::new();
+
+    {
+      let mut emitter = swc_ecma_codegen::Emitter {
+        cfg: Default::default(),
+        cm: self.cm.clone(),
+        comments: Some(&comments),
+        wr: Box::new(JsWriter::new(
+          self.cm.clone(),
+          "\n",
+          &mut output,
+          Some(&mut mappings),
+        )),
+      };
+      emitter.emit_module(ast)?;
+    }
+
+    Ok((String::from_utf8(output)?, mappings))
+  }
+
+  /// Build a source map from mappings returned by `print_with_mappings`.
+  /// Sources are the names of the source files with their contents.
+  pub fn build_source_map(
+    &self,
+    mappings: &mut Vec<(BytePos, LineCol)>,
+  ) -> rolldown_common::SourceMap {
+    self
+      .cm
+      .build_source_map_with_config(mappings, None, SourceMapConfig)
+  }
+
   pub fn print_module_item(
     &self,
     ast: &ast::ModuleItem,
@@ -72,6 +111,20 @@ impl Compiler {
     String::from_utf8(output).map_err(Into::into)
   }
 
+  pub fn print_expr(&self, expr: &ast::Expr) -> anyhow::Result<String> {
+    let mut output = Vec::new();
+
+    let mut emitter = swc_ecma_codegen::Emitter {
+      cfg: Default::default(),
+      cm: self.cm.clone(),
+      comments: None,
+      wr: Box::new(JsWriter::new(self.cm.clone(), "\n", &mut output, None)),
+    };
+
+    emitter.emit_expr(expr)?;
+    String::from_utf8(output).map_err(Into::into)
+  }
+
   pub fn debug_print(
     &self,
     ast: &ast::Module,
@@ -97,6 +150,21 @@ impl Compiler {
     self.parse_with_comments(source_file, syntax, None)
   }
 
+  pub fn parse_expr(
+    &self,
+    source_file: Arc<SourceFile>,
+    syntax: Syntax,
+  ) -> PResult<Box<ast::Expr>> {
+    let lexer = Lexer::new(
+      syntax,
+      EsVersion::latest(),
+      StringInput::from(source_file.as_ref()),
+      None,
+    );
+    let mut parser = Parser::new_from(lexer);
+    parser.parse_expr()
+  }
+
   pub fn parse_with_comments(
     &self,
     source_file: Arc<SourceFile>,
@@ -120,6 +188,18 @@ impl Compiler {
   }
 }
 
+struct SourceMapConfig;
+
+impl SourceMapGenConfig for SourceMapConfig {
+  fn file_name_to_source(&self, f: &FileName) -> String {
+    f.to_string()
+  }
+
+  fn inline_sources_content(&self, _f: &FileName) -> bool {
+    true
+  }
+}
+
 struct SyntaxContextVisualizer;
 
 impl VisitMut for SyntaxContextVisualizer {
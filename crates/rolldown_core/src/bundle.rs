@@ -1,10 +1,16 @@
+use std::path::{Path, PathBuf};
+
 use rayon::prelude::*;
-use rustc_hash::FxHashMap as HashMap;
+use rolldown_common::{ChunkId, InteropMode, SourceMap, Symbol};
+use rolldown_plugin::{RenderDynamicImportArgs, TransformedSource};
+use rustc_hash::{FxHashMap as HashMap, FxHashSet as HashSet};
+use swc_core::ecma::{atoms::JsWord, parser::Syntax};
 use tracing::instrument;
 
 use crate::{
-  Asset, BuildInputOptions, BuildOutputOptions, Chunk, CodeSplitter, FinalizeBundleContext, Graph,
-  ModuleRefMutById, SplitPointIdToChunkId, UnaryBuildResult,
+  utils::replace_asset_url_placeholders, Asset, BuildError, BuildInputOptions, BuildOutputOptions,
+  Chunk, CodeSplitter, FinalizeBundleContext, Graph, ModuleFormat, ModuleRefMutById,
+  RenderedDynamicImports, SourceMapType, SplitPointIdToChunkId, UnaryBuildResult, COMPILER,
 };
 
 #[derive(Debug)]
@@ -29,9 +35,32 @@ impl<'a> Bundle<'a> {
     }
   }
 
+  /// `asset_file_name_by_reference_id` is used to render URLs of assets referenced by chunks.
   #[instrument(skip_all)]
-  pub fn generate(&mut self) -> UnaryBuildResult<Vec<Asset>> {
+  pub async fn generate(
+    &mut self,
+    asset_file_name_by_reference_id: &HashMap<String, String>,
+  ) -> UnaryBuildResult<Vec<Asset>> {
     let chunks = self.generate_chunks()?;
+    if chunks.len() > 1 {
+      match self.output_options.format {
+        ModuleFormat::Iife => {
+          return Err(BuildError::invalid_option(
+            "output.format",
+            "iife",
+            "IIFE output format is not supported for code-splitting builds",
+          ));
+        }
+        ModuleFormat::Umd => {
+          return Err(BuildError::invalid_option(
+            "output.format",
+            "umd",
+            "UMD output format is not supported for code-splitting builds",
+          ));
+        }
+        _ => {}
+      }
+    }
     let mut chunk_by_id = chunks
       .into_iter()
       .map(|c| (c.id.clone(), c))
@@ -41,17 +70,21 @@ impl<'a> Bundle<'a> {
       chunk.gen_file_name(self.output_options);
     });
 
+    let chunk_filename_by_id = chunk_by_id
+      .values()
+      .map(|chunk| (chunk.id.clone(), chunk.filename.clone().unwrap()))
+      .collect::<HashMap<_, _>>();
+
+    let rendered_dynamic_imports = self.render_dynamic_imports(&chunk_filename_by_id).await?;
+    let interop_imported_by_other_chunks = self.interop_imported_by_other_chunks(&chunk_by_id);
+    let interop_by_external_id = self.resolve_interop().await?;
+
     let mut module_mut_ref_by_id = self
       .graph
       .module_by_id
       .iter_mut()
       .collect::<HashMap<_, _>>();
 
-    let chunk_filename_by_id = chunk_by_id
-      .values()
-      .map(|chunk| (chunk.id.clone(), chunk.filename.clone().unwrap()))
-      .collect::<HashMap<_, _>>();
-
     let chunk_and_modules = chunk_by_id
       .values_mut()
       .map(|chunk| {
@@ -74,32 +107,211 @@ impl<'a> Bundle<'a> {
         chunk.finalize(FinalizeBundleContext {
           modules: module_mut_ref_by_id,
           uf: &self.graph.uf,
+          input_options: self.input_options,
           output_options: self.output_options,
           split_point_id_to_chunk_id: &self.split_point_id_to_chunk_id,
           chunk_filename_by_id: &chunk_filename_by_id,
           unresolved_ctxt: self.graph.unresolved_ctxt,
+          rendered_dynamic_imports: &rendered_dynamic_imports,
+          interop_imported_by_other_chunks: &interop_imported_by_other_chunks,
+          interop_by_external_id: &interop_by_external_id,
         })
       },
     )?;
 
-    let chunks = chunk_by_id
+    let plugin_driver = self.graph.build_plugin_driver.clone();
+    let mut assets = Vec::with_capacity(chunk_by_id.len());
+    for chunk in chunk_by_id.values() {
+      let mut rendered = chunk.render(
+        crate::RenderContext {
+          source_map: self.output_options.sourcemap.is_some(),
+        },
+        self.graph,
+        self.input_options,
+        self.output_options,
+      )?;
+      // Before `render_chunk`, so plugins see the final URLs
+      replace_asset_url_placeholders(
+        &mut rendered.code,
+        chunk.filename.as_deref().unwrap(),
+        asset_file_name_by_reference_id,
+      );
+
+      let chunk_info = chunk.to_chunk_info(&self.graph.module_by_id);
+      let TransformedSource { mut code, map } = plugin_driver
+        .read()
+        .await
+        .render_chunk(rendered.code, rendered.map, &chunk_info)
+        .await?;
+
+      if let Some(map) = map {
+        if let Some(map_asset) = self.attach_source_map(&chunk_info.filename, &mut code, &map)? {
+          assets.push(map_asset);
+        }
+      }
+
+      assets.push(Asset {
+        content: code.into(),
+        filename: chunk_info.filename,
+      });
+    }
+
+    Ok(assets)
+  }
+
+  /// Interop functions, such as `require_foo` and `init_foo`, used in other chunks. Chunks of their owners
+  /// need to export them.
+  fn interop_imported_by_other_chunks(
+    &self,
+    chunk_by_id: &HashMap<ChunkId, Chunk>,
+  ) -> HashSet<Symbol> {
+    let chunk_id_by_module_id = chunk_by_id
       .values()
-      .map(|chunk| {
-        let code = chunk.render(
-          crate::RenderContext {},
-          self.graph,
-          self.input_options,
-          self.output_options,
-        );
-
-        code.map(|code| Asset {
-          content: code,
-          filename: chunk.filename.clone().unwrap(),
-        })
+      .flat_map(|chunk| chunk.modules.iter().map(|id| (id, &chunk.id)))
+      .collect::<HashMap<_, _>>();
+
+    self
+      .graph
+      .module_by_id
+      .values()
+      .filter_map(|m| m.as_norm())
+      .flat_map(|importer| {
+        importer
+          .linked_imports
+          .iter()
+          .filter(|(owner, _)| {
+            chunk_id_by_module_id.get(owner) != chunk_id_by_module_id.get(&importer.id)
+          })
+          .filter_map(|(owner, specs)| {
+            self
+              .graph
+              .module_by_id
+              .get(owner)
+              .and_then(|owner| owner.as_norm())
+              .map(|owner| (owner, specs))
+          })
+          .flat_map(|(owner, specs)| {
+            owner
+              .interop_exports()
+              .filter(move |interop| {
+                specs
+                  .iter()
+                  .any(|spec| spec.imported == interop.exported_as)
+              })
+              .map(|interop| interop.local_id.clone())
+          })
       })
-      .try_collect::<Vec<_>>()?;
+      .collect()
+  }
+
+  /// Interop modes of external modules required by CommonJS based output. The key `None` is for `import()` of
+  /// non-string sources.
+  async fn resolve_interop(&self) -> UnaryBuildResult<HashMap<Option<JsWord>, InteropMode>> {
+    let mut interop_by_external_id = HashMap::default();
+    if !self.output_options.format.is_cjs_based() {
+      return Ok(interop_by_external_id);
+    }
+    let external_ids = self
+      .graph
+      .module_by_id
+      .keys()
+      .filter(|id| id.is_external())
+      .map(|id| Some(id.id().clone()))
+      .chain([None]);
+    for id in external_ids {
+      let interop = (self.output_options.interop)(id.as_deref()).await?;
+      interop_by_external_id.insert(id, interop);
+    }
+    Ok(interop_by_external_id)
+  }
+
+  /// Reference the source map from the chunk as `output.sourcemap` requires. Returns the `.map` file to emit.
+  fn attach_source_map(
+    &self,
+    filename: &str,
+    code: &mut String,
+    map: &SourceMap,
+  ) -> UnaryBuildResult<Option<Asset>> {
+    let mut json = vec![];
+    map
+      .to_writer(&mut json)
+      .map_err(|e| BuildError::panic(format!("Failed to serialize source map: {e}")))?;
+    let json = String::from_utf8(json).expect("Source maps should be serialized as UTF-8");
+
+    let map_filename = format!("{filename}.map");
+    let url = match self.output_options.sourcemap {
+      Some(SourceMapType::File) => Some(
+        Path::new(&map_filename)
+          .file_name()
+          .unwrap()
+          .to_string_lossy()
+          .to_string(),
+      ),
+      Some(SourceMapType::Inline) => Some(format!(
+        "data:application/json;charset=utf-8;base64,{}",
+        base64::encode(&json)
+      )),
+      Some(SourceMapType::Hidden) | None => None,
+    };
+
+    if let Some(url) = url {
+      if !code.ends_with('\n') {
+        code.push('\n');
+      }
+      code.push_str(&format!("//# sourceMappingURL={url}\n"));
+    }
+
+    // Inline source maps are already a part of the chunk
+    let is_inline = matches!(self.output_options.sourcemap, Some(SourceMapType::Inline));
+    Ok((!is_inline).then(|| Asset {
+      filename: map_filename,
+      content: json.into(),
+    }))
+  }
 
-    Ok(chunks)
+  /// Let plugins decide how `import()` expressions are rendered.
+  async fn render_dynamic_imports(
+    &self,
+    chunk_filename_by_id: &HashMap<ChunkId, String>,
+  ) -> UnaryBuildResult<RenderedDynamicImports> {
+    let plugin_driver = self.graph.build_plugin_driver.read().await;
+    let mut rendered_dynamic_imports = RenderedDynamicImports::default();
+    for module in self.graph.module_by_id.values().filter_map(|m| m.as_norm()) {
+      for (specifier, resolved_id) in &module.resolved_module_ids {
+        if !module.dyn_dependencies.contains(resolved_id) {
+          continue;
+        }
+        let target_filename = self
+          .split_point_id_to_chunk_id
+          .get(resolved_id)
+          .and_then(|chunk_id| chunk_filename_by_id.get(chunk_id))
+          .map(|filename| format!("./{filename}"));
+        let rendered = plugin_driver
+          .render_dynamic_import(RenderDynamicImportArgs {
+            importer: &module.id,
+            specifier,
+            resolved_id,
+            target_filename: target_filename.as_deref(),
+            format: self.output_options.format,
+          })
+          .await?;
+
+        if let Some(code) = rendered {
+          let fm = COMPILER.create_source_file(
+            PathBuf::from(format!("{}?render_dynamic_import", module.id.as_ref())),
+            code,
+          );
+          let expr = COMPILER
+            .parse_expr(fm.clone(), Syntax::Es(Default::default()))
+            .map_err(|e| BuildError::parse_js_failed(fm, e))?;
+          rendered_dynamic_imports
+            .entry(module.id.clone())
+            .or_default()
+            .insert(specifier.clone(), expr);
+        }
+      }
+    }
+    Ok(rendered_dynamic_imports)
   }
 
   #[instrument(skip_all)]
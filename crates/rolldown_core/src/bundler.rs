@@ -1,10 +1,10 @@
-use std::sync::Arc;
+use std::{path::PathBuf, sync::Arc};
 
 use rolldown_plugin::BuildPlugin;
 use tracing::instrument;
 
 use crate::{
-  BuildInputOptions, BuildOutputOptions, BuildPluginDriver, BuildResult, Bundle, Graph,
+  BuildError, BuildInputOptions, BuildOutputOptions, BuildPluginDriver, BuildResult, Bundle, Graph,
   SharedBuildInputOptions, SharedBuildPluginDriver,
 };
 
@@ -13,11 +13,7 @@ pub struct BundlerCore {
   plugin_driver: SharedBuildPluginDriver,
 }
 
-#[derive(Debug)]
-pub struct Asset {
-  pub filename: String,
-  pub content: String,
-}
+pub use rolldown_common::{Asset, AssetContent};
 
 impl BundlerCore {
   pub fn new(input_opts: BuildInputOptions) -> Self {
@@ -26,9 +22,10 @@ impl BundlerCore {
 
   pub fn with_plugins(input_opts: BuildInputOptions, plugins: Vec<Box<dyn BuildPlugin>>) -> Self {
     rolldown_tracing::enable_tracing_on_demand();
+    let input_options = Arc::new(input_opts);
     Self {
-      input_options: Arc::new(input_opts),
-      plugin_driver: BuildPluginDriver::new(plugins).into_shared(),
+      plugin_driver: BuildPluginDriver::new_shared(plugins, input_options.clone()),
+      input_options,
     }
   }
 
@@ -37,9 +34,78 @@ impl BundlerCore {
     tracing::debug!("{:#?}", self.input_options);
     tracing::debug!("{:#?}", output_opts);
     let mut graph = Graph::new(self.plugin_driver.clone(), self.input_options.clone());
-    graph.generate_module_graph().await?;
+    let build_result = self.build_module_graph(&mut graph).await;
+
+    // `build_end` is called even if `build_start` failed, so plugins could clean up what they have set up.
+    let build_end_result = self
+      .plugin_driver
+      .read()
+      .await
+      .build_end(build_result.as_ref().err())
+      .await;
+    build_result?;
+    build_end_result?;
+
+    // Files imported via `Loader::File` are named before chunks are rendered, so chunks could reference their URLs.
+    let asset_file_name_by_reference_id = self
+      .plugin_driver
+      .read()
+      .await
+      .context
+      .referenced_asset_file_names(&output_opts.asset_file_names);
     let mut bundle = Bundle::new(&self.input_options, &output_opts, &mut graph);
-    let assets = bundle.generate()?;
+    let mut assets = bundle.generate(&asset_file_name_by_reference_id).await?;
+
+    let plugin_driver = self.plugin_driver.read().await;
+    let context = &plugin_driver.context;
+    add_emitted_assets(
+      &mut assets,
+      context.take_emitted_assets(&output_opts.asset_file_names),
+    )?;
+    plugin_driver.generate_bundle(&mut assets).await?;
+    // Assets emitted in `generate_bundle`
+    add_emitted_assets(
+      &mut assets,
+      context.take_emitted_assets(&output_opts.asset_file_names),
+    )?;
+
     Ok(assets)
   }
+
+  async fn build_module_graph(&self, graph: &mut Graph) -> BuildResult<()> {
+    self
+      .plugin_driver
+      .read()
+      .await
+      .build_start(&self.input_options)
+      .await?;
+    graph.generate_module_graph().await
+  }
+
+  /// Files added by plugins via `PluginContext::add_watch_file` during the last build.
+  pub async fn watch_files(&self) -> Vec<PathBuf> {
+    self.plugin_driver.read().await.context.watch_files()
+  }
+
+  /// Notify plugins that the bundling is finished.
+  #[instrument(skip_all)]
+  pub async fn close(&mut self) -> BuildResult<()> {
+    self.plugin_driver.read().await.close_bundle().await?;
+    Ok(())
+  }
+}
+
+fn add_emitted_assets(assets: &mut Vec<Asset>, emitted: Vec<Asset>) -> BuildResult<()> {
+  for asset in emitted {
+    match assets
+      .iter()
+      .find(|existing| existing.filename == asset.filename)
+    {
+      // The same file could be emitted more than once, such as an image imported by several modules.
+      Some(existing) if existing.content.as_bytes() == asset.content.as_bytes() => {}
+      Some(_) => return Err(BuildError::file_name_conflict(asset.filename).into()),
+      None => assets.push(asset),
+    }
+  }
+  Ok(())
 }
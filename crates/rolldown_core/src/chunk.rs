@@ -1,14 +1,22 @@
-use std::{collections::HashSet, path::PathBuf};
+use std::{
+  collections::HashSet,
+  path::{Path, PathBuf},
+};
 
 use hashlink::LinkedHashSet;
 use itertools::Itertools;
 use rayon::prelude::{IntoParallelIterator, IntoParallelRefIterator, ParallelIterator};
-use rolldown_common::{ChunkId, ExportedSpecifier, ImportedSpecifier, ModuleId, Symbol, UnionFind};
+use rolldown_common::{
+  concat_source_maps, ChunkId, ExportedSpecifier, ImportedSpecifier, InteropMode, ModuleId,
+  SourceMap, Symbol, UnionFind,
+};
+use rolldown_plugin::ChunkInfo;
 use rolldown_runtime_helpers::RuntimeHelpers;
-use rolldown_swc_visitors::FinalizeContext;
+use rolldown_swc_visitors::{CjsLinksFormat, FactoryDependency, FinalizeContext, SystemDependency};
 use rustc_hash::{FxHashMap, FxHashSet};
+use sugar_path::SugarPath;
 use swc_core::{
-  common::{comments::SingleThreadedComments, util::take::Take, Mark, SyntaxContext, GLOBALS},
+  common::{util::take::Take, SyntaxContext},
   ecma::{
     ast::{self, Id, Ident},
     atoms::{js_word, JsWord},
@@ -19,9 +27,11 @@ use swc_core::{
 use tracing::instrument;
 
 use crate::{
-  file_name, norm_or_ext::NormOrExt, preset_of_used_names, BuildError, BuildInputOptions,
-  BuildOutputOptions, ExportMode, Graph, MergedExports, ModuleById, ModuleRefMutById,
-  SplitPointIdToChunkId, UnaryBuildResult, COMPILER,
+  amd_wrapper, file_name, iife_wrapper, make_legal, norm_or_ext::NormOrExt, preset_of_used_names,
+  system_wrapper, umd_wrapper, BuildError, BuildInputOptions, BuildOutputOptions, ExportMode,
+  FormatWrapper, Graph, MergedExports, ModuleById, ModuleFormat, ModuleRefMutById,
+  RenderedDynamicImports, SplitPointIdToChunkId, UnaryBuildResult, WrappedDependency,
+  WrappedExports, CAPTURE_WORD_RE, COMPILER,
 };
 
 pub struct Chunk {
@@ -34,6 +44,16 @@ pub struct Chunk {
   pub(crate) after_module_items: Vec<ast::ModuleItem>,
   pub(crate) runtime_helpers: RuntimeHelpers,
   pub(crate) is_user_defined_entry: bool,
+  /// Names exported by the chunk, which are decided by `generate_cross_chunk_links`.
+  pub(crate) exports: Vec<JsWord>,
+  /// Dependencies passed to the function wrapping the chunk, which are decided by `links_to_cjs`.
+  pub(crate) factory_dependencies: Vec<FactoryDependency>,
+  /// Whether `import()` is rendered as `require` of AMD, which makes `require` a dependency of the chunk.
+  pub(crate) requires_lazily: bool,
+  /// Dependencies of `System.register` with their setters, which are decided by `links_to_system`.
+  pub(crate) system_dependencies: Vec<SystemDependency>,
+  /// Imported bindings assigned by setters of `System.register`.
+  pub(crate) system_bindings: Vec<JsWord>,
 }
 
 impl Chunk {
@@ -48,6 +68,11 @@ impl Chunk {
       filename: None,
       runtime_helpers: Default::default(),
       is_user_defined_entry,
+      exports: Default::default(),
+      factory_dependencies: Default::default(),
+      requires_lazily: false,
+      system_dependencies: Default::default(),
+      system_bindings: Default::default(),
     }
   }
 
@@ -57,10 +82,33 @@ impl Chunk {
         .entry_file_names
         .render(file_name::RenderOptions {
           name: Some(self.id.as_ref()),
+          ..Default::default()
         }),
     )
   }
 
+  pub(crate) fn to_chunk_info(&self, module_by_id: &ModuleById) -> ChunkInfo {
+    let is_dynamic_entry = module_by_id
+      .get(&self.entry)
+      .and_then(|m| m.as_norm())
+      .map_or(false, |m| m.is_dynamic_entry);
+    ChunkInfo {
+      name: self.id.value().to_string(),
+      filename: self.filename.clone().unwrap(),
+      is_entry: self.is_user_defined_entry,
+      is_dynamic_entry,
+      facade_module_id: (self.is_user_defined_entry || is_dynamic_entry)
+        .then(|| self.entry.clone()),
+      module_ids: self
+        .ordered_modules(module_by_id)
+        .into_iter()
+        .filter_map(|m| m.as_norm())
+        .map(|m| m.id.clone())
+        .collect(),
+      exports: self.exports.iter().map(|name| name.to_string()).collect(),
+    }
+  }
+
   fn ordered_modules<'m>(&self, module_by_id: &'m ModuleById) -> Vec<&'m NormOrExt> {
     let mut modules = self
       .modules
@@ -80,7 +128,7 @@ impl Chunk {
     graph: &Graph,
     input_options: &BuildInputOptions,
     output_options: &BuildOutputOptions,
-  ) -> UnaryBuildResult<String> {
+  ) -> UnaryBuildResult<RenderedChunk> {
     let mut runtime_code = self.runtime_helpers.generate_helpers().join("\n");
     runtime_code.push('\n');
 
@@ -96,41 +144,164 @@ impl Chunk {
       .map(|item| COMPILER.print_module_item(item, None).unwrap())
       .join("\n");
 
-    let code = self
+    let rendered_modules = self
       .ordered_modules(&graph.module_by_id)
       .iter()
       .filter_map(|m| m.as_norm())
       .filter(|m| m.is_included())
       .map(|module| module.render(&ctx, input_options))
-      .collect::<Vec<_>>()
-      .join("\n");
-
-    let mut code = before_code + runtime_code.as_ref() + code.as_ref() + after_code.as_ref();
-
-    if output_options.format.is_cjs() {
-      // Workaround for cjs output
-      let comments = SingleThreadedComments::default();
-      let fm = COMPILER.create_source_file(PathBuf::from(self.id.value().to_string()), code);
-      let mut program = COMPILER
-        .parse_with_comments(
-          fm.clone(),
-          swc_core::ecma::parser::Syntax::Es(Default::default()),
-          Some(&comments),
-        )
-        .map_err(|e| BuildError::parse_js_failed(fm.clone(), e))?;
-
-      program = GLOBALS.set(&Default::default(), || {
-        rolldown_swc_visitors::to_cjs(
-          program,
-          Mark::new(),
-          &comments,
-          self.export_mode.is_default() && self.is_user_defined_entry,
-        )
-      });
+      .collect::<Vec<_>>();
 
-      code = COMPILER.print(&program, Some(&comments))?;
+    let wrapper = self.format_wrapper(input_options, output_options)?;
+    let mut code = wrapper
+      .as_ref()
+      .map_or_else(String::new, |wrapper| wrapper.intro.clone())
+      + &before_code
+      + &runtime_code;
+    let mut line_offset = count_lines(&code);
+    let mut module_maps = vec![];
+    for (idx, rendered) in rendered_modules.iter().enumerate() {
+      if idx > 0 {
+        code.push('\n');
+        line_offset += 1;
+      }
+      if let Some(map) = &rendered.map {
+        module_maps.push((line_offset, map));
+      }
+      code.push_str(&rendered.code);
+      line_offset += count_lines(&rendered.code);
+    }
+    code.push_str(&after_code);
+    if let Some(wrapper) = wrapper {
+      if !code.ends_with('\n') {
+        code.push('\n');
+      }
+      code.push_str(&wrapper.outro);
     }
-    Ok(code)
+
+    let map = ctx.source_map.then(|| {
+      let filename = self.filename.as_deref().unwrap();
+      let chunk_dir = output_options
+        .dir
+        .as_ref()
+        .map(PathBuf::from)
+        .unwrap_or_else(|| input_options.cwd.join("dist"))
+        .join(filename)
+        .parent()
+        .unwrap()
+        .to_path_buf();
+      concat_source_maps(
+        Path::new(filename)
+          .file_name()
+          .and_then(|name| name.to_str()),
+        module_maps,
+        !output_options.sourcemap_exclude_sources,
+        |source| {
+          let path = Path::new(source);
+          if path.is_absolute() {
+            path
+              .relative(&chunk_dir)
+              .to_string_lossy()
+              .replace('\\', "/")
+          } else {
+            source.to_string()
+          }
+        },
+      )
+    });
+
+    Ok(RenderedChunk { code, map })
+  }
+
+  /// Code around the chunk for formats wrapping it in a function.
+  fn format_wrapper(
+    &self,
+    input_options: &BuildInputOptions,
+    output_options: &BuildOutputOptions,
+  ) -> UnaryBuildResult<Option<FormatWrapper>> {
+    let exports = match self.export_mode {
+      ExportMode::Named => WrappedExports::Named,
+      ExportMode::Default => WrappedExports::Default,
+      _ => WrappedExports::None,
+    };
+    // Global variables are only needed for dependencies passed to the function.
+    let globals = if matches!(
+      output_options.format,
+      ModuleFormat::Iife | ModuleFormat::Umd
+    ) {
+      resolve_globals(
+        self
+          .factory_dependencies
+          .iter()
+          .filter(|dep| dep.param.is_some())
+          .map(|dep| &dep.source),
+        input_options,
+        output_options,
+      )
+    } else {
+      Default::default()
+    };
+    let deps = self
+      .factory_dependencies
+      .iter()
+      .map(|dep| WrappedDependency {
+        source: &dep.source,
+        param: dep.param.as_deref(),
+        global: dep
+          .param
+          .as_ref()
+          .and_then(|_| globals.get(&dep.source))
+          .map(|global| global.as_str()),
+      })
+      .collect::<Vec<_>>();
+    let name = output_options.name.as_deref();
+    Ok(match output_options.format {
+      ModuleFormat::Iife => {
+        if exports != WrappedExports::None && name.is_none() {
+          (input_options.on_warn)(BuildError::missing_name_option_for_iife_export());
+        }
+        Some(iife_wrapper(name, exports, &deps))
+      }
+      ModuleFormat::Amd => Some(amd_wrapper(
+        output_options.amd.id.as_deref(),
+        &output_options.amd.define,
+        exports,
+        self.requires_lazily,
+        &deps,
+      )),
+      ModuleFormat::Umd => {
+        if exports != WrappedExports::None && name.is_none() {
+          return Err(BuildError::missing_name_option_for_umd_export());
+        }
+        Some(umd_wrapper(
+          name,
+          output_options.amd.id.as_deref(),
+          &output_options.amd.define,
+          exports,
+          &deps,
+        ))
+      }
+      ModuleFormat::System => {
+        let setters = self
+          .system_dependencies
+          .iter()
+          .map(|dep| {
+            let setter = dep.setter.as_ref().map_or_else(
+              || "null".to_string(),
+              |setter| COMPILER.print_expr(setter).unwrap(),
+            );
+            (&*dep.source, setter)
+          })
+          .collect::<Vec<_>>();
+        let bindings = self
+          .system_bindings
+          .iter()
+          .map(|binding| &**binding)
+          .collect::<Vec<_>>();
+        Some(system_wrapper(&setters, &bindings))
+      }
+      ModuleFormat::Esm | ModuleFormat::Cjs => None,
+    })
   }
 
   /// Deconflicting is to rename identifiers to avoid conflicts.
@@ -150,7 +321,14 @@ impl Chunk {
       .flatten()
       .collect::<FxHashSet<_>>();
 
-    used_names.extend(preset_of_used_names(&ctx.output_options.format));
+    used_names.extend(preset_of_used_names(ctx.output_options));
+    used_names.extend(
+      self
+        .runtime_helpers
+        .used_names()
+        .into_iter()
+        .map(JsWord::from),
+    );
 
     let mut id_to_name = FxHashMap::default();
     let mut root_id_to_name = FxHashMap::default();
@@ -289,6 +467,7 @@ impl Chunk {
         top_level_id_to_final_name: &id_to_name,
         split_point_id_to_chunk_id: ctx.split_point_id_to_chunk_id,
         top_level_names,
+        rendered_dynamic_imports: &Default::default(),
       };
 
       self
@@ -305,12 +484,14 @@ impl Chunk {
         top_level_id_to_final_name: &id_to_name,
         split_point_id_to_chunk_id: ctx.split_point_id_to_chunk_id,
         top_level_names,
+        rendered_dynamic_imports: &Default::default(),
       };
       self
         .after_module_items
         .visit_mut_with(&mut rolldown_swc_visitors::finalizer(finalize_ctx));
     }
 
+    let empty_rendered_dynamic_imports: FxHashMap<JsWord, Box<ast::Expr>> = Default::default();
     ordered_modules
       .into_par_iter()
       .filter_map(|m| m.as_norm_mut())
@@ -324,11 +505,91 @@ impl Chunk {
           top_level_id_to_final_name: &id_to_name,
           split_point_id_to_chunk_id: ctx.split_point_id_to_chunk_id,
           top_level_names,
+          rendered_dynamic_imports: ctx
+            .rendered_dynamic_imports
+            .get(&m.id)
+            .unwrap_or(&empty_rendered_dynamic_imports),
         };
 
         m.ast
           .visit_mut_with(&mut rolldown_swc_visitors::finalizer(finalize_ctx));
       });
+
+    if ctx.output_options.format.is_cjs_based() {
+      self.links_to_cjs(&mut ctx)?;
+    } else if ctx.output_options.format == ModuleFormat::System {
+      self.links_to_system(&mut ctx);
+    }
+    Ok(())
+  }
+
+  /// Imports of the chunk become dependencies of `System.register`, whose setters assign imported bindings, and
+  /// exports are updated by `exports` calls in modules.
+  fn links_to_system(&mut self, ctx: &mut FinalizeBundleContext) {
+    let mut modules = ctx
+      .modules
+      .values_mut()
+      .filter_map(|m| m.as_norm_mut())
+      .map(|m| &mut m.ast)
+      .collect::<Vec<_>>();
+    let links = rolldown_swc_visitors::links_to_system(
+      std::mem::take(&mut self.before_module_items),
+      std::mem::take(&mut self.after_module_items),
+      &mut modules,
+    );
+    self.before_module_items = links.before;
+    self.system_dependencies = links.dependencies;
+    self.system_bindings = links.bindings;
+  }
+
+  /// Imports and exports of the chunk are turned into `require` calls and getters on `exports`, and references of
+  /// imported bindings in modules are rewritten to members of the required modules. Formats wrapping the chunk in a
+  /// function get dependencies from parameters of the function instead.
+  fn links_to_cjs(&mut self, ctx: &mut FinalizeBundleContext) -> UnaryBuildResult<()> {
+    let format = match ctx.output_options.format {
+      ModuleFormat::Cjs => CjsLinksFormat::CommonJs,
+      ModuleFormat::Amd => CjsLinksFormat::Amd,
+      _ => CjsLinksFormat::Factory,
+    };
+    let chunk_filename_by_id = ctx.chunk_filename_by_id;
+    // Other chunks are imported by `./{filename}`.
+    let is_external = |source: &str| {
+      source.strip_prefix("./").map_or(true, |filename| {
+        !chunk_filename_by_id
+          .values()
+          .any(|chunk_filename| chunk_filename == filename)
+      })
+    };
+    let interop_by_external_id = ctx.interop_by_external_id;
+    let interop = |id: Option<&str>| {
+      interop_by_external_id
+        .get(&id.map(JsWord::from))
+        .copied()
+        .unwrap_or(InteropMode::Auto)
+    };
+    let mut modules = ctx
+      .modules
+      .values_mut()
+      .filter_map(|m| m.as_norm_mut())
+      .map(|m| &mut m.ast)
+      .collect::<Vec<_>>();
+    let links = rolldown_swc_visitors::links_to_cjs(
+      std::mem::take(&mut self.before_module_items),
+      std::mem::take(&mut self.after_module_items),
+      &mut modules,
+      rolldown_swc_visitors::CjsLinksOptions {
+        format,
+        is_external: &is_external,
+        interop: &interop,
+        default_export: self.export_mode.is_default(),
+        // Names of interop helpers are reserved by `preset_of_used_names` for CommonJS based output.
+        runtime_helpers: &self.runtime_helpers,
+      },
+    )?;
+    self.before_module_items = links.before;
+    self.after_module_items = links.after;
+    self.factory_dependencies = links.dependencies;
+    self.requires_lazily = links.requires_lazily;
     Ok(())
   }
 
@@ -421,6 +682,32 @@ impl Chunk {
         }
       });
 
+    entry_module
+      .interop_exports()
+      .filter(|interop| {
+        ctx
+          .interop_imported_by_other_chunks
+          .contains(&interop.local_id)
+      })
+      .for_each(|interop| {
+        exports_in_scope.insert(interop.exported_as.clone(), interop.clone());
+      });
+
+    self.exports = exports_in_scope
+      .keys()
+      .cloned()
+      .chain(exports_out_scope.values().flat_map(|specifiers| {
+        specifiers.iter().map(|(exported_name, spec)| {
+          if exported_name == &"*" {
+            spec.local_id.name().clone()
+          } else {
+            (*exported_name).clone()
+          }
+        })
+      }))
+      .sorted()
+      .collect();
+
     // imports and re-exports
     let module_items = depended_modules
       .par_iter()
@@ -554,7 +841,11 @@ impl Chunk {
     self.before_module_items = module_items;
 
     if self.is_user_defined_entry {
-      self.validate_export_mode(ctx.output_options, &exports_in_scope)?;
+      self.validate_export_mode(
+        ctx.output_options,
+        &exports_in_scope,
+        !entry_module.commonjs_modules_of_re_export_all.is_empty(),
+      )?;
     }
 
     if !exports_in_scope.is_empty() {
@@ -566,16 +857,49 @@ impl Chunk {
       );
       self.after_module_items.push(exports);
     }
+
+    // Names of CommonJS modules re-exported by `export *` are only known at runtime, so they're copied to `exports` by
+    // `__reExport(exports, import_foo)`, which `links_to_cjs` keeps after modules of the chunk.
+    let commonjs_re_exports = &entry_module.commonjs_modules_of_re_export_all;
+    if !commonjs_re_exports.is_empty() {
+      if ctx.output_options.format.is_cjs_based() && self.export_mode.is_named() {
+        self.runtime_helpers.re_export();
+        commonjs_re_exports.values().for_each(|import_symbol| {
+          self
+            .after_module_items
+            .push(ast::ModuleItem::Stmt(ast::Stmt::Expr(ast::ExprStmt {
+              span: Default::default(),
+              expr: Box::new(rolldown_ast_template::build_re_export_call(
+                ast::Expr::Ident(quote_ident!("exports")),
+                import_symbol.clone().to_id(),
+              )),
+            })));
+        });
+      } else {
+        (ctx.input_options.on_warn)(BuildError::unexportable_commonjs_reexport(
+          entry_module.id.as_ref(),
+          commonjs_re_exports
+            .keys()
+            .map(|id| id.to_string().into())
+            .collect(),
+        ));
+      }
+    }
     Ok(())
   }
 
+  /// `has_dynamic_exports` means the entry re-exports CommonJS modules, whose names are only known at runtime.
   fn validate_export_mode(
     &mut self,
     output_options: &BuildOutputOptions,
     exports: &FxHashMap<JsWord, ExportedSpecifier>,
+    has_dynamic_exports: bool,
   ) -> UnaryBuildResult<()> {
-    // validate export mode
-    if output_options.format.is_cjs() {
+    // validate export mode. Exports of SystemJS modules are always named.
+    if !matches!(
+      output_options.format,
+      ModuleFormat::Esm | ModuleFormat::System
+    ) {
       match output_options.export_mode {
         ExportMode::Default => {
           if !exports.contains_key(&js_word!("default")) || exports.len() != 1 {
@@ -600,9 +924,12 @@ impl Chunk {
           }
         }
         ExportMode::Auto => {
-          if exports.is_empty() {
+          if exports.is_empty() && !has_dynamic_exports {
             self.export_mode = ExportMode::None;
-          } else if exports.len() == 1 && exports.contains_key(&js_word!("default")) {
+          } else if exports.len() == 1
+            && exports.contains_key(&js_word!("default"))
+            && !has_dynamic_exports
+          {
             self.export_mode = ExportMode::Default;
           } else {
             if !output_options.format.is_es() && exports.contains_key(&js_word!("default")) {
@@ -621,7 +948,49 @@ impl Chunk {
 }
 
 #[derive(Debug)]
-pub(crate) struct RenderContext {}
+pub(crate) struct RenderContext {
+  pub(crate) source_map: bool,
+}
+
+pub(crate) struct RenderedChunk {
+  pub(crate) code: String,
+  /// Maps `code` to the original files. Sources are relative to the chunk.
+  pub(crate) map: Option<SourceMap>,
+}
+
+/// Global variables of external modules imported by the chunk. Missing ones are guessed from their ids, such as
+/// `lodash-es` -> `lodashEs`.
+fn resolve_globals<'s>(
+  sources: impl Iterator<Item = &'s JsWord>,
+  input_options: &BuildInputOptions,
+  output_options: &BuildOutputOptions,
+) -> FxHashMap<JsWord, String> {
+  sources
+    .unique()
+    .map(|source| {
+      let global = output_options
+        .globals
+        .get(&**source)
+        .cloned()
+        .unwrap_or_else(|| {
+          let guessed = make_legal(
+            &CAPTURE_WORD_RE
+              .replace_all(&**source, |caps: &regex::Captures| caps[1].to_uppercase()),
+          );
+          (input_options.on_warn)(BuildError::missing_global_name(
+            source.to_string(),
+            guessed.clone(),
+          ));
+          guessed
+        });
+      (source.clone(), global)
+    })
+    .collect()
+}
+
+fn count_lines(code: &str) -> u32 {
+  code.bytes().filter(|byte| *byte == b'\n').count() as u32
+}
 
 pub(crate) struct FinalizeBundleContext<'me> {
   pub modules: ModuleRefMutById<'me>,
@@ -630,5 +999,9 @@ pub(crate) struct FinalizeBundleContext<'me> {
   pub uf: &'me UnionFind<Symbol>,
   // pub unresolved_mark: Mark,
   pub unresolved_ctxt: SyntaxContext,
+  pub input_options: &'me BuildInputOptions,
   pub output_options: &'me BuildOutputOptions,
+  pub rendered_dynamic_imports: &'me RenderedDynamicImports,
+  pub interop_imported_by_other_chunks: &'me FxHashSet<Symbol>,
+  pub interop_by_external_id: &'me FxHashMap<Option<JsWord>, InteropMode>,
 }
@@ -2,14 +2,26 @@ use std::path::Component;
 
 use hashlink::LinkedHashSet;
 use itertools::Itertools;
+use once_cell::sync::Lazy;
 // use petgraph::stable_graph::NodeIndex;
-use rolldown_common::{ChunkId, ModuleId};
+use rolldown_common::{is_virtual_id, split_query, ChunkId, ModuleId, VIRTUAL_ID_PREFIX};
 use rustc_hash::{FxHashMap, FxHashSet};
 use sugar_path::{AsPath, SugarPath};
 use swc_core::ecma::atoms::JsWord;
 use tracing::instrument;
 
+static ILLEGAL_CHUNK_NAME_CHARACTERS: Lazy<regex::Regex> =
+  Lazy::new(|| regex::Regex::new(r#"[\x00?*:<>|"]"#).unwrap());
+
 pub fn uri_to_chunk_name(root: &str, uri: &str) -> String {
+  if is_virtual_id(uri) {
+    // `\0virtual:routes` => `virtual_routes`
+    return ILLEGAL_CHUNK_NAME_CHARACTERS
+      .replace_all(uri.trim_start_matches(VIRTUAL_ID_PREFIX), "_")
+      .to_string();
+  }
+  // `./foo.svg?raw` => `./foo.svg`
+  let (uri, _query) = split_query(uri);
   let path = uri.as_path();
   let mut relatived = path.relative(root);
   let _ext = relatived
@@ -130,11 +142,13 @@ impl<'me> CodeSplitter<'me> {
   pub fn analyze_entries(&mut self, mut entries: Vec<ModuleId>, is_entry_chunk: bool) {
     while let Some(entry) = entries.pop() {
       let _exec_order = self.graph.module_by_id[&entry].exec_order();
-      let chunk = Chunk::new(
-        uri_to_chunk_name(&self.opts.cwd.to_string_lossy(), entry.as_ref()),
-        entry.clone(),
-        is_entry_chunk,
-      );
+      let chunk_name = self
+        .graph
+        .entry_chunk_names
+        .get(&entry)
+        .cloned()
+        .unwrap_or_else(|| uri_to_chunk_name(&self.opts.cwd.to_string_lossy(), entry.as_ref()));
+      let chunk = Chunk::new(chunk_name, entry.clone(), is_entry_chunk);
       self
         .split_point_module_to_chunk
         .insert(entry.clone(), chunk.id.clone());
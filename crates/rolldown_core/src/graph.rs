@@ -1,15 +1,15 @@
-use std::sync::Arc;
-
 use derivative::Derivative;
+use hashlink::LinkedHashSet;
 use itertools::Itertools;
 use rayon::prelude::{ParallelBridge, ParallelIterator};
 use rolldown_common::{ExportedSpecifier, ImportedSpecifier, ModuleId, Symbol, UnionFind};
-use rolldown_resolver::Resolver;
+use rolldown_swc_visitors::StatementPart;
 use rolldown_tracing::ContextedTracer;
 use rustc_hash::FxHashSet as HashSet;
 use rustc_hash::{FxHashMap, FxHashSet};
 use sugar_path::AsPath;
 use swc_core::common::{Mark, SyntaxContext, GLOBALS};
+use swc_core::ecma::ast;
 use swc_core::ecma::atoms::{js_word, JsWord};
 use tracing::instrument;
 
@@ -24,6 +24,8 @@ use crate::{BuildError, BuildResult, SharedBuildInputOptions, SharedBuildPluginD
 pub struct Graph {
   pub input_options: SharedBuildInputOptions,
   pub entries: Vec<ModuleId>,
+  /// Names of entry chunks specified explicitly, such as the name of a chunk emitted by plugins.
+  pub(crate) entry_chunk_names: FxHashMap<ModuleId, String>,
   pub(crate) module_by_id: ModuleById,
   pub(crate) unresolved_mark: Mark,
   pub(crate) unresolved_ctxt: SyntaxContext,
@@ -47,6 +49,7 @@ impl Graph {
     Self {
       input_options,
       entries: Default::default(),
+      entry_chunk_names: Default::default(),
       module_by_id: Default::default(),
       unresolved_mark,
       unresolved_ctxt,
@@ -195,12 +198,433 @@ impl Graph {
       .collect::<Vec<_>>();
     order_modules.sort_unstable_by_key(|id| self.module_by_id[id].exec_order());
 
+    self.create_esm_inits(&order_modules);
+    self.link_commonjs(&order_modules);
+    self.link_esm_inits(&order_modules);
     self.link_exports(&order_modules)?;
     self.link_imports(&order_modules)?;
+    self.inline_const_enums(&order_modules);
 
     Ok(())
   }
 
+  /// Members of `const enum`s imported from other modules are inlined as literals.
+  /// ```ts
+  /// // foo.ts
+  /// export const enum Foo { Bar = 1 }
+  /// // main.ts
+  /// import { Foo } from './foo'
+  /// console.log(Foo.Bar)
+  /// ```
+  /// `Foo.Bar` becomes `1`. If `Foo` isn't referenced anymore, the enum is treeshaken in `foo.ts`.
+  #[instrument(skip_all)]
+  fn inline_const_enums(&mut self, order_modules: &[ModuleId]) {
+    order_modules
+      .iter()
+      .filter(|importer_id| !importer_id.is_external())
+      .for_each(|importer_id| {
+        let importer = Self::fetch_normal_module(&self.module_by_id, importer_id);
+        let enums = importer
+          .imports
+          .iter()
+          .flat_map(|(importee_id, specs)| specs.iter().map(move |spec| (importee_id, spec)))
+          .filter_map(|(importee_id, spec)| {
+            let exported = Self::fetch_module(&self.module_by_id, importee_id)
+              .as_norm()?
+              .find_exported(&spec.imported)?;
+            let owner = Self::fetch_module(&self.module_by_id, &exported.owner).as_norm()?;
+            if exported.local_id.ctxt() != owner.top_level_ctxt {
+              return None;
+            }
+            let members = owner.const_enums.get(exported.local_id.name())?;
+            Some((spec.imported_as.clone().to_id(), members.clone()))
+          })
+          .collect::<FxHashMap<_, _>>();
+
+        if enums.is_empty() {
+          return;
+        }
+
+        let importer = Self::fetch_normal_module_mut(&mut self.module_by_id, importer_id);
+        let unreferenced = rolldown_swc_visitors::inline_const_enums(&mut importer.ast, &enums);
+        importer.parts.parts.iter_mut().for_each(|part| {
+          part
+            .referenced
+            .retain(|symbol| !unreferenced.contains(symbol.as_id()))
+        });
+      });
+  }
+
+  /// Decide which ESM modules are evaluated lazily by `init_foo()` rather than in `exec_order`:
+  /// - Modules in import cycles, whose evaluation order can't be kept by concatenating modules.
+  /// - Modules reached through `require`, which are evaluated on the first `require()`.
+  /// - All modules with `strict_execution_order`.
+  #[instrument(skip_all)]
+  fn create_esm_inits(&mut self, order_modules: &[ModuleId]) {
+    let mut wrapped = if self.input_options.strict_execution_order {
+      order_modules.iter().cloned().collect()
+    } else {
+      self.collect_modules_in_cycles(order_modules)
+    };
+    order_modules
+      .iter()
+      .filter_map(|id| Self::fetch_module(&self.module_by_id, id).as_norm())
+      .for_each(|module| {
+        wrapped.extend(
+          module
+            .required
+            .keys()
+            .filter_map(|specifier| module.resolved_module_ids.get(specifier))
+            .cloned(),
+        );
+      });
+
+    order_modules
+      .iter()
+      .filter(|id| wrapped.contains(*id))
+      .for_each(|id| {
+        if let NormOrExt::Normal(module) = Self::fetch_module_mut(&mut self.module_by_id, id) {
+          if !module.is_commonjs {
+            module.create_esm_init();
+          }
+        }
+      });
+  }
+
+  /// Find strongly connected components of static dependencies with Tarjan's algorithm, and
+  /// return modules in components with more than one module.
+  fn collect_modules_in_cycles(&self, order_modules: &[ModuleId]) -> FxHashSet<ModuleId> {
+    let mut index_by_id: FxHashMap<&ModuleId, usize> = FxHashMap::default();
+    let mut low_link_by_id: FxHashMap<&ModuleId, usize> = FxHashMap::default();
+    let mut stack: Vec<&ModuleId> = vec![];
+    let mut on_stack: FxHashSet<&ModuleId> = FxHashSet::default();
+    let mut in_cycles = FxHashSet::default();
+
+    for root in order_modules {
+      if index_by_id.contains_key(root) {
+        continue;
+      }
+      // (module, index of the next dependency to visit)
+      let mut work_list = vec![(root, 0)];
+      while let Some((id, next_dep)) = work_list.pop() {
+        if next_dep == 0 {
+          let index = index_by_id.len();
+          index_by_id.insert(id, index);
+          low_link_by_id.insert(id, index);
+          stack.push(id);
+          on_stack.insert(id);
+        }
+
+        let dependencies = Self::fetch_module(&self.module_by_id, id).dependencies();
+        if let Some(dep) = dependencies.get(next_dep) {
+          work_list.push((id, next_dep + 1));
+          if !index_by_id.contains_key(dep) {
+            work_list.push((dep, 0));
+          } else if on_stack.contains(dep) {
+            let low_link = low_link_by_id[id].min(index_by_id[dep]);
+            low_link_by_id.insert(id, low_link);
+          }
+          continue;
+        }
+
+        if low_link_by_id[id] == index_by_id[id] {
+          let mut component = vec![];
+          while let Some(member) = stack.pop() {
+            on_stack.remove(member);
+            component.push(member);
+            if member == id {
+              break;
+            }
+          }
+          if component.len() > 1 {
+            in_cycles.extend(component.into_iter().cloned());
+          }
+        }
+        if let Some(&(parent, _)) = work_list.last() {
+          let low_link = low_link_by_id[parent].min(low_link_by_id[id]);
+          low_link_by_id.insert(parent, low_link);
+        }
+      }
+    }
+
+    in_cycles
+  }
+
+  /// Importers of modules wrapped by `__esm` call `init_foo()` before the imported bindings are used.
+  /// ```js
+  /// import { foo } from './foo'
+  /// ```
+  /// will be transformed to
+  /// ```js
+  /// init_foo();
+  /// ```
+  /// If the importer is also wrapped, the call ends up in its own `init` function.
+  #[instrument(skip_all)]
+  fn link_esm_inits(&mut self, order_modules: &[ModuleId]) {
+    order_modules
+      .iter()
+      .filter(|importer_id| !importer_id.is_external())
+      .for_each(|importer_id| {
+        let importer = Self::fetch_normal_module(&self.module_by_id, importer_id);
+        let wrapped_importees = static_importee_ids(importer)
+          .into_iter()
+          .filter_map(
+            |importee_id| match Self::fetch_module(&self.module_by_id, importee_id) {
+              NormOrExt::Normal(importee) => importee
+                .esm_init
+                .clone()
+                .map(|init| (importee_id.clone(), init)),
+              NormOrExt::External(_) => None,
+            },
+          )
+          .collect_vec();
+
+        if wrapped_importees.is_empty() {
+          return;
+        }
+
+        let importer = Self::fetch_normal_module_mut(&mut self.module_by_id, importer_id);
+        let module_items = wrapped_importees
+          .into_iter()
+          .map(|(importee_id, init)| {
+            let init_symbol = importer.create_top_level_symbol(&init.exported_as);
+            importer
+              .imports
+              .entry(importee_id)
+              .or_default()
+              .push(ImportedSpecifier {
+                imported_as: init_symbol.clone(),
+                imported: init.exported_as,
+              });
+            importer.add_statement_part(StatementPart {
+              referenced: HashSet::from_iter([init_symbol.clone()]),
+              side_effect: true,
+              ..Default::default()
+            });
+            rolldown_ast_template::build_call_stmt(init_symbol.to_id())
+          })
+          .collect_vec();
+
+        // Imports are hoisted
+        importer.ast.body.splice(0..0, module_items);
+      });
+  }
+
+  /// CommonJS interop, which needs to be done before linking exports and imports.
+  /// - `require('./foo')` is rewritten to `require_foo()`, which is linked to the wrapper of `foo.js`.
+  /// - Imports from CommonJS modules are transformed into reading properties of `module.exports`.
+  /// - Entries written in CommonJS export `module.exports` as the default export.
+  #[instrument(skip_all)]
+  fn link_commonjs(&mut self, order_modules: &[ModuleId]) {
+    order_modules
+      .iter()
+      .filter(|importer_id| !importer_id.is_external())
+      .for_each(|importer_id| {
+        // Imports from CommonJS modules go first, since `link_required` adds imports of wrappers.
+        self.link_imports_from_commonjs(importer_id);
+        self.link_required(importer_id);
+
+        let module = Self::fetch_normal_module_mut(&mut self.module_by_id, importer_id);
+        if module.is_commonjs && (module.is_user_defined_entry || module.is_dynamic_entry) {
+          module.export_commonjs_as_default();
+        }
+      });
+  }
+
+  fn link_required(&mut self, importer_id: &ModuleId) {
+    let importer = Self::fetch_normal_module(&self.module_by_id, importer_id);
+    let required = importer
+      .required
+      .iter()
+      .map(|(specifier, placeholder)| {
+        (
+          specifier.clone(),
+          placeholder.clone(),
+          importer.resolved_module_ids.get(specifier).cloned(),
+        )
+      })
+      .collect_vec();
+
+    let mut wrapper_by_specifier = FxHashMap::default();
+    for (specifier, placeholder, importee_id) in required {
+      match importee_id.filter(|id| !id.is_external()) {
+        Some(importee_id) => {
+          let wrapper = Self::fetch_normal_module_mut(&mut self.module_by_id, &importee_id)
+            .get_or_create_require_wrapper();
+          Self::fetch_normal_module_mut(&mut self.module_by_id, importer_id)
+            .imports
+            .entry(importee_id)
+            .or_default()
+            .push(ImportedSpecifier {
+              imported_as: placeholder.clone(),
+              imported: wrapper.exported_as,
+            });
+          wrapper_by_specifier.insert(specifier, placeholder.to_id());
+        }
+        None => {
+          // `require('fs')` of external modules are kept as they are
+          Self::fetch_normal_module_mut(&mut self.module_by_id, importer_id)
+            .parts
+            .parts
+            .iter_mut()
+            .for_each(|part| {
+              part.referenced.remove(&placeholder);
+            });
+        }
+      }
+    }
+
+    let importer = Self::fetch_normal_module_mut(&mut self.module_by_id, importer_id);
+    rolldown_swc_visitors::rewrite_require_calls(
+      &mut importer.ast,
+      self.unresolved_ctxt,
+      &wrapper_by_specifier,
+    );
+  }
+
+  /// ```js
+  /// import foo, { bar } from './foo.cjs'
+  /// export { baz } from './foo.cjs'
+  /// ```
+  /// will be transformed to
+  /// ```js
+  /// var import_foo = __toESM(require_foo());
+  /// var foo = import_foo.default;
+  /// var bar = import_foo.bar;
+  /// var baz = import_foo.baz;
+  /// export { baz }
+  /// ```
+  /// Names of `module.exports` are unknown statically, so `export * from './foo.cjs'` only creates `import_foo`,
+  /// which is copied to the namespace of the importer by `__reExport` at runtime. It's also created for CommonJS
+  /// modules re-exported through ES modules, since the namespace of the importer contains their names too.
+  fn link_imports_from_commonjs(&mut self, importer_id: &ModuleId) {
+    let importer = Self::fetch_normal_module(&self.module_by_id, importer_id);
+    let mut re_exported_all = LinkedHashSet::new();
+    collect_commonjs_modules_of_re_export_all(
+      &self.module_by_id,
+      importer_id,
+      &mut FxHashSet::default(),
+      &mut re_exported_all,
+    );
+    let commonjs_importees = static_importee_ids(importer)
+      .into_iter()
+      .chain(re_exported_all.iter())
+      .unique()
+      .filter_map(
+        |importee_id| match Self::fetch_module(&self.module_by_id, importee_id) {
+          NormOrExt::Normal(importee) if importee.is_commonjs => Some((
+            importee_id.clone(),
+            importee.legal_stem(),
+            importee.require_wrapper.clone().unwrap(),
+            re_exported_all.contains(importee_id),
+          )),
+          _ => None,
+        },
+      )
+      .collect_vec();
+
+    if commonjs_importees.is_empty() {
+      return;
+    }
+
+    let importer = Self::fetch_normal_module_mut(&mut self.module_by_id, importer_id);
+    let mut module_items = vec![];
+    for (importee_id, stem, wrapper, is_re_exported_all) in commonjs_importees {
+      let specs = importer.imports.remove(&importee_id).unwrap_or_default();
+      let re_exports = importer
+        .re_exported_ids
+        .remove(&importee_id)
+        .unwrap_or_default();
+
+      let require_symbol = importer.create_top_level_symbol(&format!("require_{stem}").into());
+      importer.imports.insert(
+        importee_id.clone(),
+        vec![ImportedSpecifier {
+          imported_as: require_symbol.clone(),
+          imported: wrapper.exported_as,
+        }],
+      );
+
+      if specs.is_empty() && re_exports.is_empty() && !is_re_exported_all {
+        // import './foo.cjs'
+        module_items.push(ast::ModuleItem::Stmt(ast::Stmt::Expr(ast::ExprStmt {
+          span: Default::default(),
+          expr: Box::new(rolldown_ast_template::build_require_call(
+            require_symbol.clone().to_id(),
+          )),
+        })));
+        importer.add_statement_part(StatementPart {
+          referenced: HashSet::from_iter([require_symbol]),
+          side_effect: true,
+          ..Default::default()
+        });
+        continue;
+      }
+
+      let import_symbol = importer.create_top_level_symbol(&format!("import_{stem}").into());
+      module_items.push(rolldown_ast_template::build_var_decl_stmt(
+        import_symbol.clone().to_id(),
+        rolldown_ast_template::build_to_esm_call(require_symbol.clone().to_id()),
+      ));
+      importer.add_statement_part(StatementPart {
+        declared: HashSet::from_iter([import_symbol.clone()]),
+        referenced: HashSet::from_iter([require_symbol]),
+        side_effect: true,
+        ..Default::default()
+      });
+      importer.runtime_helpers.to_esm();
+      if is_re_exported_all {
+        importer
+          .commonjs_modules_of_re_export_all
+          .insert(importee_id, import_symbol.clone());
+      }
+
+      let re_exports = re_exports
+        .into_iter()
+        .map(|spec| {
+          let local_symbol =
+            importer.create_top_level_symbol(if spec.exported_as != js_word!("default") {
+              &spec.exported_as
+            } else {
+              &spec.imported
+            });
+          importer.add_to_linked_exports(
+            spec.exported_as.clone(),
+            ExportedSpecifier {
+              exported_as: spec.exported_as,
+              local_id: local_symbol.clone(),
+              owner: importer_id.clone(),
+            },
+          );
+          ImportedSpecifier {
+            imported_as: local_symbol,
+            imported: spec.imported,
+          }
+        })
+        .collect_vec();
+
+      specs.into_iter().chain(re_exports).for_each(|spec| {
+        let value = if spec.imported == js_word!("*") {
+          ast::Expr::Ident(import_symbol.clone().to_id().into())
+        } else {
+          rolldown_ast_template::build_member_expr(import_symbol.clone().to_id(), spec.imported)
+        };
+        module_items.push(rolldown_ast_template::build_var_decl_stmt(
+          spec.imported_as.clone().to_id(),
+          value,
+        ));
+        importer.add_statement_part(StatementPart {
+          declared: HashSet::from_iter([spec.imported_as]),
+          referenced: HashSet::from_iter([import_symbol.clone()]),
+          ..Default::default()
+        });
+      });
+    }
+
+    // Imports are hoisted
+    importer.ast.body.splice(0..0, module_items);
+  }
+
   /// Example
   /// ```ts
   /// // index.ts
@@ -331,10 +755,17 @@ impl Graph {
 
         // Process re-export all
 
+        // CommonJS modules are re-exported by `__reExport` in `link_imports_from_commonjs`.
         let importee_of_being_re_exported_all =
-          Self::fetch_normal_module_mut(&mut self.module_by_id, importer_id)
+          Self::fetch_normal_module(&self.module_by_id, importer_id)
             .re_export_all
             .iter()
+            .filter(|importee_id| {
+              !matches!(
+                Self::fetch_module(&self.module_by_id, importee_id),
+                NormOrExt::Normal(importee) if importee.is_commonjs
+              )
+            })
             .cloned()
             .collect::<Vec<_>>();
 
@@ -632,6 +1063,59 @@ impl Graph {
                       },
                     );
 
+                    self
+                      .uf
+                      .union(&imported_spec.imported_as, &symbol_in_importee);
+                  } else if let Some(import_symbol) = importee
+                    .commonjs_modules_of_re_export_all
+                    .values()
+                    .next()
+                    .cloned()
+                  {
+                    // Handle case
+                    // ```js
+                    // // index.js
+                    // import { foo } from './foo'
+                    // // foo.js
+                    // export * from './bar.cjs'
+                    // ```
+                    // `var foo = import_bar.foo` is created in `foo.js`. Names of CommonJS modules are unknown
+                    // statically, so the first re-exported CommonJS module is used.
+                    let symbol_in_importee =
+                      importee.create_top_level_symbol(imported_spec.imported_as.name());
+                    importee
+                      .ast
+                      .body
+                      .push(rolldown_ast_template::build_var_decl_stmt(
+                        symbol_in_importee.clone().to_id(),
+                        rolldown_ast_template::build_member_expr(
+                          import_symbol.clone().to_id(),
+                          imported_spec.imported.clone(),
+                        ),
+                      ));
+                    importee.add_statement_part(StatementPart {
+                      declared: HashSet::from_iter([symbol_in_importee.clone()]),
+                      referenced: HashSet::from_iter([import_symbol]),
+                      ..Default::default()
+                    });
+
+                    importee.add_to_linked_exports(
+                      imported_spec.imported.clone(),
+                      ExportedSpecifier {
+                        exported_as: imported_spec.imported.clone(),
+                        local_id: symbol_in_importee.clone(),
+                        owner: importee_id.clone(),
+                      },
+                    );
+
+                    importer.add_to_linked_imports(
+                      &importee_id,
+                      ImportedSpecifier {
+                        imported: imported_spec.imported.clone(),
+                        imported_as: imported_spec.imported_as.clone(),
+                      },
+                    );
+
                     self
                       .uf
                       .union(&imported_spec.imported_as, &symbol_in_importee);
@@ -675,18 +1159,23 @@ impl Graph {
       .for_each(|module| {
         if let NormOrExt::Normal(module) = module {
           module.generate_namespace_export();
+          if module.esm_init.is_some() {
+            module.wrap_esm();
+          }
         }
       });
   }
 
   #[instrument(skip_all)]
   pub(crate) async fn generate_module_graph(&mut self) -> BuildResult<()> {
-    let resolver = Arc::new(Resolver::with_cwd(self.input_options.cwd.clone()));
+    let plugin_context = self.build_plugin_driver.read().await.context.clone();
 
     ModuleLoader::new(
       self,
-      resolver,
+      plugin_context.resolver.clone(),
+      plugin_context.tsconfig_resolver.clone(),
       self.build_plugin_driver.clone(),
+      plugin_context,
       self.input_options.clone(),
     )
     .fetch_all_modules()
@@ -718,6 +1207,53 @@ impl Graph {
   }
 }
 
+/// Modules imported by `import` and `export ... from` statements, in the order of statements.
+fn static_importee_ids(importer: &NormalModule) -> Vec<&ModuleId> {
+  importer
+    .ast
+    .body
+    .iter()
+    .filter_map(|module_item| match module_item {
+      ast::ModuleItem::ModuleDecl(ast::ModuleDecl::Import(decl)) => Some(&decl.src.value),
+      ast::ModuleItem::ModuleDecl(ast::ModuleDecl::ExportAll(decl)) => Some(&decl.src.value),
+      ast::ModuleItem::ModuleDecl(ast::ModuleDecl::ExportNamed(ast::NamedExport {
+        src: Some(src),
+        ..
+      })) => Some(&src.value),
+      _ => None,
+    })
+    .map(|specifier| &importer.resolved_module_ids[specifier])
+    .unique()
+    .collect()
+}
+
+/// CommonJS modules re-exported by `export *` of the module, including ones re-exported by `export *` of ES modules
+/// re-exported by the module.
+fn collect_commonjs_modules_of_re_export_all(
+  module_by_id: &ModuleById,
+  module_id: &ModuleId,
+  visited: &mut FxHashSet<ModuleId>,
+  collected: &mut LinkedHashSet<ModuleId>,
+) {
+  if !visited.insert(module_id.clone()) {
+    return;
+  }
+  if let NormOrExt::Normal(module) = &module_by_id[module_id] {
+    module
+      .re_export_all
+      .iter()
+      .for_each(|importee_id| match &module_by_id[importee_id] {
+        NormOrExt::Normal(importee) if importee.is_commonjs => {
+          collected.insert(importee_id.clone());
+        }
+        NormOrExt::Normal(_) => {
+          collect_commonjs_modules_of_re_export_all(module_by_id, importee_id, visited, collected)
+        }
+        NormOrExt::External(_) => {}
+      });
+  }
+}
+
 fn shim_missing_export_if_needed(importee: &mut NormalModule, imported_name: &JsWord) -> bool {
   if importee.find_exported(imported_name).is_some() {
     false
@@ -24,7 +24,7 @@ use swc_core::common::{FilePathMapping, Globals, SourceMap};
 mod bundle;
 mod norm_or_ext;
 pub use bundle::*;
-use swc_core::ecma::atoms::JsWord;
+use swc_core::ecma::{ast, atoms::JsWord};
 mod code_splitter;
 pub use code_splitter::*;
 mod chunk_graph;
@@ -40,6 +40,7 @@ pub use rolldown_output::*;
 pub(crate) type ResolvedModuleIds = FxHashMap<JsWord, ModuleId>;
 pub(crate) type MergedExports = FxHashMap<JsWord, ExportedSpecifier>;
 pub(crate) type SharedResolver = Arc<Resolver>;
+pub(crate) type SharedTsConfigResolver = Arc<TsConfigResolver>;
 pub(crate) use norm_or_ext::*;
 use once_cell::sync::Lazy;
 
@@ -55,6 +56,8 @@ pub(crate) static COMPILER: Lazy<Arc<rolldown_compiler::Compiler>> = Lazy::new(|
 pub(crate) type ModuleById = FxHashMap<ModuleId, NormOrExt>;
 pub(crate) type ModuleRefMutById<'a> = FxHashMap<&'a ModuleId, &'a mut NormOrExt>;
 pub(crate) type SplitPointIdToChunkId = FxHashMap<ModuleId, ChunkId>;
+/// Expressions rendered by the `render_dynamic_import` hook, keyed by the importer and the specifier.
+pub(crate) type RenderedDynamicImports = FxHashMap<ModuleId, FxHashMap<JsWord, Box<ast::Expr>>>;
 pub(crate) type SharedBuildInputOptions = Arc<BuildInputOptions>;
 pub(crate) static SWC_GLOBALS: Lazy<Arc<Globals>> = Lazy::new(|| Arc::new(Globals::new()));
 
@@ -1,8 +1,9 @@
-use std::collections::HashSet;
+use std::{collections::HashSet, sync::Arc};
 
 use futures::future::join_all;
-use rolldown_common::{ExportedSpecifier, ModuleId};
+use rolldown_common::{ExportedSpecifier, Loader, ModuleId};
 use rolldown_error::Errors;
+use rolldown_plugin::ModuleInfo;
 use rustc_hash::{FxHashMap, FxHashSet};
 use swc_core::common::{Mark, SyntaxContext, GLOBALS};
 
@@ -12,21 +13,24 @@ use module_task::{ModuleTask, TaskResult};
 use swc_core::ecma::atoms::js_word;
 use tracing::instrument;
 
-use crate::{norm_or_ext::NormOrExt, BuildInputOptions, Graph, NormalModule, SWC_GLOBALS};
+use crate::{norm_or_ext::NormOrExt, Graph, InputItem, NormalModule, SWC_GLOBALS};
 use crate::{
-  resolve_id, BuildError, BuildResult, ExternalModule, SharedBuildInputOptions,
-  SharedBuildPluginDriver, SharedResolver, StatementParts,
+  resolve_id, BuildError, BuildPluginContext, BuildResult, EmittedChunk, ExternalModule,
+  SharedBuildInputOptions, SharedBuildPluginDriver, SharedResolver, SharedTsConfigResolver,
+  StatementParts,
 };
 
 pub(crate) struct ModuleLoader<'a> {
   input_options: SharedBuildInputOptions,
   graph: &'a mut Graph,
   build_plugin_driver: SharedBuildPluginDriver,
+  plugin_context: Arc<BuildPluginContext>,
   loaded_modules: HashSet<ModuleId>,
   remaining_tasks: usize,
   tx: tokio::sync::mpsc::UnboundedSender<Msg>,
   rx: tokio::sync::mpsc::UnboundedReceiver<Msg>,
   resolver: SharedResolver,
+  tsconfig_resolver: SharedTsConfigResolver,
   errors: Vec<BuildError>,
   dynamic_imported_modules: FxHashSet<ModuleId>,
 }
@@ -41,7 +45,9 @@ impl<'a> ModuleLoader<'a> {
   pub(crate) fn new(
     graph: &'a mut Graph,
     resolver: SharedResolver,
+    tsconfig_resolver: SharedTsConfigResolver,
     plugin_driver: SharedBuildPluginDriver,
+    plugin_context: Arc<BuildPluginContext>,
     input_options: SharedBuildInputOptions,
   ) -> Self {
     let (tx, rx) = tokio::sync::mpsc::unbounded_channel::<Msg>();
@@ -52,16 +58,18 @@ impl<'a> ModuleLoader<'a> {
       tx,
       rx,
       resolver,
+      tsconfig_resolver,
       errors: Default::default(),
       build_plugin_driver: plugin_driver,
+      plugin_context,
       dynamic_imported_modules: Default::default(),
       input_options,
     }
   }
 
   // #[instrument(skip_all)]
-  async fn resolve_entries(&self, input_opts: &BuildInputOptions) -> BuildResult<Vec<ModuleId>> {
-    let futs = input_opts.input.iter().cloned().map(|input_item| {
+  async fn resolve_entries(&self, input_items: Vec<InputItem>) -> BuildResult<Vec<ModuleId>> {
+    let futs = input_items.into_iter().map(|input_item| {
       let build_plugin_driver = self.build_plugin_driver.clone();
       let resolver = self.resolver.clone();
       tokio::spawn(async move {
@@ -71,6 +79,7 @@ impl<'a> ModuleLoader<'a> {
           None,
           false,
           &build_plugin_driver,
+          None,
         )
         .await?;
 
@@ -116,7 +125,9 @@ impl<'a> ModuleLoader<'a> {
       );
     }
 
-    let resolved_entries = self.resolve_entries(&self.input_options).await?;
+    let resolved_entries = self
+      .resolve_entries(self.input_options.input.clone())
+      .await?;
 
     resolved_entries.into_iter().for_each(|entry_id| {
       self.loaded_modules.insert(entry_id.clone());
@@ -124,22 +135,33 @@ impl<'a> ModuleLoader<'a> {
       self.spawn_new_module_task(entry_id, true);
     });
 
-    while self.remaining_tasks > 0 {
-      let msg = self.rx.recv().await.unwrap();
-      match msg {
-        Msg::Scanned(res) => {
-          self.remaining_tasks -= 1;
-          self.handle_msg_scanned(res);
-        }
-        Msg::Error(errs) => {
-          self.remaining_tasks -= 1;
-          self.errors.extend(errs.into_vec());
+    loop {
+      while self.remaining_tasks > 0 {
+        let msg = self.rx.recv().await.unwrap();
+        match msg {
+          Msg::Scanned(res) => {
+            self.remaining_tasks -= 1;
+            self.handle_msg_scanned(res);
+          }
+          Msg::Error(errs) => {
+            self.remaining_tasks -= 1;
+            self.errors.extend(errs.into_vec());
+          }
         }
+        tracing::trace!("remaining: {}", self.remaining_tasks);
+      }
+
+      // Plugins might emit chunks while modules are loading. Load them as extra entries until no more are emitted.
+      let emitted_chunks = self.plugin_context.take_emitted_chunks();
+      if emitted_chunks.is_empty() || !self.errors.is_empty() {
+        break;
       }
-      tracing::trace!("remaining: {}", self.remaining_tasks);
+      self.add_emitted_chunks(emitted_chunks).await;
     }
+    self.plugin_context.finish_module_loading();
 
     self.mark_dynamic_imported_module();
+    self.mark_user_defined_entries();
 
     if self.errors.is_empty() {
       Ok(())
@@ -148,6 +170,47 @@ impl<'a> ModuleLoader<'a> {
     }
   }
 
+  async fn add_emitted_chunks(&mut self, emitted_chunks: Vec<EmittedChunk>) {
+    let names = emitted_chunks
+      .iter()
+      .map(|chunk| chunk.name.clone())
+      .collect::<Vec<_>>();
+    let input_items = emitted_chunks
+      .into_iter()
+      .map(|chunk| InputItem {
+        name: chunk.name.unwrap_or_default(),
+        import: chunk.id,
+      })
+      .collect();
+
+    match self.resolve_entries(input_items).await {
+      Ok(resolved_ids) => resolved_ids
+        .into_iter()
+        .zip(names)
+        .for_each(|(entry_id, name)| {
+          if let Some(name) = name {
+            self.graph.entry_chunk_names.insert(entry_id.clone(), name);
+          }
+          if !self.graph.entries.contains(&entry_id) {
+            self.graph.entries.push(entry_id.clone());
+          }
+          if self.loaded_modules.insert(entry_id.clone()) {
+            self.spawn_new_module_task(entry_id, true);
+          }
+        }),
+      Err(errs) => self.errors.extend(errs.into_vec()),
+    }
+  }
+
+  // An emitted chunk might point to a module that is already loaded as a normal dependency.
+  fn mark_user_defined_entries(&mut self) {
+    self.graph.entries.iter().for_each(|id| {
+      if let Some(NormOrExt::Normal(module)) = self.graph.module_by_id.get_mut(id) {
+        module.is_user_defined_entry = true;
+      }
+    });
+  }
+
   fn mark_dynamic_imported_module(&mut self) {
     self.dynamic_imported_modules.iter().for_each(|id| {
       let module =
@@ -176,6 +239,7 @@ impl<'a> ModuleLoader<'a> {
       unresolved_ctxt: self.graph.unresolved_ctxt,
       is_user_defined_entry,
       resolver: self.resolver.clone(),
+      tsconfig_resolver: self.tsconfig_resolver.clone(),
       plugin_driver: self.build_plugin_driver.clone(),
       is_external: self.input_options.is_external.clone(),
       input_options: self.input_options.clone(),
@@ -205,17 +269,34 @@ impl<'a> ModuleLoader<'a> {
           runtime_helpers: Default::default(),
           exports: Default::default(),
         };
+        self.plugin_context.add_module_info(ModuleInfo {
+          id: id.clone(),
+          is_entry: false,
+          is_external: true,
+          imported_ids: vec![],
+          dynamically_imported_ids: vec![],
+        });
         self.graph.add_module(NormOrExt::External(external_module));
       } else {
         self.spawn_new_module_task(id.clone(), false);
       }
     });
 
-    let dependencies = scan_result
+    let mut dependencies: Vec<ModuleId> = scan_result
       .dependencies
       .iter()
       .map(|id| resolved_ids[id].clone())
       .collect();
+    // Required modules are executed by `require()` lazily, but they still need to be bundled.
+    scan_result
+      .required
+      .keys()
+      .filter_map(|specifier| resolved_ids.get(specifier))
+      .for_each(|id| {
+        if !dependencies.contains(id) {
+          dependencies.push(id.clone());
+        }
+      });
 
     let dyn_dependencies: Vec<ModuleId> = scan_result
       .dyn_dependencies
@@ -252,7 +333,15 @@ impl<'a> ModuleLoader<'a> {
 
     let top_level_ctxt = result.top_level_ctxt;
 
-    let normal_module = NormalModule {
+    self.plugin_context.add_module_info(ModuleInfo {
+      id: module_id.clone(),
+      is_entry: result.is_user_defined_entry,
+      is_external: false,
+      imported_ids: dependencies.clone(),
+      dynamically_imported_ids: dyn_dependencies.clone(),
+    });
+
+    let mut normal_module = NormalModule {
       dependencies,
       dyn_dependencies,
       exec_order: usize::MAX,
@@ -269,8 +358,10 @@ impl<'a> ModuleLoader<'a> {
       is_facade_namespace_id_referenced: false,
       visited_global_names: scan_result.visited_global_names,
       external_modules_of_re_export_all: Default::default(),
+      commonjs_modules_of_re_export_all: Default::default(),
       is_dynamic_entry: false,
       comments: result.comments,
+      transformed_map: result.transformed_map,
       imports,
       linked_imports: Default::default(),
       local_exports: scan_result.local_exports.clone(),
@@ -283,7 +374,18 @@ impl<'a> ModuleLoader<'a> {
       runtime_helpers: Default::default(),
       parts: StatementParts::from_parts(scan_result.statement_parts),
       missing_exports: Default::default(),
+      is_commonjs: scan_result.is_commonjs,
+      require_wrapper: None,
+      esm_init: None,
+      required: scan_result.required,
+      const_enums: result.const_enums,
     };
+    if normal_module.is_commonjs {
+      normal_module.wrap_commonjs();
+    }
+    if matches!(result.loader, Loader::Binary) {
+      normal_module.runtime_helpers.to_binary();
+    }
     self.graph.add_module(NormOrExt::Normal(normal_module));
   }
 }
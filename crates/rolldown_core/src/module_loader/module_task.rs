@@ -2,13 +2,14 @@ use std::path::PathBuf;
 
 use derivative::Derivative;
 use futures::future::join_all;
-use rolldown_common::{Loader, ModuleId};
+use rolldown_common::{Loader, ModuleId, SourceMap};
 use rolldown_error::Errors;
+use rolldown_plugin::{DynamicImportSpecifier, LoadArgs, ResolveDynamicImportArgs};
 use rolldown_resolver::Resolver;
-use rolldown_swc_visitors::ScanResult;
+use rolldown_swc_visitors::{ConstEnums, ScanResult};
 use rustc_hash::FxHashMap;
 use sugar_path::AsPath;
-use swc_core::common::{Mark, SyntaxContext, GLOBALS};
+use swc_core::common::{Mark, Spanned, SyntaxContext, GLOBALS};
 use swc_core::ecma::ast;
 use swc_core::ecma::atoms::JsWord;
 use swc_core::ecma::parser::{EsConfig, Syntax, TsConfig};
@@ -17,9 +18,10 @@ use tracing::instrument;
 
 use super::Msg;
 use crate::{
-  extract_loader_by_path, resolve_id, BuildError, BuildResult, IsExternal, ResolvedModuleIds,
-  SharedBuildInputOptions, SharedBuildPluginDriver, SharedResolver, UnaryBuildResult, COMPILER,
-  SWC_GLOBALS,
+  binary_to_esm, extract_loader_by_path, json_to_esm, load_input_source_map, resolve_id,
+  text_to_esm, BuildError, BuildResult, IsExternal, JsxOptions, JsxRuntime, ResolvedModuleIds,
+  SharedBuildInputOptions, SharedBuildPluginDriver, SharedResolver, SharedTsConfigResolver,
+  TsCompilerOptions, UnaryBuildResult, COMPILER, SWC_GLOBALS,
 };
 
 pub(crate) struct ModuleTask {
@@ -32,6 +34,7 @@ pub(crate) struct ModuleTask {
   pub(crate) unresolved_mark: Mark,
   pub(crate) unresolved_ctxt: SyntaxContext,
   pub(crate) resolver: SharedResolver,
+  pub(crate) tsconfig_resolver: SharedTsConfigResolver,
   pub(crate) plugin_driver: SharedBuildPluginDriver,
   pub(crate) is_external: IsExternal,
 }
@@ -52,7 +55,15 @@ impl ModuleTask {
       return Ok(ModuleId::new(specifier, true));
     }
 
-    let resolved_id = resolve_id(resolver, specifier, Some(importer), false, plugin_driver).await?;
+    let resolved_id = resolve_id(
+      resolver,
+      specifier,
+      Some(importer),
+      false,
+      plugin_driver,
+      None,
+    )
+    .await?;
 
     if let Some(resolved) = resolved_id {
       let is_resolved_marked_as_external =
@@ -68,6 +79,62 @@ impl ModuleTask {
     }
   }
 
+  /// Plugins have the chance to resolve `import('...')` first.
+  async fn resolve_dynamic_import_id(
+    resolver: &Resolver,
+    importer: &ModuleId,
+    specifier: &str,
+    plugin_driver: &SharedBuildPluginDriver,
+    is_external: &IsExternal,
+  ) -> UnaryBuildResult<ModuleId> {
+    let resolved = plugin_driver
+      .read()
+      .await
+      .resolve_dynamic_import(ResolveDynamicImportArgs {
+        importer,
+        specifier: DynamicImportSpecifier::Literal(specifier),
+      })
+      .await?;
+
+    match resolved {
+      Some(resolved) => Ok(ModuleId::new(resolved.id, resolved.external)),
+      None => Self::resolve_id(resolver, importer, specifier, plugin_driver, is_external).await,
+    }
+  }
+
+  /// Non-literal dynamic imports could only be resolved by plugins. Resolved ones are rewritten to string literals,
+  /// so they are treated as literal dynamic imports in later phases. Unresolved ones are kept as they are.
+  async fn resolve_dynamic_import_expressions(
+    &self,
+    ast: &mut ast::Module,
+    result: &mut ScanResult,
+    resolved_ids: &mut ResolvedModuleIds,
+  ) -> UnaryBuildResult<()> {
+    let mut specifier_by_span = FxHashMap::default();
+    for expr in std::mem::take(&mut result.dyn_import_expressions) {
+      let code = COMPILER.print_expr(&expr)?;
+      let resolved = self
+        .plugin_driver
+        .read()
+        .await
+        .resolve_dynamic_import(ResolveDynamicImportArgs {
+          importer: &self.id,
+          specifier: DynamicImportSpecifier::Expression(&code),
+        })
+        .await?;
+
+      if let Some(resolved) = resolved {
+        let specifier: JsWord = code.into();
+        specifier_by_span.insert(expr.span(), specifier.clone());
+        result.dyn_dependencies.insert(specifier.clone());
+        resolved_ids.insert(specifier, ModuleId::new(resolved.id, resolved.external));
+      }
+    }
+
+    rolldown_swc_visitors::rewrite_dynamic_import_expressions(ast, &specifier_by_span);
+    Ok(())
+  }
+
   #[instrument(skip_all)]
   pub(crate) async fn run(self) {
     let tx = self.tx.clone();
@@ -88,24 +155,54 @@ impl ModuleTask {
     let dependencies = result
       .dependencies
       .iter()
-      .chain(result.dyn_dependencies.iter());
+      .map(|specifier| (specifier, false))
+      .chain(
+        result
+          .dyn_dependencies
+          .iter()
+          // Static imports take precedence over dynamic imports with the same specifier
+          .filter(|specifier| !result.dependencies.contains(*specifier))
+          .map(|specifier| (specifier, true)),
+      )
+      .chain(
+        result
+          .required
+          .keys()
+          .filter(|specifier| {
+            !result.dependencies.contains(*specifier)
+              && !result.dyn_dependencies.contains(*specifier)
+          })
+          .map(|specifier| (specifier, false)),
+      );
 
-    let jobs = dependencies.cloned().map(|specifier| {
+    let jobs = dependencies.map(|(specifier, is_dynamic)| {
+      let specifier = specifier.clone();
       let resolver = self.resolver.clone();
       let plugin_driver = self.plugin_driver.clone();
       let importer = self.id.clone();
       let is_external = self.is_external.clone();
 
       tokio::spawn(async move {
-        Self::resolve_id(
-          &resolver,
-          &importer,
-          &specifier,
-          &plugin_driver,
-          &is_external,
-        )
-        .await
-        .map(|id| (specifier.clone(), id))
+        let resolved = if is_dynamic {
+          Self::resolve_dynamic_import_id(
+            &resolver,
+            &importer,
+            &specifier,
+            &plugin_driver,
+            &is_external,
+          )
+          .await
+        } else {
+          Self::resolve_id(
+            &resolver,
+            &importer,
+            &specifier,
+            &plugin_driver,
+            &is_external,
+          )
+          .await
+        };
+        resolved.map(|id| (specifier, id))
       })
     });
 
@@ -131,41 +228,124 @@ impl ModuleTask {
     }
   }
 
-  async fn run_inner(self) -> BuildResult<TaskResult> {
-    // load hook
-    let code = tokio::fs::read_to_string(self.id.as_ref())
-      .await
-      .map_err(|e| BuildError::io_error(e))
-      .map_err(|e| e.context(format!("Read file: {}", self.id.as_ref())))?;
-
-    let loader = if self.input_options.builtins.detect_loader_by_ext {
-      extract_loader_by_path(self.id.as_path())
-    } else {
-      Loader::Js
-    };
-
-    let code = self
+  /// Plugins have the chance to load the module first. Otherwise, the module is read from the disk.
+  /// The content is returned as bytes, since modules of binary loaders are not necessarily UTF-8.
+  async fn load(&self) -> UnaryBuildResult<(Vec<u8>, Loader)> {
+    let loaded = self
       .plugin_driver
       .read()
       .await
-      .transform(&self.id, code)
+      .load(LoadArgs { id: &self.id })
       .await?;
 
-    let (mut ast, comments) = parse_to_js_ast(&self.id, code, loader, &self.input_options)?;
+    let (code, loader) = match loaded {
+      Some(loaded) => (loaded.code.into_bytes(), loaded.loader),
+      None => {
+        if self.id.is_virtual() {
+          return Err(BuildError::unloaded_virtual_module(self.id.as_ref()));
+        }
+        let path = self.id.path_without_query();
+        let code = tokio::fs::read(path)
+          .await
+          .map_err(BuildError::io_error)
+          .map_err(|e| e.context(format!("Read file: {path}")))?;
+        (code, None)
+      }
+    };
+
+    let loader = loader.unwrap_or_else(|| {
+      if self.input_options.builtins.detect_loader_by_ext {
+        extract_loader_by_path(
+          self.id.path_without_query().as_path(),
+          &self.input_options.builtins.loaders,
+        )
+      } else {
+        Loader::Js
+      }
+    });
+
+    Ok((code, loader))
+  }
+
+  /// A broken input source map only makes the output source map less accurate, so it's reported as a warning.
+  async fn load_input_source_map(&self, code: &str) -> Option<SourceMap> {
+    if self.id.is_virtual() {
+      return None;
+    }
+    match load_input_source_map(self.id.path_without_query().as_path(), code).await {
+      Ok(map) => map,
+      Err(err) => {
+        (self.input_options.on_warn)(err);
+        None
+      }
+    }
+  }
+
+  async fn run_inner(self) -> BuildResult<TaskResult> {
+    let (content, loader) = self.load().await?;
+
+    let (mut ast, comments, transformed_map, const_enums) = if loader.is_binary() {
+      let context = self.plugin_driver.read().await.context.clone();
+      let ast = binary_to_esm(
+        self.id.path_without_query().as_path(),
+        content,
+        loader,
+        |name, source| context.emit_referenced_asset(name, source),
+      );
+      (ast, SwcComments::default(), None, Default::default())
+    } else {
+      let code = String::from_utf8(content)
+        .map_err(|e| BuildError::io_error(std::io::Error::new(std::io::ErrorKind::InvalidData, e)))
+        .map_err(|e| e.context(format!("Read {} as UTF-8", self.id.as_ref())))?;
+
+      let input_map = self.load_input_source_map(&code).await;
+      let transformed = self
+        .plugin_driver
+        .read()
+        .await
+        .transform(&self.id, code, input_map)
+        .await?;
+
+      let ts_compiler_options =
+        if matches!(loader, Loader::Jsx | Loader::Ts | Loader::Tsx) && !self.id.is_virtual() {
+          self
+            .tsconfig_resolver
+            .compiler_options_of(self.id.path_without_query().as_path())?
+        } else {
+          None
+        };
+      let (ast, comments, const_enums) = parse_to_js_ast(
+        &self.id,
+        transformed.code,
+        loader,
+        &self.input_options,
+        ts_compiler_options.as_deref(),
+      )?;
+      (ast, comments, transformed.map, const_enums)
+    };
 
     // No matter what, the ast should be a pure valid JavaScript in this phrase
     GLOBALS.set(&SWC_GLOBALS, || {
       rolldown_swc_visitors::resolve(&mut ast, self.unresolved_mark, self.top_level_mark);
     });
 
-    let result = rolldown_swc_visitors::scan(
+    let mut result = rolldown_swc_visitors::scan(
       &mut ast,
       self.top_level_ctxt,
       self.unresolved_ctxt,
       self.id.clone(),
     );
 
-    let resolved_ids = self.resolve_dependencies(&result).await?;
+    let mut resolved_ids = self.resolve_dependencies(&result).await?;
+    // `require('fs')` of external modules are kept as they are, so there's no need to create modules for them.
+    resolved_ids.retain(|specifier, id| {
+      !id.is_external()
+        || result.dependencies.contains(specifier)
+        || result.dyn_dependencies.contains(specifier)
+    });
+    self
+      .resolve_dynamic_import_expressions(&mut ast, &mut result, &mut resolved_ids)
+      .await?;
 
     Ok(TaskResult {
       module_id: self.id,
@@ -176,6 +356,9 @@ impl ModuleTask {
       resolved_ids,
       comments,
       is_user_defined_entry: self.is_user_defined_entry,
+      transformed_map,
+      loader,
+      const_enums,
     })
   }
 }
@@ -192,15 +375,20 @@ pub(crate) struct TaskResult {
   #[derivative(Debug = "ignore")]
   pub comments: SwcComments,
   pub is_user_defined_entry: bool,
+  pub transformed_map: Option<SourceMap>,
+  pub loader: Loader,
+  pub const_enums: ConstEnums,
 }
 
 /// This function should emit valid JavaScript AST(with JSX)
+/// `ts_compiler_options` comes from the nearest `tsconfig.json` of TypeScript and JSX modules.
 fn parse_to_js_ast(
   id: &ModuleId,
   source: String,
   loader: Loader,
   input_options: &SharedBuildInputOptions,
-) -> UnaryBuildResult<(ast::Module, SwcComments)> {
+  ts_compiler_options: Option<&TsCompilerOptions>,
+) -> UnaryBuildResult<(ast::Module, SwcComments, ConstEnums)> {
   match loader {
     Loader::Js | Loader::Jsx | Loader::Ts | Loader::Tsx => {
       let is_jsx_or_tsx = matches!(loader, Loader::Jsx | Loader::Tsx);
@@ -222,20 +410,106 @@ fn parse_to_js_ast(
       let mut ast = COMPILER
         .parse_with_comments(fm.clone(), syntax, Some(&comments))
         .map_err(|e| BuildError::parse_js_failed(fm, e).context(format!("{loader:?}")))?;
+      let default_compiler_options = TsCompilerOptions::default();
+      let compiler_options = ts_compiler_options.unwrap_or(&default_compiler_options);
+      let jsx_options = resolve_jsx_options(&input_options.builtins.jsx, compiler_options);
+      let mut const_enums = ConstEnums::default();
       if is_ts_or_tsx {
+        const_enums = rolldown_swc_visitors::collect_exported_const_enums(&ast);
+        let use_define_for_class_fields = input_options
+          .builtins
+          .tsconfig
+          .use_define_for_class_fields
+          .or_else(|| compiler_options.resolved_use_define_for_class_fields())
+          .unwrap_or(false);
+        let decorators = (compiler_options.experimental_decorators == Some(true)).then(|| {
+          rolldown_swc_visitors::DecoratorsConfig {
+            legacy: true,
+            use_define_for_class_fields,
+            ..Default::default()
+          }
+        });
         rolldown_swc_visitors::ts_to_js(
           &mut ast,
           rolldown_swc_visitors::TsConfig {
-            use_define_for_class_fields: input_options
-              .builtins
-              .tsconfig
-              .use_define_for_class_fields,
+            use_define_for_class_fields,
+            import_not_used_as_values: if compiler_options.preserves_unused_imports() {
+              rolldown_swc_visitors::ImportsNotUsedAsValues::Preserve
+            } else {
+              rolldown_swc_visitors::ImportsNotUsedAsValues::Remove
+            },
+            pragma: jsx_options.pragma.clone(),
+            pragma_frag: jsx_options.pragma_frag.clone(),
             ..Default::default()
           },
+          decorators,
+        );
+      }
+      if is_jsx_or_tsx {
+        rolldown_swc_visitors::jsx_to_js(
+          &mut ast,
+          COMPILER.cm.clone(),
+          Some(&comments),
+          jsx_options,
         );
       }
-      Ok((ast, comments))
+      Ok((ast, comments, const_enums))
     }
-    Loader::Json => unimplemented!(),
+    Loader::Json => Ok((
+      json_to_esm(id, source)?,
+      SwcComments::default(),
+      Default::default(),
+    )),
+    Loader::Text => Ok((
+      text_to_esm(source),
+      SwcComments::default(),
+      Default::default(),
+    )),
+    Loader::Base64 | Loader::DataUrl | Loader::Binary | Loader::File | Loader::Empty => {
+      unreachable!("Modules of binary loaders are converted by `binary_to_esm`")
+    }
+  }
+}
+
+/// Builtin JSX options take precedence over `compilerOptions` of `tsconfig.json`.
+fn resolve_jsx_options(
+  options: &JsxOptions,
+  compiler_options: &TsCompilerOptions,
+) -> rolldown_swc_visitors::JsxOptions {
+  let (runtime_in_tsconfig, development_in_tsconfig) = match compiler_options.jsx.as_deref() {
+    Some("react") => (Some(JsxRuntime::Classic), None),
+    Some("react-jsx") => (Some(JsxRuntime::Automatic), Some(false)),
+    Some("react-jsxdev") => (Some(JsxRuntime::Automatic), Some(true)),
+    _ => (None, None),
+  };
+  let runtime = match options
+    .runtime
+    .or(runtime_in_tsconfig)
+    .unwrap_or(JsxRuntime::Classic)
+  {
+    JsxRuntime::Classic => rolldown_swc_visitors::JsxRuntime::Classic,
+    JsxRuntime::Automatic => rolldown_swc_visitors::JsxRuntime::Automatic,
+  };
+  rolldown_swc_visitors::JsxOptions {
+    runtime: Some(runtime),
+    pragma: options
+      .pragma
+      .clone()
+      .or_else(|| compiler_options.jsx_factory.clone()),
+    pragma_frag: options
+      .pragma_frag
+      .clone()
+      .or_else(|| compiler_options.jsx_fragment_factory.clone()),
+    import_source: options
+      .import_source
+      .clone()
+      .or_else(|| compiler_options.jsx_import_source.clone()),
+    development: Some(
+      options
+        .development
+        .or(development_in_tsconfig)
+        .unwrap_or(false),
+    ),
+    ..Default::default()
   }
 }
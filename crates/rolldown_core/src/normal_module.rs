@@ -1,11 +1,12 @@
 use derivative::Derivative;
-use hashlink::LinkedHashSet;
+use hashlink::{LinkedHashMap, LinkedHashSet};
 use itertools::Itertools;
 use rolldown_common::{
-  ExportedSpecifier, ImportedSpecifier, ModuleId, ReExportedSpecifier, Symbol,
+  collapse_source_maps, ExportedSpecifier, ImportedSpecifier, ModuleId, ReExportedSpecifier,
+  SourceMap, Symbol,
 };
 use rolldown_runtime_helpers::RuntimeHelpers;
-use rolldown_swc_visitors::StatementPart;
+use rolldown_swc_visitors::{ConstEnums, StatementPart};
 use rustc_hash::{FxHashMap as HashMap, FxHashSet as HashSet};
 use sugar_path::{AsPath, SugarPath};
 use swc_core::{
@@ -64,6 +65,9 @@ pub struct NormalModule {
   pub(crate) visited_global_names: HashSet<JsWord>,
   // (ModuleId, ImportStarId)
   pub(crate) external_modules_of_re_export_all: LinkedHashSet<ModuleId>,
+  /// CommonJS modules re-exported by `export *` directly or through other modules, and `import_foo` of them in this
+  /// module. Their names are only known at runtime, so they're copied to the namespace by `__reExport`.
+  pub(crate) commonjs_modules_of_re_export_all: LinkedHashMap<ModuleId, Symbol>,
 
   pub(crate) runtime_helpers: RuntimeHelpers,
 
@@ -77,8 +81,25 @@ pub struct NormalModule {
   #[derivative(Debug = "ignore")]
   pub(crate) comments: SwcComments,
 
+  /// Maps the transformed code to the original sources, through the input source map of the loaded code if any.
+  /// `None` means there's nothing to trace back, or positions are lost by a transform without a source map.
+  pub(crate) transformed_map: Option<SourceMap>,
+
   /// Key is missing exported name
   pub(crate) missing_exports: HashMap<JsWord, Symbol>,
+
+  /// The module is written in CommonJS, which is wrapped by `__commonJS`.
+  pub(crate) is_commonjs: bool,
+  /// The function returning `module.exports` of the module, such as `require_foo` in
+  /// `var require_foo = __commonJS(...)`. ESM modules only have it when they are `require`d.
+  pub(crate) require_wrapper: Option<ExportedSpecifier>,
+  /// Key is the specifier of `require('./foo')` and value is the placeholder symbol created in scanning.
+  pub(crate) required: LinkedHashMap<JsWord, Symbol>,
+  /// The function evaluating the module lazily, such as `init_foo` in `var init_foo = __esm(...)`.
+  /// Only modules in import cycles or `require`d, or all ESM modules with `strict_execution_order`, have it.
+  pub(crate) esm_init: Option<ExportedSpecifier>,
+  /// Members of `export const enum Foo {}` with known values, which are inlined into importers.
+  pub(crate) const_enums: ConstEnums,
 }
 
 impl NormalModule {
@@ -173,11 +194,210 @@ impl NormalModule {
   pub(crate) fn find_exported(&self, exported_name: &JsWord) -> Option<&ExportedSpecifier> {
     if exported_name == "*" {
       Some(&self.facade_id_for_namespace)
+    } else if let Some(interop) = self
+      .interop_exports()
+      .find(|interop| &interop.exported_as == exported_name)
+    {
+      Some(interop)
     } else {
       self.linked_exports.get(exported_name)
     }
   }
 
+  /// Functions generated for interop, which are not exported by users but could be imported by
+  /// other modules. They are `require_foo` and `init_foo`.
+  pub(crate) fn interop_exports(&self) -> impl Iterator<Item = &ExportedSpecifier> {
+    self.require_wrapper.iter().chain(self.esm_init.iter())
+  }
+
+  fn set_require_wrapper(&mut self, wrapper: Symbol) -> ExportedSpecifier {
+    let spec = ExportedSpecifier {
+      exported_as: wrapper.name().clone(),
+      local_id: wrapper,
+      owner: self.id.clone(),
+    };
+    self.require_wrapper = Some(spec.clone());
+    spec
+  }
+
+  /// CommonJS modules are evaluated lazily on the first `require()`, so the whole module is wrapped as
+  /// ```js
+  /// var require_foo = __commonJS(function (exports, module) {
+  ///   module.exports = 'foo';
+  /// });
+  /// ```
+  pub(crate) fn wrap_commonjs(&mut self) {
+    debug_assert!(self.is_commonjs);
+    let stem = self.legal_stem();
+    let wrapper = self.create_top_level_symbol(&format!("require_{stem}").into());
+    let body = std::mem::take(&mut self.ast.body)
+      .into_iter()
+      .map(|module_item| match module_item {
+        ast::ModuleItem::Stmt(stmt) => stmt,
+        ast::ModuleItem::ModuleDecl(_) => unreachable!("CommonJS modules have no import/export"),
+      })
+      .collect();
+    self
+      .ast
+      .body
+      .push(rolldown_ast_template::build_commonjs_wrapper_stmt(
+        wrapper.clone().to_id(),
+        body,
+      ));
+
+    // The module becomes a single statement without side effects. Top-level names of the
+    // module are still declared by it to keep them deconflicted with other modules.
+    let mut part = StatementPart {
+      declared: HashSet::from_iter([wrapper.clone()]),
+      ..Default::default()
+    };
+    std::mem::take(&mut self.parts.parts)
+      .into_iter()
+      .for_each(|each| {
+        part.declared.extend(each.declared);
+        part.referenced.extend(each.referenced);
+      });
+    self.parts = StatementParts::from_parts(vec![part]);
+
+    self.runtime_helpers.common_js();
+    self.set_require_wrapper(wrapper);
+  }
+
+  /// Return the function returning `module.exports` of the module. For ESM modules, it's generated as
+  /// ```js
+  /// function require_foo() { return __toCommonJS(foo_exports); }
+  /// ```
+  /// The module is evaluated by `init_foo()` first, if it's wrapped by `__esm`.
+  pub(crate) fn get_or_create_require_wrapper(&mut self) -> ExportedSpecifier {
+    if let Some(wrapper) = &self.require_wrapper {
+      return wrapper.clone();
+    }
+    let stem = self.legal_stem();
+    let wrapper = self.create_top_level_symbol(&format!("require_{stem}").into());
+    let namespace = self.facade_id_for_namespace.local_id.clone();
+    self.suggest_name(&js_word!("*"), &format!("{stem}_exports").into());
+    self.mark_namespace_id_referenced();
+    let init = self.esm_init.as_ref().map(|init| init.local_id.clone());
+    self
+      .ast
+      .body
+      .push(rolldown_ast_template::build_esm_require_wrapper_stmt(
+        wrapper.clone().to_id(),
+        namespace.clone().to_id(),
+        init.clone().map(|init| init.to_id()),
+      ));
+    self.add_statement_part(StatementPart {
+      declared: HashSet::from_iter([wrapper.clone()]),
+      referenced: HashSet::from_iter([namespace].into_iter().chain(init)),
+      is_included: Default::default(),
+      side_effect: false,
+    });
+    self.runtime_helpers.to_common_js();
+    self.set_require_wrapper(wrapper)
+  }
+
+  /// Create `init_foo` for the module, which is going to be wrapped by `__esm` in `wrap_esm`.
+  /// It should be done before any other module links to the module.
+  pub(crate) fn create_esm_init(&mut self) {
+    debug_assert!(!self.is_commonjs);
+    let stem = self.legal_stem();
+    let init = self.create_top_level_symbol(&format!("init_{stem}").into());
+    self.esm_init = Some(ExportedSpecifier {
+      exported_as: init.name().clone(),
+      local_id: init,
+      owner: self.id.clone(),
+    });
+  }
+
+  /// Evaluate the module lazily to keep the execution order of ESM. Declarations stay at the top level,
+  /// and the rest of the module runs on the first call of `init_foo()`.
+  /// ```js
+  /// var foo;
+  /// var init_foo = __esm(function () {
+  ///   init_bar();
+  ///   foo = bar + 1;
+  /// });
+  /// ```
+  /// Entries call `init_foo()` by themselves, and importers call it before the module is used.
+  pub(crate) fn wrap_esm(&mut self) {
+    let init = self
+      .esm_init
+      .clone()
+      .expect("`create_esm_init` should be called before wrapping");
+    // Exports are removed before being split, since the rest of statements are moved into a function.
+    rolldown_swc_visitors::remove_export_and_import(&mut self.ast);
+    let lazy_stmts = rolldown_swc_visitors::split_lazy_statements(
+      &mut self.ast,
+      &self.facade_id_for_namespace.local_id.clone().to_id(),
+    );
+    self
+      .ast
+      .body
+      .push(rolldown_ast_template::build_esm_wrapper_stmt(
+        init.local_id.clone().to_id(),
+        lazy_stmts,
+      ));
+
+    // Like CommonJS modules, the wrapped module becomes a single statement without side effects.
+    let mut part = StatementPart {
+      declared: HashSet::from_iter([init.local_id.clone()]),
+      ..Default::default()
+    };
+    std::mem::take(&mut self.parts.parts)
+      .into_iter()
+      .for_each(|each| {
+        part.declared.extend(each.declared);
+        part.referenced.extend(each.referenced);
+      });
+    self.parts = StatementParts::from_parts(vec![part]);
+
+    if self.is_user_defined_entry || self.is_dynamic_entry {
+      self.ast.body.push(rolldown_ast_template::build_call_stmt(
+        init.local_id.clone().to_id(),
+      ));
+      self.add_statement_part(StatementPart {
+        referenced: HashSet::from_iter([init.local_id]),
+        side_effect: true,
+        ..Default::default()
+      });
+    }
+
+    self.runtime_helpers.esm();
+  }
+
+  /// Entries written in CommonJS export `module.exports` as the default export.
+  /// ```js
+  /// var foo = require_foo();
+  /// export { foo as default }
+  /// ```
+  pub(crate) fn export_commonjs_as_default(&mut self) {
+    let wrapper = self
+      .require_wrapper
+      .clone()
+      .expect("CommonJS modules should be wrapped");
+    let default_symbol = Symbol::new(js_word!("default"), self.top_level_ctxt);
+    self
+      .ast
+      .body
+      .push(rolldown_ast_template::build_var_decl_stmt(
+        default_symbol.clone().to_id(),
+        rolldown_ast_template::build_require_call(wrapper.local_id.clone().to_id()),
+      ));
+    self.add_statement_part(StatementPart {
+      declared: HashSet::from_iter([default_symbol.clone()]),
+      referenced: HashSet::from_iter([wrapper.local_id]),
+      is_included: Default::default(),
+      side_effect: true,
+    });
+    let spec = ExportedSpecifier {
+      exported_as: js_word!("default"),
+      local_id: default_symbol,
+      owner: self.id.clone(),
+    };
+    self.local_exports.insert(js_word!("default"), spec.clone());
+    self.add_to_linked_exports(js_word!("default"), spec);
+  }
+
   pub(crate) fn add_to_linked_exports(&mut self, name: JsWord, spec: ExportedSpecifier) {
     debug_assert!(&name != "*");
     debug_assert!(
@@ -236,6 +456,9 @@ impl NormalModule {
       if !self.external_modules_of_re_export_all.is_empty() {
         self.runtime_helpers.merge_namespaces();
       };
+      if !self.commonjs_modules_of_re_export_all.is_empty() {
+        self.runtime_helpers.re_export();
+      }
 
       let external_modules_and_star_symbol = self
         .external_modules_of_re_export_all
@@ -269,6 +492,7 @@ impl NormalModule {
               .iter()
               .map(|(_, star_symbol)| star_symbol),
           )
+          .chain(self.commonjs_modules_of_re_export_all.values())
           .cloned()
           .collect(),
         is_included: false.into(),
@@ -306,6 +530,11 @@ impl NormalModule {
           .iter()
           .map(|(_, id)| id.clone().to_id())
           .collect(),
+        self
+          .commonjs_modules_of_re_export_all
+          .values()
+          .map(|import_symbol| import_symbol.clone().to_id())
+          .collect(),
       );
 
       self.ast.body.push(namespace_export);
@@ -313,12 +542,16 @@ impl NormalModule {
   }
 
   #[instrument(skip_all)]
-  pub(crate) fn render(&self, _ctx: &RenderContext, options: &BuildInputOptions) -> String {
+  pub(crate) fn render(&self, ctx: &RenderContext, options: &BuildInputOptions) -> RenderedModule {
     let comments = SingleThreadedComments::default();
 
     let mut text = String::new();
     text.push(' ');
-    text.push_str(&self.id.as_path().relative(&options.cwd).to_string_lossy());
+    if self.id.is_virtual() {
+      text.push_str(&self.id.as_ref().replacen('\0', "\\0", 1));
+    } else {
+      text.push_str(&self.id.as_path().relative(&options.cwd).to_string_lossy());
+    }
     comments.add_leading(
       self.ast.span_lo(),
       Comment {
@@ -328,7 +561,26 @@ impl NormalModule {
       },
     );
 
-    COMPILER.print(&self.ast, Some(&comments)).unwrap()
+    if !ctx.source_map {
+      return RenderedModule {
+        code: COMPILER.print(&self.ast, Some(&comments)).unwrap(),
+        map: None,
+      };
+    }
+
+    let (code, mut mappings) = COMPILER
+      .print_with_mappings(&self.ast, Some(&comments))
+      .unwrap();
+    let map = COMPILER.build_source_map(&mut mappings);
+    // Trace positions back to the loaded code if plugins transformed the module
+    let map = match &self.transformed_map {
+      Some(transformed_map) => collapse_source_maps(transformed_map, &map),
+      None => map,
+    };
+    RenderedModule {
+      code,
+      map: Some(map),
+    }
   }
 
   pub(crate) fn suggested_name_for(&self, sym: &JsWord) -> Option<JsWord> {
@@ -339,27 +591,35 @@ impl NormalModule {
       .map(|s| make_legal(&s).into());
 
     if ret.as_ref().is_none() && sym == "default" {
-      return Some(
-        make_legal(
-          &self
-            .id
-            .as_path()
-            .file_stem()
-            .map(|s| s.to_string_lossy().to_string())
-            .unwrap(),
-        )
-        .into(),
-      );
+      return Some(self.legal_stem().into());
     }
 
     ret
   }
 
+  /// `foo` for `/path/to/foo.js`
+  pub(crate) fn legal_stem(&self) -> String {
+    make_legal(
+      &self
+        .id
+        .as_path()
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap(),
+    )
+  }
+
   pub(crate) fn is_included(&self) -> bool {
     !self.ast.body.is_empty()
   }
 }
 
+pub(crate) struct RenderedModule {
+  pub(crate) code: String,
+  /// Maps `code` to the original file
+  pub(crate) map: Option<SourceMap>,
+}
+
 #[derive(Debug)]
 pub(crate) struct StatementParts {
   pub(crate) parts: Vec<StatementPart>,
@@ -0,0 +1,39 @@
+use std::str::FromStr;
+
+use derivative::Derivative;
+
+/// `None`s fall back to `compilerOptions` of the nearest `tsconfig.json`, such as `jsx` and
+/// `jsxImportSource`, and then the defaults of the classic runtime.
+#[derive(Derivative, Default)]
+#[derivative(Debug)]
+pub struct JsxOptions {
+  pub runtime: Option<JsxRuntime>,
+  /// The function creating elements in the classic runtime, such as `h`. Defaults to `React.createElement`.
+  pub pragma: Option<String>,
+  /// The component of fragments in the classic runtime. Defaults to `React.Fragment`.
+  pub pragma_frag: Option<String>,
+  /// The package providing `/jsx-runtime` in the automatic runtime. Defaults to `react`.
+  pub import_source: Option<String>,
+  /// Adds `__source` and `__self` to elements for debugging.
+  pub development: Option<bool>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JsxRuntime {
+  /// `<div />` becomes `React.createElement("div", null)`.
+  Classic,
+  /// `<div />` becomes `_jsx("div", {})`, where `_jsx` is imported from `react/jsx-runtime`.
+  Automatic,
+}
+
+impl FromStr for JsxRuntime {
+  type Err = String;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    match s {
+      "classic" => Ok(Self::Classic),
+      "automatic" => Ok(Self::Automatic),
+      _ => Err(format!("Unknown JSX runtime \"{}\"", s)),
+    }
+  }
+}
@@ -1,5 +1,9 @@
+mod jsx;
 mod typescript;
 use derivative::Derivative;
+use rolldown_common::Loader;
+use rustc_hash::FxHashMap;
+pub use jsx::*;
 pub use typescript::*;
 
 #[derive(Derivative)]
@@ -7,15 +11,20 @@ pub use typescript::*;
 pub struct BuiltinsOptions {
   /// None means disable the builtin
   pub tsconfig: TsConfig,
+  pub jsx: JsxOptions,
   // TODO: Should come up with a better name before exposing this option.
   pub detect_loader_by_ext: bool,
+  /// Loaders of extensions without the leading dot, such as `glsl` -> `Loader::Text`.
+  pub loaders: FxHashMap<String, Loader>,
 }
 
 impl Default for BuiltinsOptions {
   fn default() -> Self {
     Self {
       tsconfig: Default::default(),
+      jsx: Default::default(),
       detect_loader_by_ext: true,
+      loaders: Default::default(),
     }
   }
 }
@@ -1,15 +1,8 @@
 use derivative::Derivative;
 
-#[derive(Derivative)]
+/// Options here take precedence over `compilerOptions` of the nearest `tsconfig.json` of modules.
+#[derive(Derivative, Default)]
 #[derivative(Debug)]
 pub struct TsConfig {
-  pub use_define_for_class_fields: bool,
-}
-
-impl Default for TsConfig {
-  fn default() -> Self {
-    Self {
-      use_define_for_class_fields: false,
-    }
-  }
+  pub use_define_for_class_fields: Option<bool>,
 }
@@ -5,8 +5,7 @@ use futures::{future, Future, FutureExt};
 
 use crate::{UnaryBuildResult, WarningHandler};
 
-mod input_item;
-pub use input_item::*;
+pub use rolldown_common::InputItem;
 mod builtins;
 pub use builtins::*;
 
@@ -26,6 +25,9 @@ pub struct BuildInputOptions {
   #[derivative(Debug = "ignore")]
   pub on_warn: WarningHandler,
   pub shim_missing_exports: bool,
+  /// Wrap every ESM module in a lazy `__esm` initializer instead of only the modules
+  /// in import cycles or reached through `require`.
+  pub strict_execution_order: bool,
   pub builtins: BuiltinsOptions,
 }
 
@@ -40,6 +42,7 @@ impl Default for BuildInputOptions {
         eprintln!("{}", err);
       }),
       shim_missing_exports: false,
+      strict_execution_order: false,
       builtins: Default::default(),
     }
   }
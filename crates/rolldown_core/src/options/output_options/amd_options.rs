@@ -0,0 +1,17 @@
+/// Options of AMD and UMD output.
+#[derive(Debug, Clone)]
+pub struct AmdOptions {
+  /// The id of the module, such as `define('my-lib', [...], factory)`. Anonymous modules are defined if it's `None`.
+  pub id: Option<String>,
+  /// The function used in place of `define`, such as `requirejs.define`.
+  pub define: String,
+}
+
+impl Default for AmdOptions {
+  fn default() -> Self {
+    Self {
+      id: None,
+      define: "define".to_string(),
+    }
+  }
+}
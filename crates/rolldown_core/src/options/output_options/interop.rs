@@ -0,0 +1,17 @@
+use std::{pin::Pin, sync::Arc};
+
+use futures::{future, Future, FutureExt};
+pub use rolldown_common::InteropMode;
+
+use crate::UnaryBuildResult;
+
+type PinFutureBox<T> = Pin<Box<dyn Future<Output = T> + Send>>;
+
+/// Decides the interop mode of an external module in CommonJS, IIFE, AMD and UMD output by its id. The id is
+/// `None` for `import()` of non-string sources.
+pub type ResolveInterop =
+  Arc<dyn Fn(Option<&str>) -> PinFutureBox<UnaryBuildResult<InteropMode>> + Send + Sync>;
+
+pub fn interop_of_mode(mode: InteropMode) -> ResolveInterop {
+  Arc::new(move |_| future::ready(Ok(mode)).boxed())
+}
@@ -1,49 +1,40 @@
-use std::str::FromStr;
-
 use derivative::Derivative;
+pub use rolldown_common::ModuleFormat;
+use rustc_hash::FxHashMap;
 
 mod export_mode;
 pub use export_mode::*;
+mod source_map_type;
+pub use source_map_type::*;
+mod amd_options;
+pub use amd_options::*;
+mod interop;
+pub use interop::*;
 
 use self::file_name::FileNameTemplate;
 
-#[derive(Clone, Copy, PartialEq, Eq, Debug)]
-pub enum ModuleFormat {
-  Esm,
-  Cjs,
-  // AMD,
-  // UMD,
-}
-
-impl ModuleFormat {
-  pub fn is_es(self) -> bool {
-    self == ModuleFormat::Esm
-  }
-
-  pub fn is_cjs(self) -> bool {
-    self == ModuleFormat::Cjs
-  }
-}
-
-impl FromStr for ModuleFormat {
-  type Err = String;
-
-  fn from_str(value: &str) -> Result<Self, Self::Err> {
-    match value {
-      "esm" => Ok(ModuleFormat::Esm),
-      "cjs" => Ok(ModuleFormat::Cjs),
-      _ => Err(format!("Invalid module format: {value}")),
-    }
-  }
-}
-
 #[derive(Derivative)]
 #[derivative(Debug)]
 pub struct BuildOutputOptions {
   pub entry_file_names: FileNameTemplate,
   pub chunk_file_names: FileNameTemplate,
+  pub asset_file_names: FileNameTemplate,
   pub format: ModuleFormat,
   pub export_mode: ExportMode,
+  /// Paths in source maps are relative to chunks in this directory. Defaults to `<cwd>/dist`.
+  pub dir: Option<String>,
+  /// `None` means no source maps are generated.
+  pub sourcemap: Option<SourceMapType>,
+  pub sourcemap_exclude_sources: bool,
+  /// The global variable assigned with exports of IIFE and UMD bundles. Dotted names like `a.b.c`
+  /// create nested namespaces.
+  pub name: Option<String>,
+  /// Global variables of external modules in IIFE and UMD bundles, keyed by external ids, such as
+  /// `jquery` -> `jQuery`.
+  pub globals: FxHashMap<String, String>,
+  pub amd: AmdOptions,
+  #[derivative(Debug = "ignore")]
+  pub interop: ResolveInterop,
 }
 
 impl Default for BuildOutputOptions {
@@ -51,8 +42,16 @@ impl Default for BuildOutputOptions {
     Self {
       entry_file_names: FileNameTemplate::from("[name].js".to_string()),
       chunk_file_names: FileNameTemplate::from("[name]-[hash].js".to_string()),
+      asset_file_names: FileNameTemplate::from("assets/[name]-[hash][extname]".to_string()),
       format: ModuleFormat::Esm,
       export_mode: ExportMode::Auto,
+      dir: None,
+      sourcemap: None,
+      sourcemap_exclude_sources: false,
+      name: None,
+      globals: Default::default(),
+      amd: Default::default(),
+      interop: interop_of_mode(InteropMode::Auto),
     }
   }
 }
@@ -78,6 +77,10 @@ pub mod file_name {
   #[derive(Debug, Default)]
   pub struct RenderOptions<'me> {
     pub name: Option<&'me str>,
+    /// Extension with the leading dot, such as `.png`.
+    pub extname: Option<&'me str>,
+    /// Hash of the content, so files with the same name but different contents don't overwrite each other.
+    pub hash: Option<&'me str>,
   }
 
   impl FileNameTemplate {
@@ -86,6 +89,14 @@ pub mod file_name {
       if let Some(name) = options.name {
         tmp = tmp.replace("[name]", name);
       }
+      if let Some(extname) = options.extname {
+        tmp = tmp
+          .replace("[extname]", extname)
+          .replace("[ext]", extname.trim_start_matches('.'));
+      }
+      if let Some(hash) = options.hash {
+        tmp = tmp.replace("[hash]", hash);
+      }
       tmp
     }
   }
@@ -0,0 +1,10 @@
+/// How source maps of chunks are emitted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SourceMapType {
+  /// Emit `<chunk>.map` next to the chunk and reference it with a `//# sourceMappingURL` comment.
+  File,
+  /// Append the source map to the chunk as a data URL.
+  Inline,
+  /// Emit `<chunk>.map` without referencing it from the chunk.
+  Hidden,
+}
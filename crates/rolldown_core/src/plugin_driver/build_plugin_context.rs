@@ -0,0 +1,244 @@
+use std::{
+  path::{Path, PathBuf},
+  sync::{
+    atomic::{AtomicBool, Ordering},
+    Mutex, RwLock, Weak,
+  },
+};
+
+use derivative::Derivative;
+use rolldown_common::{Asset, ModuleId};
+use rolldown_plugin::{EmittedFile, ModuleInfo, PluginContextBridge, ResolveOutput, ResolvedId};
+use rustc_hash::{FxHashMap, FxHashSet};
+
+use crate::{
+  file_name::{FileNameTemplate, RenderOptions},
+  resolve_id,
+  utils::content_hash,
+  BuildError, BuildPluginDriver, SharedBuildInputOptions, SharedResolver, SharedTsConfigResolver,
+  UnaryBuildResult,
+};
+
+#[derive(Debug)]
+pub(crate) struct EmittedChunk {
+  pub(crate) id: String,
+  pub(crate) name: Option<String>,
+}
+
+#[derive(Debug)]
+struct EmittedAsset {
+  name: String,
+  /// Set if chunks reference the URL of the asset, such as files imported via `Loader::File`.
+  reference_id: Option<String>,
+  source: Vec<u8>,
+}
+
+impl EmittedAsset {
+  fn render_file_name(&self, asset_file_names: &FileNameTemplate) -> String {
+    let path = Path::new(&self.name);
+    let name = path.file_stem().and_then(|s| s.to_str()).unwrap_or("asset");
+    let extname = path
+      .extension()
+      .and_then(|s| s.to_str())
+      .map(|ext| format!(".{ext}"))
+      .unwrap_or_default();
+    let hash = content_hash(&self.source);
+    asset_file_names.render(RenderOptions {
+      name: Some(name),
+      extname: Some(&extname),
+      hash: Some(&hash),
+    })
+  }
+}
+
+/// The shared state behind `PluginContext`s of all plugins.
+#[derive(Derivative)]
+#[derivative(Debug)]
+pub(crate) struct BuildPluginContext {
+  #[derivative(Debug = "ignore")]
+  input_options: SharedBuildInputOptions,
+  pub(crate) resolver: SharedResolver,
+  pub(crate) tsconfig_resolver: SharedTsConfigResolver,
+  // Use `Weak` to avoid the reference cycle with the driver, which holds this context.
+  #[derivative(Debug = "ignore")]
+  plugin_driver: Weak<tokio::sync::RwLock<BuildPluginDriver>>,
+  module_infos: RwLock<FxHashMap<ModuleId, ModuleInfo>>,
+  watch_files: Mutex<FxHashSet<PathBuf>>,
+  emitted_chunks: Mutex<Vec<EmittedChunk>>,
+  emitted_assets: Mutex<Vec<EmittedAsset>>,
+  /// Emitted chunks are loaded as extra entries, which is impossible once the module graph is generated.
+  module_loading_finished: AtomicBool,
+}
+
+impl BuildPluginContext {
+  pub(crate) fn new(
+    input_options: SharedBuildInputOptions,
+    resolver: SharedResolver,
+    tsconfig_resolver: SharedTsConfigResolver,
+    plugin_driver: Weak<tokio::sync::RwLock<BuildPluginDriver>>,
+  ) -> Self {
+    Self {
+      input_options,
+      resolver,
+      tsconfig_resolver,
+      plugin_driver,
+      module_infos: Default::default(),
+      watch_files: Default::default(),
+      emitted_chunks: Default::default(),
+      emitted_assets: Default::default(),
+      module_loading_finished: Default::default(),
+    }
+  }
+
+  /// Forget states of the previous build.
+  pub(crate) fn reset(&self) {
+    self.module_infos.write().unwrap().clear();
+    self.watch_files.lock().unwrap().clear();
+    self.emitted_chunks.lock().unwrap().clear();
+    self.emitted_assets.lock().unwrap().clear();
+    self.module_loading_finished.store(false, Ordering::Relaxed);
+  }
+
+  pub(crate) fn add_module_info(&self, info: ModuleInfo) {
+    self
+      .module_infos
+      .write()
+      .unwrap()
+      .insert(info.id.clone(), info);
+  }
+
+  pub(crate) fn watch_files(&self) -> Vec<PathBuf> {
+    self.watch_files.lock().unwrap().iter().cloned().collect()
+  }
+
+  /// Chunks emitted since the last call.
+  pub(crate) fn take_emitted_chunks(&self) -> Vec<EmittedChunk> {
+    std::mem::take(&mut *self.emitted_chunks.lock().unwrap())
+  }
+
+  /// Emitting chunks is an error after this is called.
+  pub(crate) fn finish_module_loading(&self) {
+    self.module_loading_finished.store(true, Ordering::Relaxed);
+  }
+
+  /// Emit an asset whose URL is referenced by chunks, such as files imported via `Loader::File`. Returns the
+  /// reference id of the asset, which is the same for the same name and content.
+  pub(crate) fn emit_referenced_asset(&self, name: String, source: Vec<u8>) -> String {
+    let reference_id = content_hash(&[name.as_bytes(), b"\0", &source].concat());
+    self.emitted_assets.lock().unwrap().push(EmittedAsset {
+      name,
+      reference_id: Some(reference_id.clone()),
+      source,
+    });
+    reference_id
+  }
+
+  /// Filenames of referenced assets by their reference ids, which are the same as the ones rendered by
+  /// `take_emitted_assets`.
+  pub(crate) fn referenced_asset_file_names(
+    &self,
+    asset_file_names: &FileNameTemplate,
+  ) -> FxHashMap<String, String> {
+    self
+      .emitted_assets
+      .lock()
+      .unwrap()
+      .iter()
+      .filter_map(|asset| {
+        let reference_id = asset.reference_id.clone()?;
+        Some((reference_id, asset.render_file_name(asset_file_names)))
+      })
+      .collect()
+  }
+
+  /// Assets emitted since the last call, named by `asset_file_names`.
+  pub(crate) fn take_emitted_assets(&self, asset_file_names: &FileNameTemplate) -> Vec<Asset> {
+    std::mem::take(&mut *self.emitted_assets.lock().unwrap())
+      .into_iter()
+      .map(|asset| Asset {
+        filename: asset.render_file_name(asset_file_names),
+        content: asset.source.into(),
+      })
+      .collect()
+  }
+}
+
+#[rolldown_plugin::async_trait::async_trait]
+impl PluginContextBridge for BuildPluginContext {
+  fn warn(&self, warning: BuildError) {
+    (self.input_options.on_warn)(warning)
+  }
+
+  async fn resolve(
+    &self,
+    specifier: &str,
+    importer: Option<&ModuleId>,
+    skipped_plugin: Option<usize>,
+  ) -> ResolveOutput {
+    let is_external = &self.input_options.is_external;
+    let importer_id = importer.map(|id| id.as_ref());
+    if is_external(specifier, importer_id, false).await? {
+      return Ok(Some(ResolvedId {
+        id: specifier.to_string(),
+        external: true,
+      }));
+    }
+
+    let plugin_driver = self
+      .plugin_driver
+      .upgrade()
+      .expect("The plugin driver should be alive while plugins are running");
+    let resolved = resolve_id(
+      &self.resolver,
+      specifier,
+      importer,
+      false,
+      &plugin_driver,
+      skipped_plugin,
+    )
+    .await?;
+
+    match resolved {
+      Some(resolved) => {
+        let external =
+          resolved.is_external() || is_external(resolved.id(), importer_id, true).await?;
+        Ok(Some(ResolvedId {
+          id: resolved.id().to_string(),
+          external,
+        }))
+      }
+      None => Ok(None),
+    }
+  }
+
+  fn get_module_info(&self, id: &ModuleId) -> Option<ModuleInfo> {
+    self.module_infos.read().unwrap().get(id).cloned()
+  }
+
+  fn add_watch_file(&self, file: PathBuf) {
+    self.watch_files.lock().unwrap().insert(file);
+  }
+
+  fn emit_file(&self, file: EmittedFile) -> UnaryBuildResult<()> {
+    match file {
+      EmittedFile::Chunk { id, name } => {
+        if self.module_loading_finished.load(Ordering::Relaxed) {
+          return Err(BuildError::emit_chunk_after_module_loading());
+        }
+        self
+          .emitted_chunks
+          .lock()
+          .unwrap()
+          .push(EmittedChunk { id, name });
+      }
+      EmittedFile::Asset { name, source } => {
+        self.emitted_assets.lock().unwrap().push(EmittedAsset {
+          name,
+          reference_id: None,
+          source,
+        });
+      }
+    }
+    Ok(())
+  }
+}
@@ -1,30 +1,163 @@
-use std::sync::Arc;
+use std::{future::Future, path::Path, sync::Arc};
 
-use rolldown_common::ModuleId;
-use rolldown_plugin::{BuildPlugin, Context, ResolveArgs, ResolveOutput, TransformArgs};
+use futures::future::join_all;
+use rolldown_common::{collapse_source_maps, ModuleId, SourceMap};
+use rolldown_error::Errors;
+use rolldown_plugin::{
+  BuildPlugin, ChunkInfo, HookKind, HookMode, HookNoopReturn, InputOptions, LoadArgs, LoadOutput,
+  OutputBundle, PluginContext, PluginOrder, RenderChunkArgs, RenderDynamicImportArgs,
+  RenderDynamicImportOutput, ResolveArgs, ResolveDynamicImportArgs, ResolveOutput, ResolvedId,
+  TransformArgs, TransformedSource,
+};
+use rolldown_resolver::Resolver;
 use tokio::sync::RwLock;
 
-use crate::UnaryBuildResult;
+use crate::{
+  BuildError, BuildInputOptions, BuildPluginContext, SharedBuildInputOptions, UnaryBuildResult,
+};
 
 pub(crate) type SharedBuildPluginDriver = Arc<RwLock<BuildPluginDriver>>;
 
-#[derive(Debug, Default)]
+#[derive(Debug)]
 pub(crate) struct BuildPluginDriver {
+  /// Sorted by `BuildPlugin::order`.
   pub plugins: Vec<Box<dyn BuildPlugin>>,
+  pub(crate) context: Arc<BuildPluginContext>,
 }
 
 impl BuildPluginDriver {
-  pub(crate) fn new(plugins: Vec<Box<dyn BuildPlugin>>) -> Self {
-    Self { plugins }
+  pub(crate) fn new_shared(
+    mut plugins: Vec<Box<dyn BuildPlugin>>,
+    input_options: SharedBuildInputOptions,
+  ) -> SharedBuildPluginDriver {
+    // `sort_by_key` is stable, so plugins with the same order keep the order they are passed in.
+    plugins.sort_by_key(|plugin| plugin.order());
+    let resolver = Arc::new(Resolver::with_cwd(input_options.cwd.clone()));
+    Arc::new_cyclic(|plugin_driver| {
+      RwLock::new(Self {
+        plugins,
+        context: Arc::new(BuildPluginContext::new(
+          input_options,
+          resolver,
+          Default::default(),
+          plugin_driver.clone(),
+        )),
+      })
+    })
   }
 
-  pub(crate) fn into_shared(self) -> SharedBuildPluginDriver {
-    Arc::new(RwLock::new(self))
+  fn create_context(&self, plugin_index: usize) -> PluginContext {
+    PluginContext::new(
+      plugin_index,
+      self.plugins[plugin_index].name(),
+      self.context.clone(),
+    )
   }
 
-  pub(crate) async fn resolve(&self, mut args: ResolveArgs<'_>) -> ResolveOutput {
-    for plugin in &self.plugins {
-      let output = plugin.resolve(&mut Context::new(), &mut args).await?;
+  fn is_filtered_out(plugin: &dyn BuildPlugin, hook: HookKind, id: &str) -> bool {
+    plugin
+      .filter(hook)
+      .map_or(false, |filter| !filter.matches(id))
+  }
+
+  /// Call a hook that returns nothing according to its mode.
+  async fn call_noop_hook<'a, F, Fut>(&'a self, hook: HookKind, call: F) -> HookNoopReturn
+  where
+    F: Fn(&'a dyn BuildPlugin, PluginContext) -> Fut,
+    Fut: Future<Output = HookNoopReturn>,
+  {
+    let calls = self
+      .plugins
+      .iter()
+      .enumerate()
+      .map(|(idx, plugin)| call(plugin.as_ref(), self.create_context(idx)));
+    match hook.mode() {
+      HookMode::Parallel => join_all(calls).await.into_iter().try_collect(),
+      HookMode::Sequential => {
+        for call in calls {
+          call.await?;
+        }
+        Ok(())
+      }
+      HookMode::First => unreachable!("{hook:?} should return something"),
+    }
+  }
+
+  pub(crate) async fn build_start(&self, options: &BuildInputOptions) -> HookNoopReturn {
+    let options = InputOptions {
+      input: &options.input,
+      cwd: &options.cwd,
+      treeshake: options.treeshake,
+      shim_missing_exports: options.shim_missing_exports,
+      strict_execution_order: options.strict_execution_order,
+    };
+    let options = &options;
+    self.context.reset();
+    self
+      .call_noop_hook(HookKind::BuildStart, |plugin, mut ctx| async move {
+        plugin.build_start(&mut ctx, options).await
+      })
+      .await
+  }
+
+  /// The plugin at `skipped_plugin` will not be called.
+  ///
+  /// Bare specifiers mapped by `paths` and `baseUrl` of the importer's `tsconfig.json` are resolved before `Post`
+  /// plugins like node-resolve, so the mapping takes precedence over `node_modules` as it does in TypeScript.
+  pub(crate) async fn resolve(
+    &self,
+    mut args: ResolveArgs<'_>,
+    skipped_plugin: Option<usize>,
+  ) -> ResolveOutput {
+    let mut is_tsconfig_paths_resolved = false;
+    for (idx, plugin) in self.plugins.iter().enumerate() {
+      if !is_tsconfig_paths_resolved && plugin.order() == PluginOrder::Post {
+        is_tsconfig_paths_resolved = true;
+        if let Some(resolved) = self.resolve_tsconfig_paths(&args)? {
+          return Ok(Some(resolved));
+        }
+      }
+      if skipped_plugin == Some(idx)
+        || Self::is_filtered_out(plugin.as_ref(), HookKind::Resolve, args.specifier)
+      {
+        continue;
+      }
+      let output = plugin
+        .resolve(&mut self.create_context(idx), &mut args)
+        .await?;
+      if output.is_some() {
+        return Ok(output);
+      }
+    }
+    if is_tsconfig_paths_resolved {
+      Ok(None)
+    } else {
+      self.resolve_tsconfig_paths(&args)
+    }
+  }
+
+  fn resolve_tsconfig_paths(&self, args: &ResolveArgs) -> ResolveOutput {
+    let Some(importer) = args.importer.filter(|importer| !importer.is_virtual()) else {
+      return Ok(None);
+    };
+    let resolved = self
+      .context
+      .tsconfig_resolver
+      .resolve_paths(Path::new(importer.path_without_query()), args.specifier)?;
+    Ok(resolved.map(|path| ResolvedId {
+      id: path.to_string_lossy().to_string(),
+      external: false,
+    }))
+  }
+
+  pub(crate) async fn resolve_dynamic_import(
+    &self,
+    mut args: ResolveDynamicImportArgs<'_>,
+  ) -> ResolveOutput {
+    for (idx, plugin) in self.plugins.iter().enumerate() {
+      let output = plugin
+        .resolve_dynamic_import(&mut self.create_context(idx), &mut args)
+        .await?;
       if output.is_some() {
         return Ok(output);
       }
@@ -32,16 +165,129 @@ impl BuildPluginDriver {
     Ok(None)
   }
 
-  pub(crate) async fn transform(&self, id: &ModuleId, code: String) -> UnaryBuildResult<String> {
+  pub(crate) async fn load(&self, mut args: LoadArgs<'_>) -> LoadOutput {
+    for (idx, plugin) in self.plugins.iter().enumerate() {
+      if Self::is_filtered_out(plugin.as_ref(), HookKind::Load, args.id.as_ref()) {
+        continue;
+      }
+      let output = plugin
+        .load(&mut self.create_context(idx), &mut args)
+        .await?;
+      if output.is_some() {
+        return Ok(output);
+      }
+    }
+    Ok(None)
+  }
+
+  /// Source maps returned by plugins are collapsed onto `input_map`, so the final map points at the original sources.
+  /// Once a plugin changes the code without a source map, positions are lost and the final map is `None`.
+  pub(crate) async fn transform(
+    &self,
+    id: &ModuleId,
+    code: String,
+    input_map: Option<SourceMap>,
+  ) -> UnaryBuildResult<TransformedSource> {
     let mut code = code;
-    for plugin in &self.plugins {
+    let mut map = input_map;
+    let mut is_map_broken = false;
+    for (idx, plugin) in self.plugins.iter().enumerate() {
+      if Self::is_filtered_out(plugin.as_ref(), HookKind::Transform, id.as_ref()) {
+        continue;
+      }
       let output = plugin
-        .transform(&mut Context::new(), &mut TransformArgs { id, code: &code })
+        .transform(
+          &mut self.create_context(idx),
+          &mut TransformArgs { id, code: &code },
+        )
         .await?;
       if let Some(output) = output {
-        code = output
+        code = output.code;
+        match output.map {
+          Some(_) if is_map_broken => {}
+          Some(output_map) => {
+            map = Some(match map {
+              Some(map) => collapse_source_maps(&map, &output_map),
+              None => output_map,
+            })
+          }
+          None => {
+            is_map_broken = true;
+            map = None;
+          }
+        }
       }
     }
-    Ok(code)
+    Ok(TransformedSource { code, map })
+  }
+
+  pub(crate) async fn build_end(&self, errors: Option<&Errors>) -> HookNoopReturn {
+    self
+      .call_noop_hook(HookKind::BuildEnd, |plugin, mut ctx| async move {
+        plugin.build_end(&mut ctx, errors).await
+      })
+      .await
+  }
+
+  /// `map` is `None` if source maps are not generated. Maps returned by plugins are collapsed onto it, and it's
+  /// dropped with a warning once a plugin changes the code without a source map.
+  pub(crate) async fn render_chunk(
+    &self,
+    code: String,
+    map: Option<SourceMap>,
+    chunk: &ChunkInfo,
+  ) -> UnaryBuildResult<TransformedSource> {
+    let mut code = code;
+    let mut map = map;
+    for (idx, plugin) in self.plugins.iter().enumerate() {
+      let mut ctx = self.create_context(idx);
+      let output = plugin
+        .render_chunk(&mut ctx, &mut RenderChunkArgs { code: &code, chunk })
+        .await?;
+      if let Some(output) = output {
+        code = output.code;
+        map = match (map, output.map) {
+          (Some(map), Some(output_map)) => Some(collapse_source_maps(&map, &output_map)),
+          (Some(_), None) => {
+            ctx.warn(BuildError::sourcemap_broken(chunk.filename.clone()));
+            None
+          }
+          (None, _) => None,
+        };
+      }
+    }
+    Ok(TransformedSource { code, map })
+  }
+
+  pub(crate) async fn render_dynamic_import(
+    &self,
+    mut args: RenderDynamicImportArgs<'_>,
+  ) -> RenderDynamicImportOutput {
+    for (idx, plugin) in self.plugins.iter().enumerate() {
+      let output = plugin
+        .render_dynamic_import(&mut self.create_context(idx), &mut args)
+        .await?;
+      if output.is_some() {
+        return Ok(output);
+      }
+    }
+    Ok(None)
+  }
+
+  pub(crate) async fn generate_bundle(&self, bundle: &mut OutputBundle) -> HookNoopReturn {
+    for (idx, plugin) in self.plugins.iter().enumerate() {
+      plugin
+        .generate_bundle(&mut self.create_context(idx), bundle)
+        .await?;
+    }
+    Ok(())
+  }
+
+  pub(crate) async fn close_bundle(&self) -> HookNoopReturn {
+    self
+      .call_noop_hook(HookKind::CloseBundle, |plugin, mut ctx| async move {
+        plugin.close_bundle(&mut ctx).await
+      })
+      .await
   }
 }
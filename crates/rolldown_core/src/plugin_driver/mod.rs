@@ -1,2 +1,4 @@
 mod build_plugin_driver;
 pub(crate) use build_plugin_driver::*;
+mod build_plugin_context;
+pub(crate) use build_plugin_context::*;
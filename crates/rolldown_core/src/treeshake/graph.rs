@@ -30,15 +30,20 @@ impl Graph {
             COMPILER.debug_print(&module.ast, None).unwrap()
           );
 
-          rolldown_swc_visitors::treeshake(
-            &mut module.ast,
-            self.unresolved_mark,
-            &used_ids,
-            module.top_level_ctxt,
-            GLOBALS.set(&SWC_GLOBALS, || module.top_level_ctxt.outer()),
-            COMPILER.cm.clone(),
-            &module.comments,
-          );
+          // The wrapped body of CommonJS modules is kept as it is, since `module.exports` could be
+          // accessed in any way. Modules wrapped by `__esm` are kept as a whole too, since they are
+          // evaluated on demand.
+          if !module.is_commonjs && module.esm_init.is_none() {
+            rolldown_swc_visitors::treeshake(
+              &mut module.ast,
+              self.unresolved_mark,
+              &used_ids,
+              module.top_level_ctxt,
+              GLOBALS.set(&SWC_GLOBALS, || module.top_level_ctxt.outer()),
+              COMPILER.cm.clone(),
+              &module.comments,
+            );
+          }
           tracing::trace!(
             "[after treeshake]module: {},code: \n{}",
             module.id,
@@ -0,0 +1,156 @@
+use std::hash::Hasher;
+use std::path::Path;
+
+use rolldown_common::Loader;
+use rustc_hash::{FxHashMap, FxHasher};
+use swc_core::ecma::ast;
+use swc_core::ecma::utils::{quote_ident, quote_str};
+
+/// `Loader::Text` exports the content as it is.
+pub(crate) fn text_to_esm(text: String) -> ast::Module {
+  export_default(box ast::Expr::Lit(ast::Lit::Str(quote_str!(text))))
+}
+
+/// Convert modules of binary loaders. For `Loader::File`, `emit_file` is called with the name and the content of the
+/// file and returns the reference id of the emitted asset.
+pub(crate) fn binary_to_esm(
+  path: &Path,
+  bytes: Vec<u8>,
+  loader: Loader,
+  emit_file: impl FnOnce(String, Vec<u8>) -> String,
+) -> ast::Module {
+  let expr = match loader {
+    Loader::Base64 => box ast::Expr::Lit(ast::Lit::Str(quote_str!(base64::encode(&bytes)))),
+    Loader::DataUrl => box ast::Expr::Lit(ast::Lit::Str(quote_str!(format!(
+      "data:{};base64,{}",
+      mime_type_of(path),
+      base64::encode(&bytes)
+    )))),
+    // `__toBinary` is provided by the runtime helper of the same name
+    Loader::Binary => box ast::Expr::Call(ast::CallExpr {
+      span: Default::default(),
+      callee: ast::Callee::Expr(box ast::Expr::Ident(quote_ident!("__toBinary"))),
+      args: vec![ast::ExprOrSpread {
+        spread: None,
+        expr: box ast::Expr::Lit(ast::Lit::Str(quote_str!(base64::encode(&bytes)))),
+      }],
+      type_args: None,
+    }),
+    Loader::File => {
+      let name = path.file_name().map_or_else(
+        || "asset".to_string(),
+        |name| name.to_string_lossy().into_owned(),
+      );
+      let reference_id = emit_file(name, bytes);
+      box ast::Expr::Lit(ast::Lit::Str(quote_str!(asset_url_placeholder(
+        &reference_id
+      ))))
+    }
+    Loader::Empty => {
+      return ast::Module {
+        span: Default::default(),
+        body: vec![],
+        shebang: None,
+      }
+    }
+    Loader::Js | Loader::Jsx | Loader::Ts | Loader::Tsx | Loader::Json | Loader::Text => {
+      unreachable!("{loader:?} is not a binary loader")
+    }
+  };
+  export_default(expr)
+}
+
+fn export_default(expr: Box<ast::Expr>) -> ast::Module {
+  ast::Module {
+    span: Default::default(),
+    body: vec![ast::ModuleItem::ModuleDecl(
+      ast::ModuleDecl::ExportDefaultExpr(ast::ExportDefaultExpr {
+        span: Default::default(),
+        expr,
+      }),
+    )],
+    shebang: None,
+  }
+}
+
+/// The first 8 hex digits of the hash of `bytes`, used by `[hash]` in filenames.
+pub(crate) fn content_hash(bytes: &[u8]) -> String {
+  let mut hasher = FxHasher::default();
+  hasher.write(bytes);
+  let hash = format!("{:016x}", hasher.finish());
+  hash[..8].to_string()
+}
+
+/// Chunks aren't named yet when modules are loaded, so `Loader::File` exports a placeholder of the URL, which is
+/// replaced by `replace_asset_url_placeholders` once the chunk is rendered.
+fn asset_url_placeholder(reference_id: &str) -> String {
+  format!("__ROLLDOWN_ASSET_URL_{reference_id}__")
+}
+
+/// Replace placeholders of asset URLs in `code` with paths of the assets relative to the chunk.
+/// `asset_file_name_by_reference_id` maps reference ids of assets to their filenames in the output.
+pub(crate) fn replace_asset_url_placeholders(
+  code: &mut String,
+  chunk_file_name: &str,
+  asset_file_name_by_reference_id: &FxHashMap<String, String>,
+) {
+  if !code.contains("__ROLLDOWN_ASSET_URL_") {
+    return;
+  }
+  for (reference_id, asset_file_name) in asset_file_name_by_reference_id {
+    let placeholder = asset_url_placeholder(reference_id);
+    if code.contains(&placeholder) {
+      *code = code.replace(
+        &placeholder,
+        &relative_url(chunk_file_name, asset_file_name),
+      );
+    }
+  }
+}
+
+/// `relative_url("js/main.js", "assets/logo.png")` -> `../assets/logo.png`
+fn relative_url(from_file_name: &str, to_file_name: &str) -> String {
+  let components = |file_name: &str| -> Vec<String> {
+    file_name
+      .split('/')
+      .filter(|c| !c.is_empty() && *c != ".")
+      .map(ToString::to_string)
+      .collect()
+  };
+  let mut from_dir = components(from_file_name);
+  from_dir.pop();
+  let to = components(to_file_name);
+  let common = from_dir.iter().zip(&to).take_while(|(a, b)| a == b).count();
+  let rest = to[common..].join("/");
+  match from_dir.len() - common {
+    0 => format!("./{rest}"),
+    depth => format!("{}{rest}", "../".repeat(depth)),
+  }
+}
+
+fn mime_type_of(path: &Path) -> &'static str {
+  let ext = path
+    .extension()
+    .and_then(|s| s.to_str())
+    .map(|ext| ext.to_ascii_lowercase());
+  match ext.as_deref() {
+    Some("png") => "image/png",
+    Some("jpg" | "jpeg") => "image/jpeg",
+    Some("gif") => "image/gif",
+    Some("webp") => "image/webp",
+    Some("avif") => "image/avif",
+    Some("svg") => "image/svg+xml",
+    Some("ico") => "image/x-icon",
+    Some("woff") => "font/woff",
+    Some("woff2") => "font/woff2",
+    Some("ttf") => "font/ttf",
+    Some("otf") => "font/otf",
+    Some("wasm") => "application/wasm",
+    Some("json") => "application/json",
+    Some("js" | "mjs" | "cjs") => "text/javascript",
+    Some("css") => "text/css",
+    Some("html" | "htm") => "text/html",
+    Some("txt") => "text/plain",
+    _ => "application/octet-stream",
+  }
+}
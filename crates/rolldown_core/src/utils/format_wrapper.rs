@@ -0,0 +1,308 @@
+use rolldown_swc_visitors::amd_id_of;
+
+/// Code around the statements of a chunk, which wraps them in a function. Statements of the chunk are linked by
+/// `links_to_cjs` or `links_to_system`, so dependencies are parameters of the function or assigned by setters.
+pub(crate) struct FormatWrapper {
+  pub(crate) intro: String,
+  pub(crate) outro: String,
+}
+
+/// A dependency of the chunk passed to the factory function.
+pub(crate) struct WrappedDependency<'a> {
+  pub(crate) source: &'a str,
+  /// It's `None` for dependencies only imported for side effects, which are loaded but not passed.
+  pub(crate) param: Option<&'a str>,
+  /// The global variable of the dependency in IIFE and UMD output, such as `jQuery`.
+  pub(crate) global: Option<&'a str>,
+}
+
+/// How the chunk exposes its exports.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub(crate) enum WrappedExports {
+  None,
+  /// The factory returns the default export.
+  Default,
+  /// `exports` is passed to the factory, whose properties are exports of the chunk.
+  Named,
+}
+
+/// ```js
+/// var MyLib = (function(exports, _jquery) {
+/// ...
+/// return exports;
+/// })({}, jQuery);
+/// ```
+///
+/// Dotted names like `a.b.c` create namespaces on `this`.
+pub(crate) fn iife_wrapper(
+  name: Option<&str>,
+  exports: WrappedExports,
+  deps: &[WrappedDependency],
+) -> FormatWrapper {
+  let mut params = vec![];
+  let mut args = vec![];
+  if exports == WrappedExports::Named {
+    params.push("exports");
+    args.push("{}");
+  }
+  for dep in deps {
+    if let (Some(param), Some(global)) = (dep.param, dep.global) {
+      params.push(param);
+      args.push(global);
+    }
+  }
+
+  let mut intro = String::new();
+  match name.filter(|_| exports != WrappedExports::None) {
+    Some(name) if name.contains('.') => {
+      let (namespaces, target) = namespaces_of("this", name);
+      namespaces
+        .iter()
+        .for_each(|namespace| intro.push_str(&format!("{namespace};\n")));
+      intro.push_str(&format!("{target} = "));
+    }
+    Some(name) => intro.push_str(&format!("var {name} = ")),
+    None => {}
+  }
+  intro.push_str(&format!("(function({}) {{\n", params.join(", ")));
+
+  let mut outro = String::new();
+  if exports == WrappedExports::Named {
+    outro.push_str("return exports;\n");
+  }
+  outro.push_str(&format!("}})({});\n", args.join(", ")));
+  FormatWrapper { intro, outro }
+}
+
+/// ```js
+/// define("my-lib", ["require", "exports", "jquery"], (function(require, exports, _jquery) {
+/// ...
+/// }));
+/// ```
+///
+/// `require` is only a dependency if `import()` is used.
+pub(crate) fn amd_wrapper(
+  id: Option<&str>,
+  define: &str,
+  exports: WrappedExports,
+  requires_lazily: bool,
+  deps: &[WrappedDependency],
+) -> FormatWrapper {
+  let mut ids = vec![];
+  let mut params = vec![];
+  if requires_lazily {
+    ids.push("require");
+    params.push("require");
+  }
+  if exports == WrappedExports::Named {
+    ids.push("exports");
+    params.push("exports");
+  }
+  let (ids_of_deps, params_of_deps) = amd_dependencies(deps);
+  ids.extend(ids_of_deps);
+  params.extend(params_of_deps);
+
+  let mut args = String::new();
+  if let Some(id) = id {
+    args.push_str(&format!("{}, ", quote(id)));
+  }
+  if !ids.is_empty() {
+    args.push_str(&format!("{}, ", array_of(&ids)));
+  }
+  FormatWrapper {
+    intro: format!("{define}({args}(function({}) {{\n", params.join(", ")),
+    outro: "}));\n".to_string(),
+  }
+}
+
+/// ```js
+/// (function(global, factory) {
+///     typeof exports === "object" && typeof module !== "undefined" ? factory(exports, require("jquery")) :
+///     typeof define === "function" && define.amd ? define(["exports", "jquery"], factory) :
+///     (global = typeof globalThis !== "undefined" ? globalThis : global || self, factory(global.MyLib = {}, global.jQuery));
+/// })(this, (function(exports, _jquery) {
+/// ...
+/// }));
+/// ```
+///
+/// The factory is called with `require`d modules in CommonJS, defined in AMD or called with global variables
+/// otherwise.
+pub(crate) fn umd_wrapper(
+  name: Option<&str>,
+  amd_id: Option<&str>,
+  define: &str,
+  exports: WrappedExports,
+  deps: &[WrappedDependency],
+) -> FormatWrapper {
+  let with_exports = |exports_arg: &str, args: Vec<String>| {
+    let mut all = vec![];
+    if exports == WrappedExports::Named {
+      all.push(exports_arg.to_string());
+    }
+    all.extend(args);
+    all.join(", ")
+  };
+
+  // Dependencies only imported for side effects are passed after the ones with parameters, so they're loaded.
+  let (params_first, side_effects): (Vec<_>, Vec<_>) =
+    deps.iter().partition(|dep| dep.param.is_some());
+  let required = params_first
+    .iter()
+    .chain(&side_effects)
+    .map(|dep| format!("require({})", quote(dep.source)))
+    .collect();
+  let mut cjs = format!("factory({})", with_exports("exports", required));
+  if exports == WrappedExports::Default {
+    cjs = format!("module.exports = {cjs}");
+  }
+
+  let (ids_of_deps, params_of_deps) = amd_dependencies(deps);
+  let mut ids = vec![];
+  if exports == WrappedExports::Named {
+    ids.push("exports");
+  }
+  ids.extend(ids_of_deps);
+  let mut amd_args = String::new();
+  if let Some(id) = amd_id {
+    amd_args.push_str(&format!("{}, ", quote(id)));
+  }
+  if !ids.is_empty() {
+    amd_args.push_str(&format!("{}, ", array_of(&ids)));
+  }
+  let amd = format!("{define}({amd_args}factory)");
+
+  let globals = params_first
+    .iter()
+    .map(|dep| match dep.global {
+      Some(global) => format!("global.{global}"),
+      None => "undefined".to_string(),
+    })
+    .collect::<Vec<_>>();
+  let mut global_seq =
+    vec![r#"global = typeof globalThis !== "undefined" ? globalThis : global || self"#.to_string()];
+  match name.filter(|_| exports != WrappedExports::None) {
+    Some(name) if exports == WrappedExports::Default => {
+      // global.MyLib = factory(global.jQuery)
+      let (namespaces, target) = namespaces_of("global", name);
+      global_seq.extend(namespaces);
+      global_seq.push(format!("{target} = factory({})", globals.join(", ")));
+    }
+    Some(name) => {
+      // factory(global.MyLib = {}, global.jQuery)
+      let (mut namespaces, target) = namespaces_of("global", name);
+      namespaces.push(format!("{target} = {{}}"));
+      let exports_arg = if namespaces.len() == 1 {
+        namespaces.remove(0)
+      } else {
+        format!("({})", namespaces.join(", "))
+      };
+      global_seq.push(format!("factory({})", with_exports(&exports_arg, globals)));
+    }
+    None => global_seq.push(format!("factory({})", with_exports("{}", globals))),
+  }
+
+  let mut intro = String::from("(function(global, factory) {\n");
+  intro.push_str(&format!(
+    "    typeof exports === \"object\" && typeof module !== \"undefined\" ? {cjs} :\n"
+  ));
+  intro.push_str(&format!(
+    "    typeof {define} === \"function\" && {define}.amd ? {amd} :\n"
+  ));
+  intro.push_str(&format!("    ({});\n", global_seq.join(", ")));
+  intro.push_str(&format!(
+    "}})(this, (function({}) {{\n",
+    with_exports(
+      "exports",
+      params_of_deps
+        .iter()
+        .map(|param| param.to_string())
+        .collect()
+    )
+  ));
+  FormatWrapper {
+    intro,
+    outro: "}));\n".to_string(),
+  }
+}
+
+/// ```js
+/// System.register(["./chunk.js"], (function(exports, module) {
+/// "use strict";
+/// var a;
+/// return {
+/// setters: [function(module) {
+///     a = module.a;
+/// }],
+/// execute: (function() {
+/// ...
+/// })
+/// };
+/// }));
+/// ```
+///
+/// Dependencies are pairs of sources and printed setters. Imported bindings are declared out of `execute`, so
+/// setters are able to assign them.
+pub(crate) fn system_wrapper(deps: &[(&str, String)], bindings: &[&str]) -> FormatWrapper {
+  let sources = deps.iter().map(|(source, _)| *source).collect::<Vec<_>>();
+  let setters = deps
+    .iter()
+    .map(|(_, setter)| setter.as_str())
+    .collect::<Vec<_>>();
+  let mut intro = format!(
+    "System.register({}, (function(exports, module) {{\n\"use strict\";\n",
+    array_of(&sources)
+  );
+  if !bindings.is_empty() {
+    intro.push_str(&format!("var {};\n", bindings.join(", ")));
+  }
+  intro.push_str(&format!(
+    "return {{\nsetters: [{}],\nexecute: (function() {{\n",
+    setters.join(", ")
+  ));
+  FormatWrapper {
+    intro,
+    outro: "})\n};\n}));\n".to_string(),
+  }
+}
+
+/// Ids and parameters of dependencies. Dependencies only imported for side effects come last without parameters.
+fn amd_dependencies<'a>(deps: &[WrappedDependency<'a>]) -> (Vec<&'a str>, Vec<&'a str>) {
+  let (params_first, side_effects): (Vec<_>, Vec<_>) =
+    deps.iter().partition(|dep| dep.param.is_some());
+  let ids = params_first
+    .iter()
+    .chain(&side_effects)
+    .map(|dep| amd_id_of(dep.source))
+    .collect();
+  let params = params_first.iter().filter_map(|dep| dep.param).collect();
+  (ids, params)
+}
+
+/// `root.a = root.a || {}` and `root.a.b = root.a.b || {}` for namespaces of `a.b.c`. The last part is returned
+/// as `root.a.b.c`.
+fn namespaces_of(root: &str, name: &str) -> (Vec<String>, String) {
+  let parts = name.split('.').collect::<Vec<_>>();
+  let member_of_root = |parts: &[&str]| format!("{root}.{}", parts.join("."));
+  let namespaces = (1..parts.len())
+    .map(|len| {
+      let namespace = member_of_root(&parts[..len]);
+      format!("{namespace} = {namespace} || {{}}")
+    })
+    .collect();
+  (namespaces, member_of_root(&parts))
+}
+
+fn quote(value: &str) -> String {
+  format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+fn array_of(ids: &[&str]) -> String {
+  format!(
+    "[{}]",
+    ids
+      .iter()
+      .map(|id| quote(id))
+      .collect::<Vec<_>>()
+      .join(", ")
+  )
+}
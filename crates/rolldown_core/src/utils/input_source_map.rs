@@ -0,0 +1,64 @@
+use std::path::Path;
+
+use rolldown_common::{concat_source_maps, SourceMap};
+use sugar_path::SugarPath;
+
+use crate::{BuildError, UnaryBuildResult};
+
+/// Extract `<url>` of `//# sourceMappingURL=<url>` at the end of the code.
+fn extract_source_mapping_url(code: &str) -> Option<&str> {
+  let last_line = code.trim_end().lines().next_back()?.trim();
+  last_line
+    .strip_prefix("//# sourceMappingURL=")
+    .or_else(|| last_line.strip_prefix("//@ sourceMappingURL="))
+    .map(str::trim)
+}
+
+/// Load the source map referenced by the code of the module at `path`, either from a data URL or from a file.
+/// Sources are resolved to absolute paths. Returns `None` if the code doesn't reference any source map.
+pub(crate) async fn load_input_source_map(
+  path: &Path,
+  code: &str,
+) -> UnaryBuildResult<Option<SourceMap>> {
+  let Some(url) = extract_source_mapping_url(code) else {
+    return Ok(None);
+  };
+  let module_dir = path.parent().unwrap_or(path);
+
+  let (content, sources_dir) = if let Some(data) = url.strip_prefix("data:") {
+    let Some((_, encoded)) = data.split_once(";base64,") else {
+      return Err(BuildError::sourcemap_error(path, "Only base64 data URLs are supported"));
+    };
+    let content =
+      base64::decode(encoded).map_err(|e| BuildError::sourcemap_error(path, e.to_string()))?;
+    (content, module_dir.to_path_buf())
+  } else {
+    let map_path = module_dir.join(url).normalize();
+    let content = tokio::fs::read(&map_path).await.map_err(|e| {
+      BuildError::sourcemap_error(path, format!("Read file {}: {e}", map_path.display()))
+    })?;
+    let sources_dir = map_path.parent().unwrap_or(module_dir).to_path_buf();
+    (content, sources_dir)
+  };
+
+  let map = SourceMap::from_slice(&content)
+    .map_err(|e| BuildError::sourcemap_error(path, e.to_string()))?;
+
+  // Sources are relative to the source map. Make them absolute, so they could be made relative to chunks later.
+  Ok(Some(concat_source_maps(
+    map.get_file(),
+    [(0, &map)],
+    true,
+    |source| {
+      if source.contains("://") {
+        source.to_string()
+      } else {
+        sources_dir
+          .join(source)
+          .normalize()
+          .to_string_lossy()
+          .to_string()
+      }
+    },
+  )))
+}
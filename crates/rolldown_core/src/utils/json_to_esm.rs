@@ -0,0 +1,109 @@
+use std::path::PathBuf;
+
+use rolldown_common::ModuleId;
+use rustc_hash::FxHashMap;
+use swc_core::ecma::ast;
+use swc_core::ecma::atoms::JsWord;
+use swc_core::ecma::parser::Syntax;
+use swc_core::ecma::utils::{quote_ident, quote_str};
+
+use crate::{is_legal_identifier, BuildError, UnaryBuildResult, COMPILER};
+
+/// Convert a JSON file into an ES module.
+///
+/// ```js
+/// // {"name": "rolldown", "dev-dependencies": {}}
+/// export const name = "rolldown";
+/// export default { name, "dev-dependencies": {} };
+/// ```
+///
+/// Top-level keys that are legal identifiers are exported separately, so unused ones could be tree-shaken.
+pub(crate) fn json_to_esm(id: &ModuleId, source: String) -> UnaryBuildResult<ast::Module> {
+  // JSON is a subset of JavaScript, but the JavaScript parser accepts many inputs that are not JSON.
+  serde_json::from_str::<serde_json::Value>(&source)
+    .map_err(|err| BuildError::parse_json_failed(id.as_ref(), err.to_string()))?;
+
+  let fm = COMPILER.create_source_file(PathBuf::from(id.as_ref().to_string()), source);
+  let expr = COMPILER
+    .parse_expr(fm.clone(), Syntax::Es(Default::default()))
+    .map_err(|e| BuildError::parse_js_failed(fm, e))?;
+
+  let mut body = vec![];
+  let default_export = match *expr {
+    ast::Expr::Object(obj) => {
+      // Duplicated keys keep the position of the first one and the value of the last one like `JSON.parse`
+      let mut props: Vec<(JsWord, Box<ast::Expr>)> = vec![];
+      let mut index_by_key: FxHashMap<JsWord, usize> = FxHashMap::default();
+      for prop in obj.props {
+        let (key, value) = match prop {
+          ast::PropOrSpread::Prop(prop) => match *prop {
+            ast::Prop::KeyValue(ast::KeyValueProp {
+              key: ast::PropName::Str(key),
+              value,
+            }) => (key, value),
+            _ => unreachable!("Properties of JSON objects should be string keyed"),
+          },
+          ast::PropOrSpread::Spread(_) => unreachable!("JSON objects have no spread elements"),
+        };
+        if let Some(idx) = index_by_key.get(&key.value) {
+          props[*idx].1 = value;
+        } else {
+          index_by_key.insert(key.value.clone(), props.len());
+          props.push((key.value, value));
+        }
+      }
+
+      let props = props
+        .into_iter()
+        .map(|(key, value)| {
+          let prop = if is_legal_identifier(&key) {
+            body.push(export_const(key.clone(), value));
+            ast::Prop::Shorthand(quote_ident!(key))
+          } else {
+            ast::Prop::KeyValue(ast::KeyValueProp {
+              key: ast::PropName::Str(quote_str!(key)),
+              value,
+            })
+          };
+          ast::PropOrSpread::Prop(box prop)
+        })
+        .collect();
+
+      box ast::Expr::Object(ast::ObjectLit {
+        span: obj.span,
+        props,
+      })
+    }
+    expr => box expr,
+  };
+
+  body.push(ast::ModuleItem::ModuleDecl(
+    ast::ModuleDecl::ExportDefaultExpr(ast::ExportDefaultExpr {
+      span: Default::default(),
+      expr: default_export,
+    }),
+  ));
+
+  Ok(ast::Module {
+    span: Default::default(),
+    body,
+    shebang: None,
+  })
+}
+
+fn export_const(name: JsWord, value: Box<ast::Expr>) -> ast::ModuleItem {
+  ast::ModuleItem::ModuleDecl(ast::ModuleDecl::ExportDecl(ast::ExportDecl {
+    span: Default::default(),
+    decl: ast::Decl::Var(box ast::VarDecl {
+      span: Default::default(),
+      kind: ast::VarDeclKind::Const,
+      declare: false,
+      decls: vec![ast::VarDeclarator {
+        span: Default::default(),
+        name: ast::Pat::Ident(quote_ident!(name).into()),
+        init: Some(value),
+        definite: false,
+      }],
+    }),
+  }))
+}
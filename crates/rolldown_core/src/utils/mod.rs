@@ -6,13 +6,36 @@ mod name_helpers;
 pub use name_helpers::*;
 mod preset_of_used_names;
 pub(crate) use preset_of_used_names::*;
+mod format_wrapper;
+pub(crate) use format_wrapper::*;
+mod input_source_map;
+pub(crate) use input_source_map::*;
+mod json_to_esm;
+pub(crate) use json_to_esm::*;
+mod asset_to_esm;
+pub(crate) use asset_to_esm::*;
+mod tsconfig;
 use rolldown_common::Loader;
+use rustc_hash::FxHashMap;
+pub(crate) use tsconfig::*;
 
-pub fn extract_loader_by_path(p: &Path) -> Loader {
-  p.extension()
-    .and_then(|ext| ext.to_str())
+/// `loaders` maps extensions without the leading dot to loaders and takes precedence over the default mapping.
+pub fn extract_loader_by_path(p: &Path, loaders: &FxHashMap<String, Loader>) -> Loader {
+  let ext = match p.extension().and_then(|ext| ext.to_str()) {
+    Some(ext) => ext,
+    None => return Loader::Js,
+  };
+  if let Some(loader) = loaders.get(ext) {
+    return *loader;
+  }
+  match ext {
+    "mjs" | "cjs" => Loader::Js,
+    "mts" | "cts" => Loader::Ts,
+    "txt" => Loader::Text,
     // Unknown extension should treat like JavaScript for Rollup-compatibility
-    .map(Loader::from_str)
-    .map(|l| l.unwrap_or(Loader::Js))
-    .unwrap_or(Loader::Js)
+    _ => match Loader::from_str(ext) {
+      Ok(loader @ (Loader::Js | Loader::Jsx | Loader::Ts | Loader::Tsx | Loader::Json)) => loader,
+      _ => Loader::Js,
+    },
+  }
 }
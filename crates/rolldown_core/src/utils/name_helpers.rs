@@ -61,6 +61,11 @@ fn need_escape(s: &str) -> bool {
   starts_with_digit(s) || RESERVED_NAMES.contains(s) || s == "arguments"
 }
 
+/// Whether `value` could be used as a binding name as it is.
+pub fn is_legal_identifier(value: &str) -> bool {
+  !value.is_empty() && !ILLEGAL_CHARACTERS.is_match(value) && !need_escape(value)
+}
+
 static ILLEGAL_CHARACTERS: Lazy<regex::Regex> = Lazy::new(|| regex::Regex::new(r"[^\w$]").unwrap());
 
 pub static CAPTURE_WORD_RE: Lazy<regex::Regex> = Lazy::new(|| regex::Regex::new(r"-(\w)").unwrap());
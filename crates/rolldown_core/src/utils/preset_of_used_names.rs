@@ -1,19 +1,64 @@
 use swc_core::ecma::atoms::{js_word, JsWord};
 
-use crate::ModuleFormat;
+use crate::{BuildOutputOptions, ModuleFormat};
 
-pub(crate) fn preset_of_used_names(format: &ModuleFormat) -> Vec<JsWord> {
+pub(crate) fn preset_of_used_names(output_options: &BuildOutputOptions) -> Vec<JsWord> {
   let mut preset = vec![js_word!("Object"), js_word!("Promise")];
 
-  match format {
+  match output_options.format {
     ModuleFormat::Esm => {}
     ModuleFormat::Cjs => {
+      preset.push("exports".into());
       preset.push(js_word!("module"));
       preset.push(js_word!("require"));
+      // Interop with external modules
+      preset.extend(rolldown_swc_visitors::interop_helper_names().map(JsWord::from));
       preset.push("__filename".into());
       preset.push("__dirname".into());
     }
+    ModuleFormat::Iife => {
+      preset.push("exports".into());
+      preset.extend(rolldown_swc_visitors::interop_helper_names().map(JsWord::from));
+      preset.extend(roots_of_globals(output_options));
+    }
+    ModuleFormat::Amd => {
+      preset.push("exports".into());
+      preset.push(js_word!("require"));
+      preset.push("define".into());
+      preset.extend(rolldown_swc_visitors::interop_helper_names().map(JsWord::from));
+      preset.extend(root_of(&output_options.amd.define));
+    }
+    ModuleFormat::Umd => {
+      preset.push("exports".into());
+      preset.push(js_word!("require"));
+      preset.push(js_word!("module"));
+      preset.push("define".into());
+      preset.push("factory".into());
+      preset.push("global".into());
+      preset.extend(rolldown_swc_visitors::interop_helper_names().map(JsWord::from));
+      preset.extend(root_of(&output_options.amd.define));
+      preset.extend(roots_of_globals(output_options));
+    }
+    ModuleFormat::System => {
+      preset.push("System".into());
+      // Parameters of the `System.register` callback
+      preset.push("exports".into());
+      preset.push(js_word!("module"));
+    }
   }
 
   preset
 }
+
+/// Global variables of external modules are referenced inside the wrapper function.
+fn roots_of_globals(output_options: &BuildOutputOptions) -> impl Iterator<Item = JsWord> + '_ {
+  output_options
+    .globals
+    .values()
+    .filter_map(|global| root_of(global))
+}
+
+/// `window` of `window.jQuery`.
+fn root_of(dotted: &str) -> Option<JsWord> {
+  dotted.split('.').next().map(JsWord::from)
+}
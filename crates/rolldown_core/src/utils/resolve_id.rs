@@ -11,14 +11,18 @@ pub(crate) async fn resolve_id(
   importer: Option<&ModuleId>,
   _preserve_symlinks: bool,
   plugin_driver: &SharedBuildPluginDriver,
+  skipped_plugin: Option<usize>,
 ) -> UnaryBuildResult<Option<ModuleId>> {
   let plugin_result = plugin_driver
     .read()
     .await
-    .resolve(ResolveArgs {
-      importer,
-      specifier,
-    })
+    .resolve(
+      ResolveArgs {
+        importer,
+        specifier,
+      },
+      skipped_plugin,
+    )
     .await?;
 
   if plugin_result.is_some() {
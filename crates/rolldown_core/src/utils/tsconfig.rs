@@ -0,0 +1,383 @@
+use std::{
+  path::{Path, PathBuf},
+  sync::{Arc, Mutex},
+};
+
+use rustc_hash::FxHashMap;
+use serde_json::Value;
+use sugar_path::SugarPathBuf;
+
+use crate::{BuildError, UnaryBuildResult};
+
+/// `compilerOptions` of `tsconfig.json` that affect how modules are transformed. `None` means the
+/// option is not set by the config or any config it extends.
+#[derive(Debug, Default, Clone)]
+pub(crate) struct TsCompilerOptions {
+  pub experimental_decorators: Option<bool>,
+  pub use_define_for_class_fields: Option<bool>,
+  /// `preserve`, `react`, `react-jsx`, `react-jsxdev` or `react-native`, in lowercase.
+  pub jsx: Option<String>,
+  pub jsx_factory: Option<String>,
+  pub jsx_fragment_factory: Option<String>,
+  pub jsx_import_source: Option<String>,
+  pub verbatim_module_syntax: Option<bool>,
+  /// `remove`, `preserve` or `error`, in lowercase.
+  pub imports_not_used_as_values: Option<String>,
+  /// Such as `es2019` or `esnext`, in lowercase. It's only used for the default of
+  /// `useDefineForClassFields`, syntax isn't lowered to the target.
+  pub target: Option<String>,
+  /// Absolute path resolved against the config declaring it.
+  pub base_url: Option<PathBuf>,
+  pub paths: Option<TsPaths>,
+}
+
+/// `paths` with the directory of the config declaring it, which targets are resolved against if
+/// `baseUrl` is not set.
+#[derive(Debug, Clone)]
+pub(crate) struct TsPaths {
+  dir: PathBuf,
+  patterns: Vec<(String, Vec<String>)>,
+}
+
+impl TsPaths {
+  /// Like TypeScript, an exact pattern is preferred, otherwise the wildcard pattern with the longest
+  /// prefix wins. Returns targets of the pattern and the part matched by `*`.
+  fn find_match<'a>(&'a self, specifier: &'a str) -> Option<(&'a [String], &'a str)> {
+    if let Some((_, targets)) = self
+      .patterns
+      .iter()
+      .find(|(pattern, _)| pattern == specifier)
+    {
+      return Some((targets, ""));
+    }
+    self
+      .patterns
+      .iter()
+      .filter_map(|(pattern, targets)| {
+        let (prefix, suffix) = pattern.split_once('*')?;
+        let matched = specifier.strip_prefix(prefix)?.strip_suffix(suffix)?;
+        Some((prefix.len(), targets.as_slice(), matched))
+      })
+      .max_by_key(|(prefix_len, ..)| *prefix_len)
+      .map(|(_, targets, matched)| (targets, matched))
+  }
+}
+
+impl TsCompilerOptions {
+  fn from_json(value: &Value, config_dir: &Path) -> Self {
+    let bool_of = |key: &str| value.get(key).and_then(Value::as_bool);
+    let string_of = |key: &str| {
+      value
+        .get(key)
+        .and_then(Value::as_str)
+        .map(ToString::to_string)
+    };
+    let lowercase_string_of = |key: &str| string_of(key).map(|value| value.to_ascii_lowercase());
+    Self {
+      experimental_decorators: bool_of("experimentalDecorators"),
+      use_define_for_class_fields: bool_of("useDefineForClassFields"),
+      jsx: lowercase_string_of("jsx"),
+      jsx_factory: string_of("jsxFactory"),
+      jsx_fragment_factory: string_of("jsxFragmentFactory"),
+      jsx_import_source: string_of("jsxImportSource"),
+      verbatim_module_syntax: bool_of("verbatimModuleSyntax"),
+      imports_not_used_as_values: lowercase_string_of("importsNotUsedAsValues"),
+      target: lowercase_string_of("target"),
+      base_url: string_of("baseUrl").map(|base_url| config_dir.join(base_url).into_absolutize()),
+      paths: value
+        .get("paths")
+        .and_then(Value::as_object)
+        .map(|paths| TsPaths {
+          dir: config_dir.to_path_buf(),
+          patterns: paths
+            .iter()
+            .map(|(pattern, targets)| {
+              let targets = targets
+                .as_array()
+                .map(|targets| {
+                  targets
+                    .iter()
+                    .filter_map(Value::as_str)
+                    .map(ToString::to_string)
+                    .collect()
+                })
+                .unwrap_or_default();
+              (pattern.clone(), targets)
+            })
+            .collect(),
+        }),
+    }
+  }
+
+  /// Options set by `self` take precedence over `base`.
+  fn extend(self, base: Self) -> Self {
+    Self {
+      experimental_decorators: self
+        .experimental_decorators
+        .or(base.experimental_decorators),
+      use_define_for_class_fields: self
+        .use_define_for_class_fields
+        .or(base.use_define_for_class_fields),
+      jsx: self.jsx.or(base.jsx),
+      jsx_factory: self.jsx_factory.or(base.jsx_factory),
+      jsx_fragment_factory: self.jsx_fragment_factory.or(base.jsx_fragment_factory),
+      jsx_import_source: self.jsx_import_source.or(base.jsx_import_source),
+      verbatim_module_syntax: self.verbatim_module_syntax.or(base.verbatim_module_syntax),
+      imports_not_used_as_values: self
+        .imports_not_used_as_values
+        .or(base.imports_not_used_as_values),
+      target: self.target.or(base.target),
+      base_url: self.base_url.or(base.base_url),
+      paths: self.paths.or(base.paths),
+    }
+  }
+
+  /// Like TypeScript, `useDefineForClassFields` defaults to `true` if `target` is `ES2022` or newer.
+  pub fn resolved_use_define_for_class_fields(&self) -> Option<bool> {
+    self.use_define_for_class_fields.or_else(|| {
+      let target = self.target.as_deref()?;
+      let year = target
+        .strip_prefix("es")
+        .and_then(|year| year.parse::<u32>().ok());
+      Some(target == "esnext" || year.map_or(false, |year| year >= 2022))
+    })
+  }
+
+  /// Paths that a bare specifier is mapped to by `paths` and `baseUrl`, in the order they should be
+  /// tried.
+  fn path_candidates(&self, specifier: &str) -> Vec<PathBuf> {
+    let mut candidates = vec![];
+    if let Some(paths) = &self.paths {
+      let base = self.base_url.as_deref().unwrap_or(&paths.dir);
+      if let Some((targets, matched)) = paths.find_match(specifier) {
+        candidates.extend(targets.iter().map(|target| {
+          base
+            .join(target.replacen('*', matched, 1))
+            .into_absolutize()
+        }));
+      }
+    }
+    if let Some(base_url) = &self.base_url {
+      candidates.push(base_url.join(specifier).into_absolutize());
+    }
+    candidates
+  }
+
+  /// Imports only used as types are kept for their side effects.
+  pub fn preserves_unused_imports(&self) -> bool {
+    self.verbatim_module_syntax == Some(true)
+      || matches!(
+        self.imports_not_used_as_values.as_deref(),
+        Some("preserve" | "error")
+      )
+  }
+}
+
+/// Find the nearest `tsconfig.json` of modules and resolve their `compilerOptions`. Results are cached
+/// by directories, since modules in the same directory share the same config.
+#[derive(Debug, Default)]
+pub(crate) struct TsConfigResolver {
+  options_by_dir: Mutex<FxHashMap<PathBuf, Option<Arc<TsCompilerOptions>>>>,
+}
+
+impl TsConfigResolver {
+  pub fn compiler_options_of(
+    &self,
+    module_path: &Path,
+  ) -> UnaryBuildResult<Option<Arc<TsCompilerOptions>>> {
+    match module_path.parent() {
+      Some(dir) => self.compiler_options_in(dir),
+      None => Ok(None),
+    }
+  }
+
+  /// Resolve a bare specifier by `paths` and `baseUrl` of the config governing the importer. `None`
+  /// means the specifier isn't mapped, or none of the mapped paths exists.
+  pub fn resolve_paths(
+    &self,
+    importer: &Path,
+    specifier: &str,
+  ) -> UnaryBuildResult<Option<PathBuf>> {
+    if specifier.starts_with('.') || Path::new(specifier).is_absolute() {
+      return Ok(None);
+    }
+    let Some(options) = self.compiler_options_of(importer)? else {
+      return Ok(None);
+    };
+    Ok(
+      options
+        .path_candidates(specifier)
+        .iter()
+        .find_map(|candidate| resolve_file(candidate)),
+    )
+  }
+
+  fn compiler_options_in(&self, dir: &Path) -> UnaryBuildResult<Option<Arc<TsCompilerOptions>>> {
+    if let Some(options) = self.options_by_dir.lock().unwrap().get(dir) {
+      return Ok(options.clone());
+    }
+    let config_path = dir.join("tsconfig.json");
+    let options = if config_path.is_file() {
+      Some(Arc::new(load_tsconfig(&config_path, &mut vec![])?))
+    } else {
+      match dir.parent() {
+        Some(parent) => self.compiler_options_in(parent)?,
+        None => None,
+      }
+    };
+    self
+      .options_by_dir
+      .lock()
+      .unwrap()
+      .insert(dir.to_path_buf(), options.clone());
+    Ok(options)
+  }
+}
+
+/// Load `compilerOptions` of the config, following `extends`. `extending` is the chain of configs
+/// being loaded, which is used to break circular `extends`.
+fn load_tsconfig(path: &Path, extending: &mut Vec<PathBuf>) -> UnaryBuildResult<TsCompilerOptions> {
+  if extending.iter().any(|p| p == path) {
+    return Ok(Default::default());
+  }
+  let content = std::fs::read_to_string(path)
+    .map_err(|e| BuildError::io_error(e).context(format!("Read file: {}", path.display())))?;
+  let json: Value = serde_json::from_str(&strip_json_comments(&content))
+    .map_err(|e| BuildError::parse_json_failed(path, e.to_string()))?;
+
+  let mut options = json
+    .get("compilerOptions")
+    .map(|compiler_options| {
+      TsCompilerOptions::from_json(compiler_options, path.parent().unwrap_or(path))
+    })
+    .unwrap_or_default();
+  let extends = match json.get("extends") {
+    Some(Value::String(specifier)) => vec![specifier.as_str()],
+    Some(Value::Array(specifiers)) => specifiers.iter().filter_map(Value::as_str).collect(),
+    _ => vec![],
+  };
+
+  extending.push(path.to_path_buf());
+  let config_dir = path.parent().unwrap_or(path);
+  // Later configs in `extends` take precedence over former ones.
+  for specifier in extends.into_iter().rev() {
+    let base_path = resolve_extends(specifier, config_dir).ok_or_else(|| {
+      BuildError::parse_json_failed(path, format!("Cannot find `{specifier}` in `extends`"))
+    })?;
+    options = options.extend(load_tsconfig(&base_path, extending)?);
+  }
+  extending.pop();
+
+  Ok(options)
+}
+
+/// `extends` is either a path relative to the config, or a config inside a package such as
+/// `@tsconfig/node16/tsconfig.json` and `@tsconfig/node16`.
+fn resolve_extends(specifier: &str, config_dir: &Path) -> Option<PathBuf> {
+  let with_json_ext = |path: &Path| with_extension_appended(path, "json");
+
+  if specifier.starts_with('.') || Path::new(specifier).is_absolute() {
+    let path = config_dir.join(specifier);
+    return [with_json_ext(&path), path]
+      .into_iter()
+      .rev()
+      .find(|path| path.is_file());
+  }
+
+  config_dir.ancestors().find_map(|dir| {
+    let path = dir.join("node_modules").join(specifier);
+    if path.is_file() {
+      return Some(path);
+    }
+    let path_with_json_ext = with_json_ext(&path);
+    if path_with_json_ext.is_file() {
+      return Some(path_with_json_ext);
+    }
+    if path.is_dir() {
+      // The `tsconfig` field of `package.json` points to the config of the package.
+      let config_in_package_json = std::fs::read_to_string(path.join("package.json"))
+        .ok()
+        .and_then(|content| serde_json::from_str::<Value>(&content).ok())
+        .and_then(|package_json| {
+          package_json
+            .get("tsconfig")
+            .and_then(Value::as_str)
+            .map(|config| path.join(config))
+        });
+      return config_in_package_json
+        .into_iter()
+        .chain([path.join("tsconfig.json")])
+        .find(|path| path.is_file());
+    }
+    None
+  })
+}
+
+const RESOLVED_EXTENSIONS: [&str; 9] =
+  ["ts", "tsx", "mts", "cts", "js", "jsx", "mjs", "cjs", "json"];
+
+/// Mapped paths could omit extensions or point to directories with index files.
+fn resolve_file(path: &Path) -> Option<PathBuf> {
+  if path.is_file() {
+    return Some(path.to_path_buf());
+  }
+  RESOLVED_EXTENSIONS
+    .iter()
+    .map(|ext| with_extension_appended(path, ext))
+    .chain(
+      RESOLVED_EXTENSIONS
+        .iter()
+        .map(|ext| path.join(format!("index.{ext}"))),
+    )
+    .find(|path| path.is_file())
+}
+
+/// Unlike `Path::with_extension`, `foo.config` becomes `foo.config.json` rather than `foo.json`.
+fn with_extension_appended(path: &Path, ext: &str) -> PathBuf {
+  let mut path = path.to_path_buf().into_os_string();
+  path.push(".");
+  path.push(ext);
+  PathBuf::from(path)
+}
+
+/// `tsconfig.json` is JSON with comments and trailing commas, which are removed here.
+fn strip_json_comments(content: &str) -> String {
+  let mut stripped = String::with_capacity(content.len());
+  let mut chars = content.chars().peekable();
+  while let Some(c) = chars.next() {
+    match c {
+      '"' => {
+        stripped.push(c);
+        while let Some(c) = chars.next() {
+          stripped.push(c);
+          match c {
+            '\\' => stripped.extend(chars.next()),
+            '"' => break,
+            _ => {}
+          }
+        }
+      }
+      '/' if chars.peek() == Some(&'/') => while chars.next_if(|c| *c != '\n').is_some() {},
+      '/' if chars.peek() == Some(&'*') => {
+        chars.next();
+        let mut prev = '\0';
+        for c in chars.by_ref() {
+          if prev == '*' && c == '/' {
+            break;
+          }
+          prev = c;
+        }
+      }
+      ']' | '}' => {
+        // Remove the trailing comma before the closing bracket
+        let trimmed_len = stripped.trim_end().len();
+        if stripped[..trimmed_len].ends_with(',') {
+          stripped.truncate(trimmed_len - 1);
+        }
+        stripped.push(c);
+      }
+      _ => stripped.push(c),
+    }
+  }
+  stripped
+}
@@ -129,6 +129,71 @@ impl Error {
     })
   }
 
+  pub fn sourcemap_error(id: impl AsRef<Path>, reason: impl Into<StaticStr>) -> Self {
+    Self::with_kind(ErrorKind::SourcemapError {
+      id: id.as_ref().to_path_buf(),
+      reason: reason.into(),
+    })
+  }
+
+  pub fn sourcemap_broken(filename: impl Into<StaticStr>) -> Self {
+    Self::with_kind(ErrorKind::SourcemapBroken {
+      filename: filename.into(),
+    })
+  }
+
+  pub fn invalid_option(
+    option: &'static str,
+    value: impl Into<StaticStr>,
+    explanation: impl Into<StaticStr>,
+  ) -> Self {
+    Self::with_kind(ErrorKind::InvalidOption {
+      option,
+      value: value.into(),
+      explanation: explanation.into(),
+    })
+  }
+
+  pub fn missing_name_option_for_iife_export() -> Self {
+    Self::with_kind(ErrorKind::MissingNameOptionForIifeExport)
+  }
+
+  pub fn missing_name_option_for_umd_export() -> Self {
+    Self::with_kind(ErrorKind::MissingNameOptionForUmdExport)
+  }
+
+  pub fn missing_global_name(
+    source: impl Into<StaticStr>,
+    guessed_name: impl Into<StaticStr>,
+  ) -> Self {
+    Self::with_kind(ErrorKind::MissingGlobalName {
+      source: source.into(),
+      guessed_name: guessed_name.into(),
+    })
+  }
+
+  pub fn unexpected_named_import(
+    source: impl Into<StaticStr>,
+    imported: impl Into<StaticStr>,
+    is_reexport: bool,
+  ) -> Self {
+    Self::with_kind(ErrorKind::UnexpectedNamedImport {
+      source: source.into(),
+      imported: imported.into(),
+      is_reexport,
+    })
+  }
+
+  pub fn file_name_conflict(file_name: impl Into<StaticStr>) -> Self {
+    Self::with_kind(ErrorKind::FileNameConflict {
+      file_name: file_name.into(),
+    })
+  }
+
+  pub fn emit_chunk_after_module_loading() -> Self {
+    Self::with_kind(ErrorKind::EmitChunkAfterModuleLoading)
+  }
+
   // --- rolldown special
 
   pub fn parse_js_failed(
@@ -141,6 +206,29 @@ impl Error {
     })
   }
 
+  pub fn unloaded_virtual_module(id: impl AsRef<Path>) -> Self {
+    Self::with_kind(ErrorKind::UnloadedVirtualModule {
+      id: id.as_ref().to_path_buf(),
+    })
+  }
+
+  pub fn parse_json_failed(id: impl AsRef<Path>, reason: impl Into<StaticStr>) -> Self {
+    Self::with_kind(ErrorKind::ParseJsonFailed {
+      id: id.as_ref().to_path_buf(),
+      reason: reason.into(),
+    })
+  }
+
+  pub fn unexportable_commonjs_reexport(
+    entry_module: impl AsRef<Path>,
+    sources: Vec<PathBuf>,
+  ) -> Self {
+    Self::with_kind(ErrorKind::UnexportableCommonJsReexport {
+      entry_module: entry_module.as_ref().to_path_buf(),
+      sources,
+    })
+  }
+
   // --- TODO: we should remove following errors
 
   pub fn io_error(e: std::io::Error) -> Self {
@@ -71,3 +71,5 @@ pub const VALIDATION_ERROR: &str = "VALIDATION_ERROR";
 
 pub const PANIC: &str = "PANIC";
 pub const IO_ERROR: &str = "IO_ERROR";
+pub const UNLOADED_VIRTUAL_MODULE: &str = "UNLOADED_VIRTUAL_MODULE";
+pub const UNEXPORTABLE_COMMONJS_REEXPORT: &str = "UNEXPORTABLE_COMMONJS_REEXPORT";
@@ -47,12 +47,50 @@ pub enum ErrorKind {
     exporter: PathBuf,
     export_name: StaticStr,
   },
+  SourcemapError {
+    id: PathBuf,
+    reason: StaticStr,
+  },
+  SourcemapBroken {
+    filename: StaticStr,
+  },
+  InvalidOption {
+    option: &'static str,
+    value: StaticStr,
+    explanation: StaticStr,
+  },
+  MissingNameOptionForIifeExport,
+  MissingNameOptionForUmdExport,
+  MissingGlobalName {
+    source: StaticStr,
+    guessed_name: StaticStr,
+  },
+  UnexpectedNamedImport {
+    source: StaticStr,
+    imported: StaticStr,
+    is_reexport: bool,
+  },
+  FileNameConflict {
+    file_name: StaticStr,
+  },
+  EmitChunkAfterModuleLoading,
 
   // --- Rolldown specific
   ParseJsFailed {
     source_file: Arc<SourceFile>,
     source: swc_core::ecma::parser::error::Error,
   },
+  UnloadedVirtualModule {
+    id: PathBuf,
+  },
+  ParseJsonFailed {
+    id: PathBuf,
+    reason: StaticStr,
+  },
+  UnexportableCommonJsReexport {
+    entry_module: PathBuf,
+    sources: Vec<PathBuf>,
+  },
 
   /// This error means that rolldown panics because unrecoverable error happens.
   ///
@@ -107,12 +145,33 @@ impl Display for ErrorKind {
       }
       ErrorKind::ShimmedExport { binding, exporter } => write!(f, r#"Missing export "{binding}" has been shimmed in module "{}"."#, exporter.may_display_relative()),
       ErrorKind::CircularReexport { export_name, exporter } => write!(f, r#""{export_name}" cannot be exported from "{}" as it is a reexport that references itself."#, exporter.may_display_relative()),
+      ErrorKind::SourcemapError { id, reason } => write!(f, r#"Failed to load the source map of "{}": {reason}"#, id.may_display_relative()),
+      ErrorKind::SourcemapBroken { filename } => write!(f, r#"The source map of "{filename}" is dropped, because the code is changed by "renderChunk" without a source map."#),
+      ErrorKind::InvalidOption { option, value, explanation } => write!(f, r#"Invalid value "{value}" for option "{option}" - {explanation}."#),
+      ErrorKind::MissingNameOptionForIifeExport => write!(f, r#"If you do not supply "output.name", you may not be able to access the exports of an IIFE bundle."#),
+      ErrorKind::MissingNameOptionForUmdExport => write!(f, r#"You must supply "output.name" for UMD bundles that have exports so that the exports are accessible in environments without a module loader."#),
+      ErrorKind::MissingGlobalName { source, guessed_name } => write!(f, r#"No name was provided for external module "{source}" in "output.globals" – guessing "{guessed_name}"."#),
+      ErrorKind::UnexpectedNamedImport { source, imported, is_reexport } => {
+        let (verb, kind) = if *is_reexport { ("re-exported", "re-export") } else { ("imported", "import") };
+        write!(f, r#"The named export "{imported}" was {verb} from the external module "{source}" even though its interop type is "defaultOnly". Either remove or change this {kind} or change the value of the "output.interop" option."#)
+      }
+      ErrorKind::FileNameConflict { file_name } => write!(f, r#"The emitted file "{file_name}" overwrites a previously emitted file of the same name."#),
+      ErrorKind::EmitChunkAfterModuleLoading => write!(f, "Cannot emit chunks after module loading has finished."),
       // Rolldown specific
       ErrorKind::Panic { source } => source.fmt(f),
       ErrorKind::Napi { status, reason } => write!(f, "Napi error: {} {}", status, reason),
       ErrorKind::ParseJsFailed { source_file, .. } => {
         write!(f, "Parse failed: {}", source_file.name )
       }
+      ErrorKind::UnloadedVirtualModule { id } => write!(f, r#"Virtual module "{}" is not loaded by any plugin."#, id.may_display_relative()),
+      ErrorKind::ParseJsonFailed { id, reason } => write!(f, r#"Failed to parse JSON file "{}": {reason}"#, id.may_display_relative()),
+      ErrorKind::UnexportableCommonJsReexport { entry_module, sources } => write!(
+        f,
+        r#""{entry}" re-exports all names of the CommonJS {} {}, which are only known at runtime. They can't be exported by the chunk with the current "output.format" and "output.exports", but they're still available on the namespace of "{entry}"."#,
+        if sources.len() == 1 { "module" } else { "modules" },
+        format_quoted_strings(&sources.iter().map(|p| p.may_display_relative()).collect::<Vec<_>>()),
+        entry = entry_module.may_display_relative(),
+      ),
       ErrorKind::IoError(e) => e.fmt(f),
     }
   }
@@ -137,9 +196,21 @@ impl ErrorKind {
       ErrorKind::IncompatibleExportOptionValue { .. } => error_code::INVALID_EXPORT_OPTION,
       ErrorKind::ShimmedExport { .. } => error_code::SHIMMED_EXPORT,
       ErrorKind::CircularReexport { .. } => error_code::CIRCULAR_REEXPORT,
+      ErrorKind::SourcemapError { .. } => error_code::SOURCEMAP_ERROR,
+      ErrorKind::SourcemapBroken { .. } => error_code::SOURCEMAP_BROKEN,
+      ErrorKind::InvalidOption { .. } => error_code::INVALID_OPTION,
+      ErrorKind::MissingNameOptionForIifeExport => error_code::MISSING_NAME_OPTION_FOR_IIFE_EXPORT,
+      ErrorKind::MissingNameOptionForUmdExport => error_code::INVALID_OPTION,
+      ErrorKind::MissingGlobalName { .. } => error_code::MISSING_GLOBAL_NAME,
+      ErrorKind::UnexpectedNamedImport { .. } => error_code::UNEXPECTED_NAMED_IMPORT,
+      ErrorKind::FileNameConflict { .. } => error_code::FILE_NAME_CONFLICT,
+      ErrorKind::EmitChunkAfterModuleLoading => error_code::INVALID_ROLLUP_PHASE,
       // Rolldown specific
       ErrorKind::Panic { .. } => error_code::PANIC,
       ErrorKind::IoError(_) => error_code::IO_ERROR,
+      ErrorKind::UnloadedVirtualModule { .. } => error_code::UNLOADED_VIRTUAL_MODULE,
+      ErrorKind::UnexportableCommonJsReexport { .. } => error_code::UNEXPORTABLE_COMMONJS_REEXPORT,
+      ErrorKind::ParseJsonFailed { .. } => error_code::PARSE_ERROR,
       ErrorKind::Napi {
         status: _,
         reason: _,
@@ -17,6 +17,10 @@ impl Errors {
     self.0.push(error);
   }
 
+  pub fn iter(&self) -> impl Iterator<Item = &Error> {
+    self.0.iter()
+  }
+
   pub fn into_vec(self) -> Vec<Error> {
     self.0
   }
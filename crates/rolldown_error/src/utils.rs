@@ -39,10 +39,15 @@ pub trait PathExt {
 
 impl PathExt for Path {
   fn may_display_relative(&self) -> Cow<str> {
+    let display = self.to_string_lossy();
+    // Make the `\0` prefix of virtual ids visible
+    if let Some(stripped) = display.strip_prefix('\0') {
+      return Cow::Owned(format!("\\0{stripped}"));
+    }
     let path = if CWD.is_set() && self.is_absolute() {
       CWD.with(|cwd| self.relative(cwd))
     } else {
-      return self.to_string_lossy();
+      return display;
     };
     Cow::Owned(path.display().to_string())
   }
@@ -3,6 +3,16 @@
 
 /* auto-generated by NAPI-RS */
 
+/** Information of a rendered chunk, which is passed to `renderChunk`. */
+export interface ChunkInfo {
+  name: string
+  fileName: string
+  isEntry: boolean
+  isDynamicEntry: boolean
+  facadeModuleId?: string
+  moduleIds: Array<string>
+  exports: Array<string>
+}
 export interface ExternalOption {
   function?: (specifier: string, importer: string | undefined, isResolved: boolean) => boolean
   string: Array<string>
@@ -11,21 +21,72 @@ export interface ResolveIdResult {
   id: string
   external: boolean
 }
+export interface TransformResult {
+  code: string
+  /** Source map in JSON */
+  map?: string
+}
+export interface LoadResult {
+  code: string
+  loader?: 'js' | 'jsx' | 'ts' | 'tsx' | 'json' | 'text' | 'base64' | 'dataurl' | 'binary' | 'file' | 'empty'
+}
+/** Exactly one of `glob` and `regex` should be provided. */
+export interface FilterPatternOption {
+  glob?: string
+  /** Uses the syntax of the `regex` crate, which is compatible with most JavaScript regular expressions. */
+  regex?: string
+}
+export interface HookFilterOption {
+  include?: Array<FilterPatternOption>
+  exclude?: Array<FilterPatternOption>
+}
 export interface BuildPluginOption {
   name: string
-  transform?: (id: string, code: string) => Promise<string | null | undefined>
-  resolveId?: (specifier: string, importer?: string) => Promise<string | null | ResolveIdResult>
+  enforce?: 'pre' | 'post'
+  buildStart?: (ctx: PluginContext) => Promise<void>
+  transform?: (ctx: PluginContext, code: string, id: string) => Promise<TransformResult | null | undefined>
+  /** Skip calling `transform` for modules that don't match the filter. */
+  transformFilter?: HookFilterOption
+  resolveId?: (ctx: PluginContext, specifier: string, importer?: string) => Promise<null | ResolveIdResult>
+  /** Skip calling `resolveId` for specifiers that don't match the filter. */
+  resolveIdFilter?: HookFilterOption
+  load?: (ctx: PluginContext, id: string) => Promise<null | LoadResult>
+  /** Skip calling `load` for modules that don't match the filter. */
+  loadFilter?: HookFilterOption
+  /** `error` is the message of errors if the build failed. */
+  buildEnd?: (ctx: PluginContext, error?: string) => Promise<void>
+  renderChunk?: (ctx: PluginContext, code: string, chunk: ChunkInfo) => Promise<TransformResult | null | undefined>
+  /** Return the new outputs to add, rename or delete outputs. `null` means the outputs are not changed. */
+  generateBundle?: (ctx: PluginContext, outputs: Array<OutputChunk>) => Promise<Array<OutputChunk> | null | undefined>
+  closeBundle?: (ctx: PluginContext) => Promise<void>
+}
+export interface ModuleInfo {
+  id: string
+  isEntry: boolean
+  isExternal: boolean
+  importedIds: Array<string>
+  dynamicallyImportedIds: Array<string>
 }
 export interface NodeResolveOptions {
   extensions: Array<string>
 }
 export interface TsConfigOptions {
-  useDefineForClassFields: boolean
+  useDefineForClassFields?: boolean
+}
+export interface JsxOptions {
+  runtime?: 'classic' | 'automatic'
+  pragma?: string
+  pragmaFrag?: string
+  importSource?: string
+  development?: boolean
 }
 export interface BuiltinsOptions {
   /** None means disable the behaviors */
   nodeResolve?: NodeResolveOptions
   tsconfig?: TsConfigOptions
+  jsx?: JsxOptions
+  /** Loaders by extensions, such as `{ '.glsl': 'text', '.png': 'file' }` */
+  loaders?: Record<string, 'js' | 'jsx' | 'ts' | 'tsx' | 'json' | 'text' | 'base64' | 'dataurl' | 'binary' | 'file' | 'empty'>
 }
 export interface InputOptions {
   external: ExternalOption
@@ -33,20 +94,47 @@ export interface InputOptions {
   plugins: Array<BuildPluginOption>
   preserveSymlinks: boolean
   shimMissingExports: boolean
+  strictExecutionOrder: boolean
   treeshake?: boolean
   cwd: string
   builtins: BuiltinsOptions
 }
+/** Exactly one of `function` and `mode` should be provided. */
+export interface InteropOption {
+  function?: (id: string | null) => string
+  mode?: 'auto' | 'esModule' | 'default' | 'defaultOnly' | 'compat'
+}
+export interface AmdOptions {
+  id?: string
+  define?: string
+}
 export interface OutputOptions {
   entryFileNames?: string
   chunkFileNames?: string
+  assetFileNames?: string
+  amd?: AmdOptions
   dir?: string
   exports?: 'default' | 'named' | 'none' | 'auto'
-  format?: 'esm' | 'cjs'
+  format?: 'esm' | 'cjs' | 'iife' | 'amd' | 'umd' | 'system'
+  globals?: Record<string, string>
+  interop?: InteropOption
+  name?: string
+  sourcemap?: boolean | 'inline' | 'hidden'
+  sourcemapExcludeSources?: boolean
 }
 export interface OutputChunk {
+  /** Empty for binary assets, whose content is in `source`. */
   code: string
   fileName: string
+  /** Content of binary assets emitted by plugins, which might not be valid UTF-8. */
+  source?: Buffer
+}
+/** The context passed to hooks of JS plugins as the first argument. It's `this` of rollup plugins. */
+export class PluginContext {
+  warn(message: string): void
+  resolve(specifier: string, importer: string | undefined | null, skipSelf: boolean): Promise<ResolveIdResult | null>
+  getModuleInfo(id: string): ModuleInfo | null
+  addWatchFile(file: string): void
 }
 export class Bundler {
   constructor(inputOpts: InputOptions)
@@ -59,13 +59,7 @@ impl Bundler {
       .await
       .map_err(|err| self.handle_errors(err))?;
 
-    let output_chunks = outputs
-      .into_iter()
-      .map(|asset| OutputChunk {
-        code: asset.content,
-        file_name: asset.filename,
-      })
-      .collect::<Vec<_>>();
+    let output_chunks = outputs.iter().map(OutputChunk::from).collect::<Vec<_>>();
     Ok(output_chunks)
   }
 
@@ -82,13 +76,7 @@ impl Bundler {
       .await
       .map_err(|err| self.handle_errors(err))?;
 
-    let output_chunks = outputs
-      .into_iter()
-      .map(|asset| OutputChunk {
-        code: asset.content,
-        file_name: asset.filename,
-      })
-      .collect::<Vec<_>>();
+    let output_chunks = outputs.iter().map(OutputChunk::from).collect::<Vec<_>>();
     Ok(output_chunks)
   }
 
@@ -0,0 +1,31 @@
+use derivative::Derivative;
+use serde::Deserialize;
+
+/// Information of a rendered chunk, which is passed to `renderChunk`.
+#[napi_derive::napi(object)]
+#[derive(Deserialize, Default, Derivative)]
+#[serde(rename_all = "camelCase")]
+#[derivative(Debug)]
+pub struct ChunkInfo {
+  pub name: String,
+  pub file_name: String,
+  pub is_entry: bool,
+  pub is_dynamic_entry: bool,
+  pub facade_module_id: Option<String>,
+  pub module_ids: Vec<String>,
+  pub exports: Vec<String>,
+}
+
+impl From<&rolldown_plugin::ChunkInfo> for ChunkInfo {
+  fn from(chunk: &rolldown_plugin::ChunkInfo) -> Self {
+    Self {
+      name: chunk.name.clone(),
+      file_name: chunk.filename.clone(),
+      is_entry: chunk.is_entry,
+      is_dynamic_entry: chunk.is_dynamic_entry,
+      facade_module_id: chunk.facade_module_id.as_ref().map(|id| id.to_string()),
+      module_ids: chunk.module_ids.iter().map(|id| id.to_string()).collect(),
+      exports: chunk.exports.clone(),
+    }
+  }
+}
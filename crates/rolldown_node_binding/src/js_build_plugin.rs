@@ -1,9 +1,20 @@
+use std::str::FromStr;
+
 use derivative::Derivative;
-use rolldown_plugin::{BuildPlugin, PluginName, ResolvedId};
+use itertools::Itertools;
+use rolldown_error::{Error as BuildError, Errors};
+use rolldown_plugin::{
+  BuildPlugin, HookFilter, HookKind, HookNoopReturn, LoadedSource, PluginName, PluginOrder,
+  ResolvedId, SourceMap, TransformedSource,
+};
 
 use crate::{
-  js_callbacks::{ResolveIdCallback, TransformCallback},
-  options::BuildPluginOption,
+  js_callbacks::{
+    BuildEndCallback, BuildStartCallback, CloseBundleCallback, GenerateBundleCallback,
+    LoadCallback, RenderChunkCallback, ResolveIdCallback, TransformCallback,
+  },
+  options::{BuildPluginOption, TransformResult},
+  output_chunk::OutputChunk,
   utils::NapiErrorExt,
 };
 
@@ -11,14 +22,62 @@ use crate::{
 #[derivative(Debug)]
 pub struct JsBuildPlugin {
   pub name: String,
+  order: PluginOrder,
+  transform_filter: Option<HookFilter>,
+  resolve_id_filter: Option<HookFilter>,
+  load_filter: Option<HookFilter>,
+  #[derivative(Debug = "ignore")]
+  build_start_cb: Option<BuildStartCallback>,
   #[derivative(Debug = "ignore")]
   transform_cb: Option<TransformCallback>,
   #[derivative(Debug = "ignore")]
   resolve_id_cb: Option<ResolveIdCallback>,
+  #[derivative(Debug = "ignore")]
+  load_cb: Option<LoadCallback>,
+  #[derivative(Debug = "ignore")]
+  build_end_cb: Option<BuildEndCallback>,
+  #[derivative(Debug = "ignore")]
+  render_chunk_cb: Option<RenderChunkCallback>,
+  #[derivative(Debug = "ignore")]
+  generate_bundle_cb: Option<GenerateBundleCallback>,
+  #[derivative(Debug = "ignore")]
+  close_bundle_cb: Option<CloseBundleCallback>,
 }
 
 impl JsBuildPlugin {
-  pub fn new(option: BuildPluginOption) -> napi::Result<Self> {
+  pub fn new(mut option: BuildPluginOption) -> napi::Result<Self> {
+    let order = PluginOrder::from_enforce(option.enforce.as_deref()).ok_or_else(|| {
+      napi::Error::new(
+        napi::Status::InvalidArg,
+        format!(
+          "Invalid enforce {:?} of plugin {}. Expected \"pre\" or \"post\"",
+          option.enforce, option.name
+        ),
+      )
+    })?;
+
+    let transform_filter = option
+      .transform_filter
+      .take()
+      .map(|filter| filter.into_hook_filter())
+      .transpose()?;
+    let resolve_id_filter = option
+      .resolve_id_filter
+      .take()
+      .map(|filter| filter.into_hook_filter())
+      .transpose()?;
+    let load_filter = option
+      .load_filter
+      .take()
+      .map(|filter| filter.into_hook_filter())
+      .transpose()?;
+
+    let build_start_cb = option
+      .build_start
+      .as_ref()
+      .map(BuildStartCallback::new)
+      .transpose()?;
+
     let transform_tsfn = option
       .transform
       .as_ref()
@@ -31,16 +90,73 @@ impl JsBuildPlugin {
       .map(ResolveIdCallback::new)
       .transpose()?;
 
+    let load_cb = option.load.as_ref().map(LoadCallback::new).transpose()?;
+
+    let build_end_cb = option
+      .build_end
+      .as_ref()
+      .map(BuildEndCallback::new)
+      .transpose()?;
+
+    let render_chunk_cb = option
+      .render_chunk
+      .as_ref()
+      .map(RenderChunkCallback::new)
+      .transpose()?;
+
+    let generate_bundle_cb = option
+      .generate_bundle
+      .as_ref()
+      .map(GenerateBundleCallback::new)
+      .transpose()?;
+
+    let close_bundle_cb = option
+      .close_bundle
+      .as_ref()
+      .map(CloseBundleCallback::new)
+      .transpose()?;
+
     Ok(JsBuildPlugin {
       name: option.name,
+      order,
+      transform_filter,
+      resolve_id_filter,
+      load_filter,
+      build_start_cb,
       transform_cb: transform_tsfn,
       resolve_id_cb,
+      load_cb,
+      build_end_cb,
+      render_chunk_cb,
+      generate_bundle_cb,
+      close_bundle_cb,
     })
   }
 
   pub fn new_boxed(option: BuildPluginOption) -> napi::Result<Box<dyn BuildPlugin>> {
     Ok(Box::new(Self::new(option)?))
   }
+
+  fn to_transformed_source(
+    &self,
+    ret: TransformResult,
+    hook: &str,
+  ) -> rolldown_error::Result<TransformedSource> {
+    let map = ret
+      .map
+      .map(|map| SourceMap::from_slice(map.as_bytes()))
+      .transpose()
+      .map_err(|err| {
+        BuildError::panic(format!(
+          "Invalid source map returned by the {hook} hook of plugin {}: {}",
+          self.name, err
+        ))
+      })?;
+    Ok(TransformedSource {
+      code: ret.code,
+      map,
+    })
+  }
 }
 
 #[async_trait::async_trait]
@@ -49,16 +165,47 @@ impl BuildPlugin for JsBuildPlugin {
     std::borrow::Cow::Borrowed(&self.name)
   }
 
+  fn order(&self) -> PluginOrder {
+    self.order
+  }
+
+  // Checking filters in Rust saves a round-trip to JavaScript for every module that the plugin doesn't care about.
+  fn filter(&self, hook: HookKind) -> Option<&HookFilter> {
+    match hook {
+      HookKind::Resolve => self.resolve_id_filter.as_ref(),
+      HookKind::Load => self.load_filter.as_ref(),
+      HookKind::Transform => self.transform_filter.as_ref(),
+      _ => None,
+    }
+  }
+
+  async fn build_start(
+    &self,
+    ctx: &mut rolldown_plugin::PluginContext,
+    _options: &rolldown_plugin::InputOptions,
+  ) -> HookNoopReturn {
+    if let Some(cb) = &self.build_start_cb {
+      cb.call_async((ctx.into(),))
+        .await
+        .map_err(|e| e.into_bundle_error())?;
+    }
+    Ok(())
+  }
+
   async fn transform(
     &self,
-    _ctx: &mut rolldown_plugin::Context,
+    ctx: &mut rolldown_plugin::PluginContext,
     args: &mut rolldown_plugin::TransformArgs,
   ) -> rolldown_plugin::TransformOutput {
     if let Some(cb) = &self.transform_cb {
-      let res = cb
-        .call_async((args.code.to_string(), args.id.to_string()))
-        .await;
-      res.map_err(|e| e.into_bundle_error())
+      let cb_ret = cb
+        .call_async((ctx.into(), args.code.to_string(), args.id.to_string()))
+        .await
+        .map_err(|e| e.into_bundle_error())?;
+
+      cb_ret
+        .map(|cb_ret| self.to_transformed_source(cb_ret, "transform"))
+        .transpose()
     } else {
       Ok(None)
     }
@@ -66,12 +213,13 @@ impl BuildPlugin for JsBuildPlugin {
 
   async fn resolve(
     &self,
-    _ctx: &mut rolldown_plugin::Context,
+    ctx: &mut rolldown_plugin::PluginContext,
     args: &mut rolldown_plugin::ResolveArgs,
   ) -> rolldown_plugin::ResolveOutput {
     if let Some(cb) = &self.resolve_id_cb {
       let cb_ret = cb
         .call_async((
+          ctx.into(),
           args.specifier.to_string(),
           args.importer.map(|s| s.to_string()),
         ))
@@ -86,4 +234,93 @@ impl BuildPlugin for JsBuildPlugin {
       Ok(None)
     }
   }
+
+  async fn load(
+    &self,
+    ctx: &mut rolldown_plugin::PluginContext,
+    args: &mut rolldown_plugin::LoadArgs,
+  ) -> rolldown_plugin::LoadOutput {
+    if let Some(cb) = &self.load_cb {
+      let cb_ret = cb
+        .call_async((ctx.into(), args.id.to_string()))
+        .await
+        .map_err(|e| e.into_bundle_error())?;
+
+      cb_ret
+        .map(|cb_ret| {
+          let loader = cb_ret
+            .loader
+            .map(|loader| rolldown_plugin::Loader::from_str(&loader))
+            .transpose()
+            .map_err(BuildError::panic)?;
+          Ok(LoadedSource {
+            code: cb_ret.code,
+            loader,
+          })
+        })
+        .transpose()
+    } else {
+      Ok(None)
+    }
+  }
+
+  async fn build_end(
+    &self,
+    ctx: &mut rolldown_plugin::PluginContext,
+    errors: Option<&Errors>,
+  ) -> HookNoopReturn {
+    if let Some(cb) = &self.build_end_cb {
+      let error = errors.map(|errors| errors.iter().map(|err| err.to_string()).join("\n"));
+      cb.call_async((ctx.into(), error))
+        .await
+        .map_err(|e| e.into_bundle_error())?;
+    }
+    Ok(())
+  }
+
+  async fn render_chunk(
+    &self,
+    ctx: &mut rolldown_plugin::PluginContext,
+    args: &mut rolldown_plugin::RenderChunkArgs,
+  ) -> rolldown_plugin::RenderChunkOutput {
+    if let Some(cb) = &self.render_chunk_cb {
+      let cb_ret = cb
+        .call_async((ctx.into(), args.code.to_string(), args.chunk.into()))
+        .await
+        .map_err(|e| e.into_bundle_error())?;
+
+      cb_ret
+        .map(|cb_ret| self.to_transformed_source(cb_ret, "renderChunk"))
+        .transpose()
+    } else {
+      Ok(None)
+    }
+  }
+
+  async fn generate_bundle(
+    &self,
+    ctx: &mut rolldown_plugin::PluginContext,
+    bundle: &mut rolldown_plugin::OutputBundle,
+  ) -> HookNoopReturn {
+    if let Some(cb) = &self.generate_bundle_cb {
+      let outputs = bundle.iter().map(OutputChunk::from).collect();
+      let outputs = cb
+        .call_async((ctx.into(), outputs))
+        .await
+        .map_err(|e| e.into_bundle_error())?;
+      if let Some(outputs) = outputs {
+        *bundle = outputs.into_iter().map(Into::into).collect();
+      }
+    }
+    Ok(())
+  }
+
+  async fn close_bundle(&self, ctx: &mut rolldown_plugin::PluginContext) -> HookNoopReturn {
+    if let Some(cb) = &self.close_bundle_cb {
+      cb.call_async((ctx.into(),))
+        .await
+        .map_err(|e| e.into_bundle_error())?;
+    }
+    Ok(())
+  }
 }
@@ -1,7 +1,25 @@
-use crate::{options::ResolveIdResult, utils::JsCallback};
+use crate::{
+  chunk_info::ChunkInfo,
+  options::{LoadResult, ResolveIdResult, TransformResult},
+  output_chunk::OutputChunk,
+  plugin_context::PluginContext,
+  utils::JsCallback,
+};
 
 pub type IsExternalCallback = JsCallback<(String, Option<String>, bool), bool>;
+pub type InteropCallback = JsCallback<(Option<String>,), String>;
 
 // Build hooks
-pub type ResolveIdCallback = JsCallback<(String, Option<String>), Option<ResolveIdResult>>;
-pub type TransformCallback = JsCallback<(String, String), Option<String>>;
+pub type BuildStartCallback = JsCallback<(PluginContext,), ()>;
+pub type ResolveIdCallback =
+  JsCallback<(PluginContext, String, Option<String>), Option<ResolveIdResult>>;
+pub type LoadCallback = JsCallback<(PluginContext, String), Option<LoadResult>>;
+pub type TransformCallback = JsCallback<(PluginContext, String, String), Option<TransformResult>>;
+pub type BuildEndCallback = JsCallback<(PluginContext, Option<String>), ()>;
+
+// Output generation hooks
+pub type RenderChunkCallback =
+  JsCallback<(PluginContext, String, ChunkInfo), Option<TransformResult>>;
+pub type GenerateBundleCallback =
+  JsCallback<(PluginContext, Vec<OutputChunk>), Option<Vec<OutputChunk>>>;
+pub type CloseBundleCallback = JsCallback<(PluginContext,), ()>;
@@ -10,10 +10,12 @@
 static ALLOC: mimalloc_rust::GlobalMiMalloc = mimalloc_rust::GlobalMiMalloc;
 
 pub mod bundler;
+pub mod chunk_info;
 pub mod js_build_plugin;
 pub mod js_callbacks;
 pub mod options;
 pub mod output_chunk;
+pub mod plugin_context;
 pub mod utils;
 
 scoped_tls::scoped_thread_local!(static NAPI_ENV: napi::Env);
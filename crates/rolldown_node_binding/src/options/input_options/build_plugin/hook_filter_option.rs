@@ -0,0 +1,54 @@
+use derivative::Derivative;
+use rolldown_plugin::{FilterPattern, HookFilter};
+use serde::Deserialize;
+
+/// Exactly one of `glob` and `regex` should be provided.
+#[napi_derive::napi(object)]
+#[derive(Deserialize, Default, Derivative)]
+#[serde(rename_all = "camelCase")]
+#[derivative(Debug)]
+pub struct FilterPatternOption {
+  pub glob: Option<String>,
+  /// Uses the syntax of the `regex` crate, which is compatible with most JavaScript regular expressions.
+  pub regex: Option<String>,
+}
+
+#[napi_derive::napi(object)]
+#[derive(Deserialize, Default, Derivative)]
+#[serde(rename_all = "camelCase")]
+#[derivative(Debug)]
+pub struct HookFilterOption {
+  pub include: Option<Vec<FilterPatternOption>>,
+  pub exclude: Option<Vec<FilterPatternOption>>,
+}
+
+impl FilterPatternOption {
+  fn into_pattern(self) -> napi::Result<FilterPattern> {
+    let invalid_arg = |reason: String| napi::Error::new(napi::Status::InvalidArg, reason);
+    match (self.glob, self.regex) {
+      (Some(glob), None) => FilterPattern::glob(&glob)
+        .map_err(|err| invalid_arg(format!("Invalid glob pattern {:?}: {}", glob, err))),
+      (None, Some(regex)) => FilterPattern::regex(&regex)
+        .map_err(|err| invalid_arg(format!("Invalid regex pattern {:?}: {}", regex, err))),
+      _ => Err(invalid_arg(
+        "A filter pattern should have exactly one of `glob` and `regex`".to_string(),
+      )),
+    }
+  }
+}
+
+impl HookFilterOption {
+  pub fn into_hook_filter(self) -> napi::Result<HookFilter> {
+    let into_patterns = |patterns: Option<Vec<FilterPatternOption>>| {
+      patterns
+        .unwrap_or_default()
+        .into_iter()
+        .map(FilterPatternOption::into_pattern)
+        .collect::<napi::Result<Vec<_>>>()
+    };
+    Ok(HookFilter {
+      include: into_patterns(self.include)?,
+      exclude: into_patterns(self.exclude)?,
+    })
+  }
+}
@@ -0,0 +1,14 @@
+use derivative::Derivative;
+use serde::Deserialize;
+
+#[napi_derive::napi(object)]
+#[derive(Deserialize, Default, Derivative)]
+#[serde(rename_all = "camelCase")]
+#[derivative(Debug)]
+pub struct LoadResult {
+  pub code: String,
+  #[napi(
+    ts_type = "'js' | 'jsx' | 'ts' | 'tsx' | 'json' | 'text' | 'base64' | 'dataurl' | 'binary' | 'file' | 'empty'"
+  )]
+  pub loader: Option<String>,
+}
@@ -4,6 +4,12 @@ use serde::Deserialize;
 
 mod resolve_id_result;
 pub use resolve_id_result::*;
+mod load_result;
+pub use load_result::*;
+mod hook_filter_option;
+pub use hook_filter_option::*;
+mod transform_result;
+pub use transform_result::*;
 
 #[napi_derive::napi(object)]
 #[derive(Deserialize, Default, Derivative)]
@@ -12,15 +18,65 @@ pub use resolve_id_result::*;
 pub struct BuildPluginOption {
   pub name: String,
 
+  #[napi(ts_type = "'pre' | 'post'")]
+  pub enforce: Option<String>,
+
+  #[derivative(Debug = "ignore")]
+  #[serde(skip_deserializing)]
+  #[napi(ts_type = "(ctx: PluginContext) => Promise<void>")]
+  pub build_start: Option<JsFunction>,
+
   #[derivative(Debug = "ignore")]
   #[serde(skip_deserializing)]
-  #[napi(ts_type = "(id: string, code: string) => Promise<string | null | undefined>")]
+  #[napi(
+    ts_type = "(ctx: PluginContext, code: string, id: string) => Promise<TransformResult | null | undefined>"
+  )]
   pub transform: Option<JsFunction>,
 
+  /// Skip calling `transform` for modules that don't match the filter.
+  pub transform_filter: Option<HookFilterOption>,
+
   #[derivative(Debug = "ignore")]
   #[serde(skip_deserializing)]
   #[napi(
-    ts_type = "(specifier: string, importer?: string) => Promise<string | null | ResolveIdResult>"
+    ts_type = "(ctx: PluginContext, specifier: string, importer?: string) => Promise<null | ResolveIdResult>"
   )]
   pub resolve_id: Option<JsFunction>,
+
+  /// Skip calling `resolveId` for specifiers that don't match the filter.
+  pub resolve_id_filter: Option<HookFilterOption>,
+
+  #[derivative(Debug = "ignore")]
+  #[serde(skip_deserializing)]
+  #[napi(ts_type = "(ctx: PluginContext, id: string) => Promise<null | LoadResult>")]
+  pub load: Option<JsFunction>,
+
+  /// Skip calling `load` for modules that don't match the filter.
+  pub load_filter: Option<HookFilterOption>,
+
+  /// `error` is the message of errors if the build failed.
+  #[derivative(Debug = "ignore")]
+  #[serde(skip_deserializing)]
+  #[napi(ts_type = "(ctx: PluginContext, error?: string) => Promise<void>")]
+  pub build_end: Option<JsFunction>,
+
+  #[derivative(Debug = "ignore")]
+  #[serde(skip_deserializing)]
+  #[napi(
+    ts_type = "(ctx: PluginContext, code: string, chunk: ChunkInfo) => Promise<TransformResult | null | undefined>"
+  )]
+  pub render_chunk: Option<JsFunction>,
+
+  /// Return the new outputs to add, rename or delete outputs. `null` means the outputs are not changed.
+  #[derivative(Debug = "ignore")]
+  #[serde(skip_deserializing)]
+  #[napi(
+    ts_type = "(ctx: PluginContext, outputs: Array<OutputChunk>) => Promise<Array<OutputChunk> | null | undefined>"
+  )]
+  pub generate_bundle: Option<JsFunction>,
+
+  #[derivative(Debug = "ignore")]
+  #[serde(skip_deserializing)]
+  #[napi(ts_type = "(ctx: PluginContext) => Promise<void>")]
+  pub close_bundle: Option<JsFunction>,
 }
@@ -0,0 +1,12 @@
+use derivative::Derivative;
+use serde::Deserialize;
+
+#[napi_derive::napi(object)]
+#[derive(Deserialize, Default, Derivative)]
+#[serde(rename_all = "camelCase")]
+#[derivative(Debug)]
+pub struct TransformResult {
+  pub code: String,
+  /// Source map in JSON
+  pub map: Option<String>,
+}
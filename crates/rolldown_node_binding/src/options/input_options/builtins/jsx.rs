@@ -0,0 +1,15 @@
+use derivative::Derivative;
+use serde::Deserialize;
+
+#[napi_derive::napi(object)]
+#[derive(Deserialize, Default, Derivative)]
+#[serde(rename_all = "camelCase")]
+#[derivative(Debug)]
+pub struct JsxOptions {
+  #[napi(ts_type = "'classic' | 'automatic'")]
+  pub runtime: Option<String>,
+  pub pragma: Option<String>,
+  pub pragma_frag: Option<String>,
+  pub import_source: Option<String>,
+  pub development: Option<bool>,
+}
@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+
 use derivative::Derivative;
 use serde::Deserialize;
 
@@ -5,6 +7,8 @@ mod node_resolve;
 pub use node_resolve::*;
 mod tsconfig;
 pub use tsconfig::*;
+mod jsx;
+pub use jsx::*;
 
 #[napi_derive::napi(object)]
 #[derive(Deserialize, Default, Derivative)]
@@ -14,4 +18,10 @@ pub struct BuiltinsOptions {
   /// None means disable the behaviors
   pub node_resolve: Option<NodeResolveOptions>,
   pub tsconfig: Option<TsConfigOptions>,
+  pub jsx: Option<JsxOptions>,
+  /// Loaders by extensions, such as `{ '.glsl': 'text', '.png': 'file' }`
+  #[napi(
+    ts_type = "Record<string, 'js' | 'jsx' | 'ts' | 'tsx' | 'json' | 'text' | 'base64' | 'dataurl' | 'binary' | 'file' | 'empty'>"
+  )]
+  pub loaders: Option<HashMap<String, String>>,
 }
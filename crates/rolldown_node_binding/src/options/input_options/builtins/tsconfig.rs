@@ -6,5 +6,5 @@ use serde::Deserialize;
 #[serde(rename_all = "camelCase")]
 #[derivative(Debug)]
 pub struct TsConfigOptions {
-  pub use_define_for_class_fields: bool,
+  pub use_define_for_class_fields: Option<bool>,
 }
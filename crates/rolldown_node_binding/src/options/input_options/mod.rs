@@ -1,4 +1,4 @@
-use std::{collections::HashMap, path::PathBuf};
+use std::{collections::HashMap, path::PathBuf, str::FromStr};
 
 use napi_derive::*;
 use rolldown::default_warning_handler;
@@ -43,6 +43,7 @@ pub struct InputOptions {
   // preserveModules?: boolean;
   pub preserve_symlinks: bool,
   pub shim_missing_exports: bool,
+  pub strict_execution_order: bool,
   // strictDeprecations?: boolean;
   pub treeshake: Option<bool>,
   // watch?: WatcherOptions | false;
@@ -66,6 +67,31 @@ pub fn resolve_input_options(
 
   let is_external = resolve_external(opts.external)?;
 
+  let loaders = opts
+    .builtins
+    .loaders
+    .unwrap_or_default()
+    .into_iter()
+    .map(|(ext, loader)| {
+      let loader = rolldown::Loader::from_str(&loader)
+        .map_err(|err| napi::Error::new(napi::Status::InvalidArg, err))?;
+      Ok((ext.trim_start_matches('.').to_string(), loader))
+    })
+    .collect::<napi::Result<HashMap<_, _>>>()?;
+
+  let jsx = opts.builtins.jsx.unwrap_or_default();
+  let jsx = rolldown::JsxOptions {
+    runtime: jsx
+      .runtime
+      .map(|runtime| rolldown::JsxRuntime::from_str(&runtime))
+      .transpose()
+      .map_err(|err| napi::Error::new(napi::Status::InvalidArg, err))?,
+    pragma: jsx.pragma,
+    pragma_frag: jsx.pragma_frag,
+    import_source: jsx.import_source,
+    development: jsx.development,
+  };
+
   Ok((
     rolldown::InputOptions {
       input: opts
@@ -87,9 +113,12 @@ pub fn resolve_input_options(
         tsconfig: opts.builtins.tsconfig.map(|opts| rolldown::TsConfig {
           use_define_for_class_fields: opts.use_define_for_class_fields,
         }),
+        jsx,
+        loaders,
       },
       on_warn: default_warning_handler(),
       shim_missing_exports: opts.shim_missing_exports,
+      strict_execution_order: opts.strict_execution_order,
     },
     plugins,
   ))
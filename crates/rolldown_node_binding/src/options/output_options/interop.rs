@@ -0,0 +1,55 @@
+use std::{str::FromStr, sync::Arc};
+
+use derivative::Derivative;
+use futures::FutureExt;
+use napi::JsFunction;
+use rolldown_error::Error as BuildError;
+use serde::Deserialize;
+
+use crate::{js_callbacks::InteropCallback, utils::NapiErrorExt};
+
+/// Exactly one of `function` and `mode` should be provided.
+#[napi_derive::napi(object)]
+#[derive(Deserialize, Default, Derivative)]
+#[serde(rename_all = "camelCase")]
+#[derivative(Debug)]
+pub struct InteropOption {
+  #[serde(skip_deserializing)]
+  #[napi(ts_type = "(id: string | null) => string")]
+  #[derivative(Debug = "ignore")]
+  pub function: Option<JsFunction>,
+  #[napi(ts_type = "'auto' | 'esModule' | 'default' | 'defaultOnly' | 'compat'")]
+  pub mode: Option<String>,
+}
+
+pub fn resolve_interop(interop: InteropOption) -> napi::Result<rolldown::ResolveInterop> {
+  if let Some(function) = interop.function.as_ref() {
+    let interop_cb = InteropCallback::new(function)?;
+    return Ok(Arc::new(move |id| {
+      let interop_cb = interop_cb.clone();
+      let id = id.map(|id| id.to_string());
+      async move {
+        let mode = interop_cb
+          .call_async((id,))
+          .await
+          .map_err(|e| e.into_bundle_error())?;
+        rolldown::InteropMode::from_str(&mode).map_err(|_| {
+          BuildError::invalid_option(
+            "output.interop",
+            mode,
+            r#"the interop mode should be one of "auto", "esModule", "default", "defaultOnly" and "compat""#,
+          )
+        })
+      }
+      .boxed()
+    }));
+  }
+
+  let mode = interop
+    .mode
+    .map(|mode| rolldown::InteropMode::from_str(&mode))
+    .transpose()
+    .map_err(|err| napi::Error::new(napi::Status::InvalidArg, err))?
+    .unwrap_or(rolldown::InteropMode::Auto);
+  Ok(rolldown::interop_of_mode(mode))
+}
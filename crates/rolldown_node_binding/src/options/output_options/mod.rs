@@ -1,8 +1,19 @@
-use std::str::FromStr;
+use std::{collections::HashMap, str::FromStr};
 
+use napi::Either;
 use napi_derive::*;
-use rolldown::ModuleFormat;
+use rolldown::{ModuleFormat, SourceMapType};
 use serde::Deserialize;
+mod interop;
+pub use interop::*;
+
+#[napi(object)]
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct AmdOptions {
+  pub id: Option<String>,
+  pub define: Option<String>,
+}
 
 #[napi(object)]
 #[derive(Deserialize, Debug)]
@@ -13,9 +24,9 @@ pub struct OutputOptions {
   // dynamicImportFunction: string | undefined;
   pub entry_file_names: Option<String>,
   pub chunk_file_names: Option<String>,
+  pub asset_file_names: Option<String>,
 
-  // amd: NormalizedAmdOptions;
-  // assetFileNames: string | ((chunkInfo: PreRenderedAsset) => string);
+  pub amd: Option<AmdOptions>,
   // banner: () => string | Promise<string>;
   // chunkFileNames: string | ((chunkInfo: PreRenderedChunk) => string);
   // compact: boolean;
@@ -27,19 +38,19 @@ pub struct OutputOptions {
   // extend: boolean;
   // externalLiveBindings: boolean;
   // footer: () => string | Promise<string>;
-  #[napi(ts_type = "'esm' | 'cjs'")]
+  #[napi(ts_type = "'esm' | 'cjs' | 'iife' | 'amd' | 'umd' | 'system'")]
   pub format: Option<String>,
   // freeze: boolean;
   // generatedCode: NormalizedGeneratedCodeOptions;
-  // globals: GlobalsOption;
+  pub globals: Option<HashMap<String, String>>,
   // hoistTransitiveImports: boolean;
   // indent: true | string;
   // inlineDynamicImports: boolean;
-  // interop: GetInterop;
+  pub interop: Option<InteropOption>,
   // intro: () => string | Promise<string>;
   // manualChunks: ManualChunksOption;
   // minifyInternalExports: boolean;
-  // name: string | undefined;
+  pub name: Option<String>,
   // namespaceToStringTag: boolean;
   // noConflict: boolean;
   // outro: () => string | Promise<string>;
@@ -49,8 +60,10 @@ pub struct OutputOptions {
   // preserveModules: boolean;
   // preserveModulesRoot: string | undefined;
   // sanitizeFileName: (fileName: string) => string;
-  // sourcemap: boolean | 'inline' | 'hidden';
-  // sourcemapExcludeSources: boolean;
+  #[serde(skip_deserializing)]
+  #[napi(ts_type = "boolean | 'inline' | 'hidden'")]
+  pub sourcemap: Option<Either<bool, String>>,
+  pub sourcemap_exclude_sources: Option<bool>,
   // sourcemapFile: string | undefined;
   // sourcemapPathTransform: SourcemapPathTransformOption | undefined;
   // strict: boolean;
@@ -70,6 +83,9 @@ pub fn resolve_output_options(opts: OutputOptions) -> napi::Result<rolldown::Out
   if let Some(chunk_file_names) = opts.chunk_file_names {
     defaults.chunk_file_names = chunk_file_names.into()
   }
+  if let Some(asset_file_names) = opts.asset_file_names {
+    defaults.asset_file_names = asset_file_names.into()
+  }
   if let Some(format) = opts.format {
     defaults.format = ModuleFormat::from_str(format.as_str()).map_err(|err| {
       napi::Error::new(
@@ -79,7 +95,41 @@ pub fn resolve_output_options(opts: OutputOptions) -> napi::Result<rolldown::Out
     })?;
   }
 
+  if let Some(sourcemap) = opts.sourcemap {
+    defaults.sourcemap = match sourcemap {
+      Either::A(true) => Some(SourceMapType::File),
+      Either::A(false) => None,
+      Either::B(value) if value == "inline" => Some(SourceMapType::Inline),
+      Either::B(value) if value == "hidden" => Some(SourceMapType::Hidden),
+      Either::B(value) => {
+        return Err(napi::Error::new(
+          napi::Status::InvalidArg,
+          format!("Invalid sourcemap option {}", value),
+        ))
+      }
+    };
+  }
+  if let Some(sourcemap_exclude_sources) = opts.sourcemap_exclude_sources {
+    defaults.sourcemap_exclude_sources = sourcemap_exclude_sources;
+  }
+
+  if let Some(amd) = opts.amd {
+    defaults.amd.id = amd.id;
+    if let Some(define) = amd.define {
+      defaults.amd.define = define;
+    }
+  }
+
+  if let Some(interop) = opts.interop {
+    defaults.interop = resolve_interop(interop)?;
+  }
+
+  if let Some(globals) = opts.globals {
+    defaults.globals = globals;
+  }
+
   defaults.dir = opts.dir;
+  defaults.name = opts.name;
 
   Ok(defaults)
 }
@@ -1,4 +1,6 @@
 use derivative::Derivative;
+use napi::bindgen_prelude::Buffer;
+use rolldown::{Asset, AssetContent};
 use serde::Deserialize;
 
 #[napi_derive::napi(object)]
@@ -6,6 +8,37 @@ use serde::Deserialize;
 #[serde(rename_all = "camelCase")]
 #[derivative(Debug)]
 pub struct OutputChunk {
+  /// Empty for binary assets, whose content is in `source`.
   pub code: String,
   pub file_name: String,
+  /// Content of binary assets emitted by plugins, which might not be valid UTF-8.
+  #[serde(skip)]
+  #[derivative(Debug = "ignore")]
+  pub source: Option<Buffer>,
+}
+
+impl From<&Asset> for OutputChunk {
+  fn from(asset: &Asset) -> Self {
+    let (code, source) = match &asset.content {
+      AssetContent::Code(code) => (code.clone(), None),
+      AssetContent::Binary(bytes) => (String::new(), Some(bytes.clone().into())),
+    };
+    Self {
+      code,
+      file_name: asset.filename.clone(),
+      source,
+    }
+  }
+}
+
+impl From<OutputChunk> for Asset {
+  fn from(chunk: OutputChunk) -> Self {
+    Self {
+      content: match chunk.source {
+        Some(source) => source.to_vec().into(),
+        None => chunk.code.into(),
+      },
+      filename: chunk.file_name,
+    }
+  }
 }
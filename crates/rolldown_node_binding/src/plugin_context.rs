@@ -0,0 +1,90 @@
+use derivative::Derivative;
+use napi_derive::napi;
+use rolldown_error::Error as BuildError;
+use rolldown_plugin::ModuleId;
+use serde::Deserialize;
+
+use crate::options::ResolveIdResult;
+
+#[napi_derive::napi(object)]
+#[derive(Deserialize, Default, Derivative)]
+#[serde(rename_all = "camelCase")]
+#[derivative(Debug)]
+pub struct ModuleInfo {
+  pub id: String,
+  pub is_entry: bool,
+  pub is_external: bool,
+  pub imported_ids: Vec<String>,
+  pub dynamically_imported_ids: Vec<String>,
+}
+
+impl From<rolldown_plugin::ModuleInfo> for ModuleInfo {
+  fn from(info: rolldown_plugin::ModuleInfo) -> Self {
+    Self {
+      id: info.id.to_string(),
+      is_entry: info.is_entry,
+      is_external: info.is_external,
+      imported_ids: info.imported_ids.iter().map(|id| id.to_string()).collect(),
+      dynamically_imported_ids: info
+        .dynamically_imported_ids
+        .iter()
+        .map(|id| id.to_string())
+        .collect(),
+    }
+  }
+}
+
+/// The context passed to hooks of JS plugins as the first argument. It's `this` of rollup plugins.
+#[napi]
+#[derive(Debug)]
+pub struct PluginContext {
+  inner: rolldown_plugin::PluginContext,
+}
+
+impl From<&rolldown_plugin::PluginContext> for PluginContext {
+  fn from(ctx: &rolldown_plugin::PluginContext) -> Self {
+    Self { inner: ctx.clone() }
+  }
+}
+
+#[napi]
+impl PluginContext {
+  #[napi]
+  pub fn warn(&self, message: String) {
+    self.inner.warn(BuildError::panic(message))
+  }
+
+  #[napi]
+  pub async fn resolve(
+    &self,
+    specifier: String,
+    importer: Option<String>,
+    skip_self: bool,
+  ) -> napi::Result<Option<ResolveIdResult>> {
+    let importer = importer.map(|id| ModuleId::new(id, false));
+    let resolved = self
+      .inner
+      .resolve(&specifier, importer.as_ref(), skip_self)
+      .await
+      .map_err(|err| napi::Error::from_reason(err.to_string()))?;
+    Ok(resolved.map(|resolved| ResolveIdResult {
+      id: resolved.id,
+      external: resolved.external,
+    }))
+  }
+
+  #[napi]
+  pub fn get_module_info(&self, id: String) -> Option<ModuleInfo> {
+    // Whether the module is external is unknown from the id
+    self
+      .inner
+      .get_module_info(&ModuleId::new(id.clone(), false))
+      .or_else(|| self.inner.get_module_info(&ModuleId::new(id, true)))
+      .map(Into::into)
+  }
+
+  #[napi]
+  pub fn add_watch_file(&self, file: String) {
+    self.inner.add_watch_file(file)
+  }
+}
@@ -1,4 +1,16 @@
-use rolldown_common::ModuleId;
+use std::path::Path;
+
+use rolldown_common::{InputItem, ModuleFormat, ModuleId};
+
+/// A read-only view of the input options, which is passed to `build_start`.
+#[derive(Debug, Clone)]
+pub struct InputOptions<'a> {
+  pub input: &'a [InputItem],
+  pub cwd: &'a Path,
+  pub treeshake: bool,
+  pub shim_missing_exports: bool,
+  pub strict_execution_order: bool,
+}
 
 #[derive(Debug, Clone)]
 pub struct ResolveArgs<'a> {
@@ -6,7 +18,59 @@ pub struct ResolveArgs<'a> {
   pub specifier: &'a str,
 }
 
+#[derive(Debug, Clone, Copy)]
+pub enum DynamicImportSpecifier<'a> {
+  /// `import('./foo.js')`
+  Literal(&'a str),
+  /// Source code of the non-literal argument. For `` import(`./locales/${lang}.js`) ``,
+  /// it's `` `./locales/${lang}.js` ``.
+  Expression(&'a str),
+}
+
+#[derive(Debug, Clone)]
+pub struct ResolveDynamicImportArgs<'a> {
+  pub importer: &'a ModuleId,
+  pub specifier: DynamicImportSpecifier<'a>,
+}
+
+#[derive(Debug, Clone)]
+pub struct LoadArgs<'a> {
+  pub id: &'a ModuleId,
+}
+
 pub struct TransformArgs<'a> {
   pub id: &'a ModuleId,
   pub code: &'a String,
 }
+
+#[derive(Debug, Clone)]
+pub struct ChunkInfo {
+  /// The name of the chunk, which is used to render `[name]` in the filename template.
+  pub name: String,
+  pub filename: String,
+  /// Whether the chunk is created by a user-defined entry.
+  pub is_entry: bool,
+  pub is_dynamic_entry: bool,
+  /// The entry module of an entry chunk or a dynamic entry chunk. `None` for shared chunks.
+  pub facade_module_id: Option<ModuleId>,
+  /// Ids of modules in the chunk, sorted by the execution order.
+  pub module_ids: Vec<ModuleId>,
+  /// Sorted names exported by the chunk. Names re-exported by `export * from 'external'` are unknown and not included.
+  pub exports: Vec<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct RenderDynamicImportArgs<'a> {
+  pub importer: &'a ModuleId,
+  /// The specifier of the `import()` in the source code.
+  pub specifier: &'a str,
+  pub resolved_id: &'a ModuleId,
+  /// The relative path to the chunk of `resolved_id`, such as `./foo.js`. `None` for external modules.
+  pub target_filename: Option<&'a str>,
+  pub format: ModuleFormat,
+}
+
+pub struct RenderChunkArgs<'a> {
+  pub code: &'a String,
+  pub chunk: &'a ChunkInfo,
+}
@@ -1,16 +1,84 @@
-#[derive(Debug, Default)]
-pub struct Context<Ctx = ()> {
-  pub context: Ctx,
+use std::{fmt::Debug, path::PathBuf, sync::Arc};
+
+use rolldown_common::ModuleId;
+use rolldown_error::Error as BuildError;
+
+use crate::{EmittedFile, ModuleInfo, ResolveOutput};
+
+/// Abilities that the bundler exposes to plugins. It's implemented by `rolldown_core`.
+#[async_trait::async_trait]
+pub trait PluginContextBridge: Debug + Send + Sync {
+  fn warn(&self, warning: BuildError);
+
+  /// Resolve `specifier` through the plugins and the builtin resolver.
+  /// The plugin at `skipped_plugin` will not be called.
+  async fn resolve(
+    &self,
+    specifier: &str,
+    importer: Option<&ModuleId>,
+    skipped_plugin: Option<usize>,
+  ) -> ResolveOutput;
+
+  fn get_module_info(&self, id: &ModuleId) -> Option<ModuleInfo>;
+
+  fn add_watch_file(&self, file: PathBuf);
+
+  fn emit_file(&self, file: EmittedFile) -> rolldown_error::Result<()>;
 }
 
-impl Context {
-  pub fn new() -> Self {
-    Self::with_context(())
-  }
+#[derive(Debug, Clone)]
+pub struct PluginContext {
+  plugin_index: usize,
+  plugin_name: String,
+  bridge: Arc<dyn PluginContextBridge>,
 }
 
-impl<T> Context<T> {
-  pub fn with_context(context: T) -> Self {
-    Self { context }
+impl PluginContext {
+  pub fn new(
+    plugin_index: usize,
+    plugin_name: impl Into<String>,
+    bridge: Arc<dyn PluginContextBridge>,
+  ) -> Self {
+    Self {
+      plugin_index,
+      plugin_name: plugin_name.into(),
+      bridge,
+    }
+  }
+
+  /// Emit a warning via `on_warn` of the input options. The warning is tagged with the name of the plugin.
+  pub fn warn(&self, warning: BuildError) {
+    self
+      .bridge
+      .warn(warning.context(format!("plugin: {}", self.plugin_name)))
+  }
+
+  /// Resolve `specifier` in the same way rolldown resolves imports.
+  /// If `skip_self` is `true`, the `resolve` hook of current plugin will not be called.
+  pub async fn resolve(
+    &self,
+    specifier: &str,
+    importer: Option<&ModuleId>,
+    skip_self: bool,
+  ) -> ResolveOutput {
+    self
+      .bridge
+      .resolve(specifier, importer, skip_self.then_some(self.plugin_index))
+      .await
+  }
+
+  /// Return `None` if the module is not loaded yet.
+  pub fn get_module_info(&self, id: &ModuleId) -> Option<ModuleInfo> {
+    self.bridge.get_module_info(id)
+  }
+
+  pub fn add_watch_file(&self, file: impl Into<PathBuf>) {
+    self.bridge.add_watch_file(file.into())
+  }
+
+  /// Add an extra entry chunk or an asset to the output.
+  /// Chunks can only be emitted before the module graph is generated, such as in `build_start`, `resolve` and `load`.
+  pub fn emit_file(&self, file: EmittedFile) -> rolldown_error::Result<()> {
+    self.bridge.emit_file(file)
   }
 }
@@ -0,0 +1,9 @@
+#[derive(Debug, Clone)]
+pub enum EmittedFile {
+  /// An extra entry chunk. `id` is resolved in the same way as the `input` option.
+  /// The name of the chunk is derived from the resolved id if `name` is `None`.
+  Chunk { id: String, name: Option<String> },
+  /// A raw file, such as an image or a `.wasm` file. The filename is rendered by `asset_file_names`.
+  /// Assets emitted in `generate_bundle` are added to the output after all plugins are called.
+  Asset { name: String, source: Vec<u8> },
+}
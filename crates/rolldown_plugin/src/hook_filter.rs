@@ -0,0 +1,52 @@
+use std::fmt::Debug;
+
+/// A pattern to match ids against. Globs are matched against the whole id.
+#[derive(Clone)]
+pub enum FilterPattern {
+  Glob(glob::Pattern),
+  Regex(regex::Regex),
+}
+
+impl FilterPattern {
+  pub fn glob(pattern: &str) -> Result<Self, glob::PatternError> {
+    glob::Pattern::new(pattern).map(Self::Glob)
+  }
+
+  pub fn regex(pattern: &str) -> Result<Self, regex::Error> {
+    regex::Regex::new(pattern).map(Self::Regex)
+  }
+
+  pub fn matches(&self, id: &str) -> bool {
+    match self {
+      FilterPattern::Glob(pattern) => pattern.matches(id),
+      FilterPattern::Regex(regex) => regex.is_match(id),
+    }
+  }
+}
+
+impl Debug for FilterPattern {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match self {
+      Self::Glob(pattern) => write!(f, "Glob({})", pattern.as_str()),
+      Self::Regex(regex) => write!(f, "Regex({})", regex.as_str()),
+    }
+  }
+}
+
+/// Decides whether a hook of a plugin should be called for an id.
+///
+/// An id is matched if it matches none of `exclude` and, when `include` is not empty, any of `include`.
+#[derive(Debug, Default, Clone)]
+pub struct HookFilter {
+  pub include: Vec<FilterPattern>,
+  pub exclude: Vec<FilterPattern>,
+}
+
+impl HookFilter {
+  pub fn matches(&self, id: &str) -> bool {
+    if self.exclude.iter().any(|pattern| pattern.matches(id)) {
+      return false;
+    }
+    self.include.is_empty() || self.include.iter().any(|pattern| pattern.matches(id))
+  }
+}
@@ -3,5 +3,12 @@ pub use plugin::*;
 mod args;
 pub use args::*;
 mod context;
+mod emitted_file;
+mod hook_filter;
+mod module_info;
 pub use async_trait;
 pub use context::*;
+pub use emitted_file::*;
+pub use hook_filter::*;
+pub use module_info::*;
+pub use rolldown_common::{Loader, ModuleId, SourceMap};
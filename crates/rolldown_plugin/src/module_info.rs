@@ -0,0 +1,11 @@
+use rolldown_common::ModuleId;
+
+#[derive(Debug, Clone)]
+pub struct ModuleInfo {
+  pub id: ModuleId,
+  pub is_entry: bool,
+  pub is_external: bool,
+  /// Resolved ids of static imports and re-exports.
+  pub imported_ids: Vec<ModuleId>,
+  pub dynamically_imported_ids: Vec<ModuleId>,
+}
@@ -1,6 +1,12 @@
 use std::{borrow::Cow, fmt::Debug};
 
-use crate::{Context, ResolveArgs, TransformArgs};
+use rolldown_common::{Asset, Loader, SourceMap};
+use rolldown_error::Errors;
+
+use crate::{
+  HookFilter, InputOptions, LoadArgs, PluginContext, RenderChunkArgs, RenderDynamicImportArgs,
+  ResolveArgs, ResolveDynamicImportArgs, TransformArgs,
+};
 
 #[derive(Debug)]
 pub struct ResolvedId {
@@ -8,19 +14,174 @@ pub struct ResolvedId {
   pub external: bool,
 }
 
+#[derive(Debug)]
+pub struct LoadedSource {
+  pub code: String,
+  /// `None` means the loader is decided by rolldown, such as detecting by the extension of the id.
+  pub loader: Option<Loader>,
+}
+
+#[derive(Debug)]
+pub struct TransformedSource {
+  pub code: String,
+  /// Maps `code` to the code passed to the hook. `None` means the transformation doesn't preserve positions.
+  pub map: Option<SourceMap>,
+}
+
+impl From<String> for TransformedSource {
+  fn from(code: String) -> Self {
+    Self { code, map: None }
+  }
+}
+
+pub type HookNoopReturn = rolldown_error::Result<()>;
 pub type ResolveOutput = rolldown_error::Result<Option<ResolvedId>>;
-pub type TransformOutput = rolldown_error::Result<Option<String>>;
+pub type LoadOutput = rolldown_error::Result<Option<LoadedSource>>;
+pub type TransformOutput = rolldown_error::Result<Option<TransformedSource>>;
+pub type RenderChunkOutput = rolldown_error::Result<Option<TransformedSource>>;
+pub type RenderDynamicImportOutput = rolldown_error::Result<Option<String>>;
+pub type OutputBundle = Vec<Asset>;
 pub type PluginName<'a> = Cow<'a, str>;
 
+/// Plugins are sorted by their order. Plugins with the same order keep the order they are passed in.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum PluginOrder {
+  Pre,
+  #[default]
+  Normal,
+  Post,
+}
+
+impl PluginOrder {
+  /// Accepts `enforce` values of vite-style plugins.
+  pub fn from_enforce(enforce: Option<&str>) -> Option<Self> {
+    match enforce {
+      None => Some(PluginOrder::Normal),
+      Some("pre") => Some(PluginOrder::Pre),
+      Some("post") => Some(PluginOrder::Post),
+      Some(_) => None,
+    }
+  }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HookKind {
+  BuildStart,
+  Resolve,
+  ResolveDynamicImport,
+  Load,
+  Transform,
+  BuildEnd,
+  RenderChunk,
+  RenderDynamicImport,
+  GenerateBundle,
+  CloseBundle,
+}
+
+/// How plugins are called for a hook.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HookMode {
+  /// Plugins are called one by one. The output of a plugin is the input of the next one.
+  Sequential,
+  /// Plugins are called one by one until one of them returns something.
+  First,
+  /// Plugins are called concurrently. The hook fails if any of them fails.
+  Parallel,
+}
+
+impl HookKind {
+  pub fn mode(self) -> HookMode {
+    match self {
+      HookKind::Resolve
+      | HookKind::ResolveDynamicImport
+      | HookKind::Load
+      | HookKind::RenderDynamicImport => HookMode::First,
+      HookKind::Transform | HookKind::RenderChunk | HookKind::GenerateBundle => {
+        HookMode::Sequential
+      }
+      HookKind::BuildStart | HookKind::BuildEnd | HookKind::CloseBundle => HookMode::Parallel,
+    }
+  }
+}
+
 #[async_trait::async_trait]
 pub trait BuildPlugin: Debug + Send + Sync {
   fn name(&self) -> PluginName;
 
-  async fn resolve(&self, _ctx: &mut Context, _args: &mut ResolveArgs) -> ResolveOutput {
+  fn order(&self) -> PluginOrder {
+    PluginOrder::Normal
+  }
+
+  /// Plugins are not called for ids that don't match the filter of a hook. It's checked for `resolve`, `load` and `transform`.
+  /// For `resolve`, the specifier is matched instead of the id.
+  fn filter(&self, _hook: HookKind) -> Option<&HookFilter> {
+    None
+  }
+
+  async fn build_start(&self, _ctx: &mut PluginContext, _options: &InputOptions) -> HookNoopReturn {
+    Ok(())
+  }
+
+  async fn resolve(&self, _ctx: &mut PluginContext, _args: &mut ResolveArgs) -> ResolveOutput {
+    Ok(None)
+  }
+
+  /// Resolve `import()`. Non-literal arguments are only resolved by this hook.
+  /// Returning `None` falls back to `resolve` for literal arguments and keeps non-literal ones as they are.
+  async fn resolve_dynamic_import(
+    &self,
+    _ctx: &mut PluginContext,
+    _args: &mut ResolveDynamicImportArgs,
+  ) -> ResolveOutput {
+    Ok(None)
+  }
+
+  async fn load(&self, _ctx: &mut PluginContext, _args: &mut LoadArgs) -> LoadOutput {
     Ok(None)
   }
 
-  async fn transform(&self, _ctx: &mut Context, _args: &mut TransformArgs) -> TransformOutput {
+  async fn transform(
+    &self,
+    _ctx: &mut PluginContext,
+    _args: &mut TransformArgs,
+  ) -> TransformOutput {
     Ok(None)
   }
+
+  /// `errors` is `Some` if the build failed.
+  async fn build_end(&self, _ctx: &mut PluginContext, _errors: Option<&Errors>) -> HookNoopReturn {
+    Ok(())
+  }
+
+  /// Return the new code of the chunk. The returned source map is collapsed onto the map of the chunk.
+  async fn render_chunk(
+    &self,
+    _ctx: &mut PluginContext,
+    _args: &mut RenderChunkArgs,
+  ) -> RenderChunkOutput {
+    Ok(None)
+  }
+
+  /// Return the code of an expression to replace the `import()`.
+  async fn render_dynamic_import(
+    &self,
+    _ctx: &mut PluginContext,
+    _args: &mut RenderDynamicImportArgs,
+  ) -> RenderDynamicImportOutput {
+    Ok(None)
+  }
+
+  /// Called before the outputs are written. Plugins could add, rename or delete outputs in `bundle`.
+  async fn generate_bundle(
+    &self,
+    _ctx: &mut PluginContext,
+    _bundle: &mut OutputBundle,
+  ) -> HookNoopReturn {
+    Ok(())
+  }
+
+  /// Called after the bundle is written or generated. Plugins should release resources here.
+  async fn close_bundle(&self, _ctx: &mut PluginContext) -> HookNoopReturn {
+    Ok(())
+  }
 }
@@ -1,7 +1,10 @@
 use std::path::{Path, PathBuf};
 
 use nodejs_resolver::{ResolveResult, Resolver};
-use rolldown_plugin::{async_trait, BuildPlugin, Context, ResolveArgs, ResolveOutput, ResolvedId};
+use rolldown_common::{is_virtual_id, split_query};
+use rolldown_plugin::{
+  async_trait, BuildPlugin, PluginContext, PluginOrder, ResolveArgs, ResolveOutput, ResolvedId,
+};
 
 #[derive(Debug)]
 pub struct NodeResolvePlugin {
@@ -24,12 +27,22 @@ impl BuildPlugin for NodeResolvePlugin {
     std::borrow::Cow::Borrowed("builtin:node-resolve")
   }
 
-  async fn resolve(&self, _ctx: &mut Context, args: &mut ResolveArgs) -> ResolveOutput {
+  // Plugins like alias should have the chance to rewrite specifiers before they are resolved from `node_modules`.
+  fn order(&self) -> PluginOrder {
+    PluginOrder::Post
+  }
+
+  async fn resolve(&self, _ctx: &mut PluginContext, args: &mut ResolveArgs) -> ResolveOutput {
+    if is_virtual_id(args.specifier) {
+      return Ok(None);
+    }
+    let (specifier, query) = split_query(args.specifier);
     let importer = args
       .importer
-      .map(|importer| Path::new(importer.as_ref()).parent().unwrap())
+      .filter(|importer| !importer.is_virtual())
+      .map(|importer| Path::new(importer.path_without_query()).parent().unwrap())
       .unwrap_or_else(|| Path::new(&self.cwd));
-    let s = self.resolver.resolve(importer, args.specifier);
+    let s = self.resolver.resolve(importer, specifier);
     if s.is_err() {
       // println!("{args:#?}");
       // println!(
@@ -46,7 +59,7 @@ impl BuildPlugin for NodeResolvePlugin {
     let s = s.unwrap();
     match s {
       ResolveResult::Info(info) => Ok(Some(ResolvedId {
-        id: info.path().to_string_lossy().to_string(),
+        id: format!("{}{query}", info.path().to_string_lossy()),
         external: false,
       })),
       ResolveResult::Ignored => Ok(None),
@@ -1,5 +1,6 @@
 use std::path::PathBuf;
 
+use rolldown_common::{is_virtual_id, split_query};
 use sugar_path::{AsPath, SugarPathBuf};
 
 #[derive(Debug)]
@@ -27,6 +28,17 @@ impl Default for Resolver {
 
 impl Resolver {
   pub fn resolve(&self, importer: Option<&str>, specifier: &str) -> rolldown_error::Result<String> {
+    // Virtual ids are not paths. Keep them as they are.
+    if is_virtual_id(specifier) {
+      return Ok(specifier.to_string());
+    }
+
+    let (specifier, query) = split_query(specifier);
+    // Relative imports in virtual modules are resolved against the cwd.
+    let importer = importer
+      .filter(|importer| !is_virtual_id(importer))
+      .map(|importer| split_query(importer).0);
+
     let mut path = if specifier.as_path().is_absolute() {
       specifier.as_path().to_path_buf()
     } else if let Some(importer) = importer {
@@ -41,7 +53,8 @@ impl Resolver {
     };
 
     add_js_extension(&mut path);
-    let id = path.to_string_lossy().to_string();
+    let mut id = path.to_string_lossy().to_string();
+    id.push_str(query);
     Ok(id)
   }
 }
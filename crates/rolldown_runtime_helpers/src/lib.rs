@@ -82,6 +82,17 @@ impl RuntimeHelpers {
 
 define_helpers!(Helpers {
     merge_namespaces(_mergeNamespaces): (),
+    to_binary(__toBinary): (),
+    common_js(__commonJS): (),
+    to_esm(__toESM): (),
+    re_export(__reExport): (),
+    to_common_js(__toCommonJS): (),
+    esm(__esm): (),
+    interop_default(__interopDefault): (),
+    interop_default_compat(__interopDefaultCompat): (),
+    interop_namespace_compat(__interopNamespaceCompat): (),
+    interop_namespace_default(__interopNamespaceDefault): (),
+    interop_namespace_default_only(__interopNamespaceDefaultOnly): (),
 });
 
 #[test]
@@ -0,0 +1,5 @@
+function __esm(fn, res) {
+	return function () {
+		return fn && (res = fn(fn = 0)), res;
+	};
+}
\ No newline at end of file
@@ -0,0 +1,3 @@
+function __interopDefault(e) {
+	return e && e.__esModule ? e : { default: e };
+}
\ No newline at end of file
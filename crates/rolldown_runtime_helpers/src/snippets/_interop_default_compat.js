@@ -0,0 +1,3 @@
+function __interopDefaultCompat(e) {
+	return e && typeof e === 'object' && 'default' in e ? e : { default: e };
+}
\ No newline at end of file
@@ -0,0 +1,12 @@
+function __interopNamespaceDefault(e) {
+	var n = { __proto__: null, default: e };
+	e && (typeof e === 'object' || typeof e === 'function') && Object.keys(e).forEach(function (k) {
+		if (k !== 'default') {
+			Object.defineProperty(n, k, {
+				enumerable: true,
+				get: function () { return e[k]; }
+			});
+		}
+	});
+	return Object.freeze(n);
+}
\ No newline at end of file
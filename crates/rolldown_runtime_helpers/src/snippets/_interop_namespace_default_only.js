@@ -0,0 +1,3 @@
+function __interopNamespaceDefaultOnly(e) {
+	return Object.freeze({ __proto__: null, default: e });
+}
\ No newline at end of file
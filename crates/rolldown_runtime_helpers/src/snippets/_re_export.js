@@ -0,0 +1,11 @@
+function __reExport(target, mod) {
+	Object.keys(mod).forEach(function (k) {
+		if (k !== 'default' && !Object.prototype.hasOwnProperty.call(target, k)) {
+			Object.defineProperty(target, k, {
+				enumerable: true,
+				get: function () { return mod[k]; }
+			});
+		}
+	});
+	return target;
+}
\ No newline at end of file
@@ -0,0 +1,16 @@
+var __toBinary = /* #__PURE__ */ (function () {
+	var table = new Uint8Array(128);
+	for (var i = 0; i < 64; i++) table[i < 26 ? i + 65 : i < 52 ? i + 71 : i < 62 ? i - 4 : i * 4 - 205] = i;
+	return function (base64) {
+		var n = base64.length;
+		var bytes = new Uint8Array((((n - (base64[n - 1] == '=') - (base64[n - 2] == '=')) * 3) / 4) | 0);
+		for (var i = 0, j = 0; i < n; ) {
+			var c0 = table[base64.charCodeAt(i++)], c1 = table[base64.charCodeAt(i++)];
+			var c2 = table[base64.charCodeAt(i++)], c3 = table[base64.charCodeAt(i++)];
+			bytes[j++] = (c0 << 2) | (c1 >> 4);
+			bytes[j++] = (c1 << 4) | (c2 >> 2);
+			bytes[j++] = (c2 << 6) | c3;
+		}
+		return bytes;
+	};
+})();
\ No newline at end of file
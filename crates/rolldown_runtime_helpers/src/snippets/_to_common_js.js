@@ -0,0 +1,10 @@
+function __toCommonJS(ns) {
+	var mod = Object.defineProperty({}, '__esModule', { value: true });
+	Object.keys(ns).forEach(function (k) {
+		Object.defineProperty(mod, k, {
+			enumerable: true,
+			get: function () { return ns[k]; }
+		});
+	});
+	return mod;
+}
\ No newline at end of file
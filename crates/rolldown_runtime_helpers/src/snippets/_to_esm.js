@@ -0,0 +1,13 @@
+function __toESM(mod) {
+	if (mod && mod.__esModule) return mod;
+	var n = { __proto__: null, default: mod };
+	mod && (typeof mod === 'object' || typeof mod === 'function') && Object.keys(mod).forEach(function (k) {
+		if (k !== 'default') {
+			Object.defineProperty(n, k, {
+				enumerable: true,
+				get: function () { return mod[k]; }
+			});
+		}
+	});
+	return n;
+}
\ No newline at end of file
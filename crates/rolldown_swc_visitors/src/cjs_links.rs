@@ -0,0 +1,691 @@
+use hashlink::LinkedHashMap;
+use rolldown_common::InteropMode;
+use rolldown_error::Error;
+use rolldown_runtime_helpers::RuntimeHelpers;
+use rustc_hash::FxHashMap;
+use swc_core::{
+  common::DUMMY_SP,
+  ecma::{
+    ast,
+    atoms::{js_word, JsWord},
+    utils::{self as swc_ecma_utils, member_expr},
+    visit::{VisitMut, VisitMutWith, VisitWith},
+  },
+};
+use swc_ecma_utils::quote_ident;
+
+use crate::module_wrapper::{
+  assign, call, conflictless_name, export_name_of, expr_stmt, fn_expr, ident, legal_name_of,
+  member, prop_of, str_lit, IdentCollector,
+};
+
+/// How the linked chunk gets its dependencies and exposes its default export.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum CjsLinksFormat {
+  /// Dependencies are `require`d and the default export is assigned to `module.exports`.
+  CommonJs,
+  /// Dependencies are parameters of a factory function, which returns the default export. `import()` is kept as
+  /// is, since there's no loader to `require` other chunks. Used by IIFE and UMD output.
+  Factory,
+  /// Like `Factory`, but `import()` is turned into `require([...])` of the AMD loader.
+  Amd,
+}
+
+pub struct CjsLinksOptions<'a> {
+  pub format: CjsLinksFormat,
+  /// Whether the source of an import is an external module rather than another chunk.
+  pub is_external: &'a dyn Fn(&str) -> bool,
+  /// The interop mode of an external module by its id. The id is `None` for `import()` of non-string sources.
+  pub interop: &'a dyn Fn(Option<&str>) -> InteropMode,
+  /// `module.exports` is assigned with the default export rather than filling `exports`.
+  pub default_export: bool,
+  /// Interop helpers called by the chunk are marked as used.
+  pub runtime_helpers: &'a RuntimeHelpers,
+}
+
+pub struct CjsLinks {
+  /// `require` calls and export getters, which come before modules of the chunk.
+  pub before: Vec<ast::ModuleItem>,
+  /// `module.exports = ...` or `return ...` in the `default` export mode.
+  pub after: Vec<ast::ModuleItem>,
+  /// Dependencies passed to the factory function in the order of imports. It's empty for CommonJS output.
+  pub dependencies: Vec<FactoryDependency>,
+  /// `require` of the AMD loader is called by `import()` expressions.
+  pub requires_lazily: bool,
+}
+
+pub struct FactoryDependency {
+  pub source: JsWord,
+  /// The parameter of the factory function. It's `None` for dependencies only imported for side effects.
+  pub param: Option<JsWord>,
+}
+
+/// Runtime helpers wrapping required external modules, which decide how their default exports and namespaces
+/// are interpreted.
+#[derive(Clone, Copy)]
+enum InteropHelper {
+  ToEsm,
+  InteropDefault,
+  InteropDefaultCompat,
+  InteropNamespaceCompat,
+  InteropNamespaceDefault,
+  InteropNamespaceDefaultOnly,
+}
+
+impl InteropHelper {
+  const ALL: [InteropHelper; 6] = [
+    InteropHelper::ToEsm,
+    InteropHelper::InteropDefault,
+    InteropHelper::InteropDefaultCompat,
+    InteropHelper::InteropNamespaceCompat,
+    InteropHelper::InteropNamespaceDefault,
+    InteropHelper::InteropNamespaceDefaultOnly,
+  ];
+
+  fn name(self) -> &'static str {
+    match self {
+      InteropHelper::ToEsm => "__toESM",
+      InteropHelper::InteropDefault => "__interopDefault",
+      InteropHelper::InteropDefaultCompat => "__interopDefaultCompat",
+      InteropHelper::InteropNamespaceCompat => "__interopNamespaceCompat",
+      InteropHelper::InteropNamespaceDefault => "__interopNamespaceDefault",
+      InteropHelper::InteropNamespaceDefaultOnly => "__interopNamespaceDefaultOnly",
+    }
+  }
+
+  fn mark_used(self, runtime_helpers: &RuntimeHelpers) {
+    match self {
+      InteropHelper::ToEsm => runtime_helpers.to_esm(),
+      InteropHelper::InteropDefault => runtime_helpers.interop_default(),
+      InteropHelper::InteropDefaultCompat => runtime_helpers.interop_default_compat(),
+      InteropHelper::InteropNamespaceCompat => runtime_helpers.interop_namespace_compat(),
+      InteropHelper::InteropNamespaceDefault => runtime_helpers.interop_namespace_default(),
+      InteropHelper::InteropNamespaceDefaultOnly => {
+        runtime_helpers.interop_namespace_default_only()
+      }
+    }
+  }
+
+  /// The suffix of the variable holding the wrapped module, such as `_jquery__default`.
+  fn suffix(self) -> &'static str {
+    match self {
+      InteropHelper::InteropDefault | InteropHelper::InteropDefaultCompat => "default",
+      _ => "namespace",
+    }
+  }
+
+  /// The helper turning a required module into a namespace object.
+  fn namespace_of(interop: InteropMode) -> Option<Self> {
+    match interop {
+      InteropMode::Auto => Some(InteropHelper::ToEsm),
+      InteropMode::EsModule => None,
+      InteropMode::Default => Some(InteropHelper::InteropNamespaceDefault),
+      InteropMode::DefaultOnly => Some(InteropHelper::InteropNamespaceDefaultOnly),
+      InteropMode::Compat => Some(InteropHelper::InteropNamespaceCompat),
+    }
+  }
+
+  /// The helper wrapping a required module, and whether the default export is the wrapped module itself rather
+  /// than its `default` property.
+  fn of(
+    interop: InteropMode,
+    uses_namespace: bool,
+    uses_default: bool,
+    uses_named: bool,
+  ) -> (Option<Self>, bool) {
+    match interop {
+      InteropMode::EsModule => (None, false),
+      _ if uses_namespace || (uses_default && uses_named) => (Self::namespace_of(interop), false),
+      InteropMode::Default | InteropMode::DefaultOnly => (None, true),
+      InteropMode::Auto if uses_default => (Some(InteropHelper::InteropDefault), false),
+      InteropMode::Compat if uses_default => (Some(InteropHelper::InteropDefaultCompat), false),
+      InteropMode::Auto | InteropMode::Compat => (None, false),
+    }
+  }
+}
+
+/// Names of interop helpers, which are reserved in CommonJS based output since they are decided after deconflicting.
+pub fn interop_helper_names() -> impl Iterator<Item = &'static str> {
+  InteropHelper::ALL.into_iter().map(InteropHelper::name)
+}
+
+/// Turns imports and exports of a finalized chunk into CommonJS without printing and parsing the chunk again.
+///
+/// - `import { a } from './chunk.js'` becomes `var _chunk = require('./chunk.js')`, and references of `a` in
+///   `modules` are rewritten to `_chunk.a` to keep live bindings.
+/// - `export { a as b }` becomes a getter `Object.defineProperty(exports, 'b', { get: ... })`.
+/// - `import('./chunk.js')` becomes `Promise.resolve().then(function () { return require('./chunk.js'); })`.
+///
+/// Required external modules are wrapped in interop helpers depending on their interop modes. For formats wrapping
+/// the chunk in a factory function, `_chunk` is a parameter of the factory rather than a `require` call.
+pub fn links_to_cjs(
+  imports: Vec<ast::ModuleItem>,
+  exports: Vec<ast::ModuleItem>,
+  modules: &mut [&mut ast::Module],
+  options: CjsLinksOptions,
+) -> Result<CjsLinks, Error> {
+  let mut collector = IdentCollector::default();
+  modules
+    .iter()
+    .for_each(|module| module.visit_with(&mut collector));
+  imports.visit_with(&mut collector);
+  exports.visit_with(&mut collector);
+  let mut used_names = collector.names;
+  used_names.extend(
+    ["exports", "module", "require"]
+      .into_iter()
+      .chain(interop_helper_names())
+      .map(JsWord::from),
+  );
+
+  let mut decls_by_source: LinkedHashMap<JsWord, Vec<ast::ModuleDecl>> = LinkedHashMap::new();
+  imports.into_iter().for_each(|item| {
+    let source = match &item {
+      ast::ModuleItem::ModuleDecl(ast::ModuleDecl::Import(ast::ImportDecl { src, .. }))
+      | ast::ModuleItem::ModuleDecl(ast::ModuleDecl::ExportAll(ast::ExportAll { src, .. })) => {
+        src.value.clone()
+      }
+      ast::ModuleItem::ModuleDecl(ast::ModuleDecl::ExportNamed(ast::NamedExport {
+        src: Some(src),
+        ..
+      })) => src.value.clone(),
+      _ => return,
+    };
+    if let ast::ModuleItem::ModuleDecl(decl) = item {
+      decls_by_source
+        .entry(source)
+        .or_insert_with(Vec::new)
+        .push(decl);
+    }
+  });
+
+  let mut bindings: FxHashMap<JsWord, Box<ast::Expr>> = Default::default();
+  let mut exported: Vec<(JsWord, Box<ast::Expr>)> = vec![];
+  let mut requires = vec![];
+  let mut dependencies = vec![];
+  let mut export_alls = vec![];
+  for (source, decls) in decls_by_source {
+    let namespace = decls.iter().find_map(|decl| match decl {
+      ast::ModuleDecl::Import(import) => import.specifiers.iter().find_map(|spec| match spec {
+        ast::ImportSpecifier::Namespace(ns) => Some(ns.local.sym.clone()),
+        _ => None,
+      }),
+      _ => None,
+    });
+    let interop = if (options.is_external)(&source) {
+      (options.interop)(Some(&*source))
+    } else {
+      // Other chunks are always transpiled ES modules
+      InteropMode::EsModule
+    };
+    let mut uses_default = false;
+    let mut uses_namespace = false;
+    // The first named import, and whether it's a re-export
+    let mut named_import = None;
+    decls.iter().for_each(|decl| match decl {
+      ast::ModuleDecl::Import(import) => import.specifiers.iter().for_each(|spec| match spec {
+        ast::ImportSpecifier::Named(named) => {
+          let imported = named
+            .imported
+            .as_ref()
+            .map_or_else(|| named.local.sym.clone(), export_name_of);
+          if imported == js_word!("default") {
+            uses_default = true;
+          } else {
+            named_import.get_or_insert((imported, false));
+          }
+        }
+        ast::ImportSpecifier::Default(_) => uses_default = true,
+        ast::ImportSpecifier::Namespace(_) => uses_namespace = true,
+      }),
+      ast::ModuleDecl::ExportNamed(export) => {
+        export.specifiers.iter().for_each(|spec| match spec {
+          ast::ExportSpecifier::Named(named) => {
+            let orig = export_name_of(&named.orig);
+            if orig == js_word!("default") {
+              uses_default = true;
+            } else {
+              named_import.get_or_insert((orig, true));
+            }
+          }
+          ast::ExportSpecifier::Namespace(_) => uses_namespace = true,
+          ast::ExportSpecifier::Default(_) => {}
+        })
+      }
+      _ => {}
+    });
+    if interop == InteropMode::DefaultOnly
+      && let Some((imported, is_reexport)) = &named_import
+    {
+      return Err(Error::unexpected_named_import(
+        source.to_string(),
+        imported.to_string(),
+        *is_reexport,
+      ));
+    }
+    let (helper, default_is_module) = InteropHelper::of(
+      interop,
+      uses_namespace,
+      uses_default,
+      named_import.is_some(),
+    );
+    let needs_var = decls.iter().any(|decl| match decl {
+      ast::ModuleDecl::Import(import) => !import.specifiers.is_empty(),
+      _ => true,
+    });
+
+    let var_name = match options.format {
+      CjsLinksFormat::CommonJs => {
+        let mut required = call(ident("require"), vec![str_lit(&source)]);
+        if let Some(helper) = helper {
+          helper.mark_used(options.runtime_helpers);
+          required = call(ident(helper.name()), vec![required]);
+        }
+        if !needs_var {
+          requires.push(expr_stmt(required));
+          continue;
+        }
+        let var_name =
+          namespace.unwrap_or_else(|| conflictless_name(&legal_name_of(&source), &mut used_names));
+        requires.push(var_stmt(&var_name, required));
+        var_name
+      }
+      CjsLinksFormat::Factory | CjsLinksFormat::Amd if !needs_var => {
+        dependencies.push(FactoryDependency {
+          source,
+          param: None,
+        });
+        continue;
+      }
+      CjsLinksFormat::Factory | CjsLinksFormat::Amd => {
+        let (param, var_name) = match helper {
+          // The parameter is used as is
+          None => {
+            let param = namespace
+              .unwrap_or_else(|| conflictless_name(&legal_name_of(&source), &mut used_names));
+            (param.clone(), param)
+          }
+          Some(helper) => {
+            helper.mark_used(options.runtime_helpers);
+            let param = conflictless_name(&legal_name_of(&source), &mut used_names);
+            let var_name = namespace.unwrap_or_else(|| {
+              conflictless_name(&format!("{param}__{}", helper.suffix()), &mut used_names)
+            });
+            requires.push(var_stmt(
+              &var_name,
+              call(ident(helper.name()), vec![ident(&param)]),
+            ));
+            (param, var_name)
+          }
+        };
+        dependencies.push(FactoryDependency {
+          source,
+          param: Some(param),
+        });
+        var_name
+      }
+    };
+    let prop_of_var = |name: &str| {
+      if default_is_module && name == "default" {
+        ident(&var_name)
+      } else {
+        prop_of(ident(&var_name), name)
+      }
+    };
+
+    for decl in decls {
+      match decl {
+        ast::ModuleDecl::Import(import) => {
+          import.specifiers.into_iter().for_each(|spec| match spec {
+            ast::ImportSpecifier::Named(named) => {
+              let imported = named
+                .imported
+                .as_ref()
+                .map_or_else(|| named.local.sym.clone(), export_name_of);
+              bindings.insert(named.local.sym, prop_of_var(&imported));
+            }
+            ast::ImportSpecifier::Default(default) => {
+              bindings.insert(default.local.sym, prop_of_var("default"));
+            }
+            ast::ImportSpecifier::Namespace(_) => {}
+          })
+        }
+        ast::ModuleDecl::ExportNamed(export) => {
+          export.specifiers.into_iter().for_each(|spec| match spec {
+            ast::ExportSpecifier::Named(named) => {
+              let orig = export_name_of(&named.orig);
+              let name = named
+                .exported
+                .as_ref()
+                .map_or_else(|| orig.clone(), export_name_of);
+              exported.push((name, prop_of_var(&orig)));
+            }
+            ast::ExportSpecifier::Namespace(ns) => {
+              exported.push((export_name_of(&ns.name), ident(&var_name)));
+            }
+            ast::ExportSpecifier::Default(_) => {}
+          })
+        }
+        ast::ModuleDecl::ExportAll(_) => export_alls.push(export_all_stmt(&var_name)),
+        _ => {}
+      }
+    }
+  }
+
+  // Statements among exports, such as `__reExport(exports, import_foo)`, are kept after modules of the chunk.
+  let mut after = vec![];
+  exports.into_iter().for_each(|item| match item {
+    ast::ModuleItem::ModuleDecl(ast::ModuleDecl::ExportNamed(export)) => {
+      export.specifiers.into_iter().for_each(|spec| {
+        if let ast::ExportSpecifier::Named(named) = spec
+          && let ast::ModuleExportName::Ident(local) = named.orig
+        {
+          let name = named.exported.as_ref().map_or_else(|| local.sym.clone(), export_name_of);
+          let value = bindings
+            .get(&local.sym)
+            .cloned()
+            .unwrap_or_else(|| box ast::Expr::Ident(local));
+          exported.push((name, value));
+        }
+      });
+    }
+    ast::ModuleItem::Stmt(stmt) => after.push(stmt),
+    _ => {}
+  });
+  exported.sort_by(|a, b| a.0.cmp(&b.0));
+
+  let mut before = vec![expr_stmt(str_lit("use strict"))];
+  if options.default_export {
+    if let Some((_, value)) = exported.into_iter().find(|(name, _)| name == "default") {
+      after.push(match options.format {
+        CjsLinksFormat::CommonJs => {
+          expr_stmt(assign(member_expr!(DUMMY_SP, module.exports), value))
+        }
+        CjsLinksFormat::Factory | CjsLinksFormat::Amd => ast::Stmt::Return(ast::ReturnStmt {
+          span: DUMMY_SP,
+          arg: Some(value),
+        }),
+      });
+    }
+  } else {
+    if !exported.is_empty() || !export_alls.is_empty() || !after.is_empty() {
+      // Object.defineProperty(exports, '__esModule', { value: true });
+      before.push(define_property(
+        "__esModule",
+        vec![key_value("value", true_lit())],
+      ));
+    }
+    before.extend(
+      exported
+        .into_iter()
+        .map(|(name, value)| define_getter(ident("exports"), str_lit(&name), value)),
+    );
+  }
+  before.extend(requires);
+  before.extend(export_alls);
+
+  let mut rewriter = ImportBindingRewriter {
+    format: options.format,
+    bindings: &bindings,
+    is_external: options.is_external,
+    interop: options.interop,
+    runtime_helpers: options.runtime_helpers,
+    requires_lazily: false,
+  };
+  modules
+    .iter_mut()
+    .for_each(|module| module.visit_mut_with(&mut rewriter));
+
+  Ok(CjsLinks {
+    before: before.into_iter().map(ast::ModuleItem::Stmt).collect(),
+    after: after.into_iter().map(ast::ModuleItem::Stmt).collect(),
+    dependencies,
+    requires_lazily: rewriter.requires_lazily,
+  })
+}
+
+/// AMD loaders append `.js` to relative ids themselves.
+pub fn amd_id_of(source: &str) -> &str {
+  if source.starts_with('.') {
+    source.strip_suffix(".js").unwrap_or(source)
+  } else {
+    source
+  }
+}
+
+fn var_stmt(name: &str, init: Box<ast::Expr>) -> ast::Stmt {
+  ast::Stmt::Decl(ast::Decl::Var(box ast::VarDecl {
+    span: DUMMY_SP,
+    kind: ast::VarDeclKind::Var,
+    declare: false,
+    decls: vec![ast::VarDeclarator {
+      span: DUMMY_SP,
+      name: ast::Pat::Ident(quote_ident!(name).into()),
+      init: Some(init),
+      definite: false,
+    }],
+  }))
+}
+
+fn true_lit() -> Box<ast::Expr> {
+  box ast::Expr::Lit(ast::Lit::Bool(ast::Bool {
+    span: DUMMY_SP,
+    value: true,
+  }))
+}
+
+fn key_value(key: &str, value: Box<ast::Expr>) -> ast::PropOrSpread {
+  ast::PropOrSpread::Prop(box ast::Prop::KeyValue(ast::KeyValueProp {
+    key: ast::PropName::Ident(quote_ident!(key)),
+    value,
+  }))
+}
+
+fn define_property(name: &str, props: Vec<ast::PropOrSpread>) -> ast::Stmt {
+  expr_stmt(call(
+    member(ident("Object"), "defineProperty"),
+    vec![
+      ident("exports"),
+      str_lit(name),
+      box ast::Expr::Object(ast::ObjectLit {
+        span: DUMMY_SP,
+        props,
+      }),
+    ],
+  ))
+}
+
+/// `Object.defineProperty(target, name, { enumerable: true, get: function () { return value; } })`
+fn define_getter(target: Box<ast::Expr>, name: Box<ast::Expr>, value: Box<ast::Expr>) -> ast::Stmt {
+  let getter = fn_expr(
+    vec![],
+    vec![ast::Stmt::Return(ast::ReturnStmt {
+      span: DUMMY_SP,
+      arg: Some(value),
+    })],
+  );
+  expr_stmt(call(
+    member(ident("Object"), "defineProperty"),
+    vec![
+      target,
+      name,
+      box ast::Expr::Object(ast::ObjectLit {
+        span: DUMMY_SP,
+        props: vec![
+          key_value("enumerable", true_lit()),
+          key_value("get", getter),
+        ],
+      }),
+    ],
+  ))
+}
+
+/// ```js
+/// Object.keys(dep).forEach(function (k) {
+///   if (k !== 'default' && !Object.prototype.hasOwnProperty.call(exports, k)) Object.defineProperty(exports, k, {
+///     enumerable: true,
+///     get: function () { return dep[k]; }
+///   });
+/// });
+/// ```
+fn export_all_stmt(var_name: &str) -> ast::Stmt {
+  let key = || ident("k");
+  let value = box ast::Expr::Member(ast::MemberExpr {
+    span: DUMMY_SP,
+    obj: ident(var_name),
+    prop: ast::MemberProp::Computed(ast::ComputedPropName {
+      span: DUMMY_SP,
+      expr: key(),
+    }),
+  });
+  let test = box ast::Expr::Bin(ast::BinExpr {
+    span: DUMMY_SP,
+    op: ast::BinaryOp::LogicalAnd,
+    left: box ast::Expr::Bin(ast::BinExpr {
+      span: DUMMY_SP,
+      op: ast::BinaryOp::NotEqEq,
+      left: key(),
+      right: str_lit("default"),
+    }),
+    right: box ast::Expr::Unary(ast::UnaryExpr {
+      span: DUMMY_SP,
+      op: ast::UnaryOp::Bang,
+      arg: call(
+        member_expr!(DUMMY_SP, Object.prototype.hasOwnProperty.call),
+        vec![ident("exports"), key()],
+      ),
+    }),
+  });
+  let body = ast::Stmt::If(ast::IfStmt {
+    span: DUMMY_SP,
+    test,
+    cons: box define_getter(ident("exports"), key(), value),
+    alt: None,
+  });
+  expr_stmt(call(
+    member(
+      call(member(ident("Object"), "keys"), vec![ident(var_name)]),
+      "forEach",
+    ),
+    vec![fn_expr(vec![quote_ident!("k")], vec![body])],
+  ))
+}
+
+/// Rewrites references of imported bindings to member expressions of required modules. After finalizing, scoped
+/// bindings never share names with top-level ones, so references are matched by names.
+struct ImportBindingRewriter<'a> {
+  format: CjsLinksFormat,
+  bindings: &'a FxHashMap<JsWord, Box<ast::Expr>>,
+  is_external: &'a dyn Fn(&str) -> bool,
+  interop: &'a dyn Fn(Option<&str>) -> InteropMode,
+  runtime_helpers: &'a RuntimeHelpers,
+  requires_lazily: bool,
+}
+
+impl VisitMut for ImportBindingRewriter<'_> {
+  fn visit_mut_expr(&mut self, expr: &mut ast::Expr) {
+    expr.visit_mut_children_with(self);
+    match expr {
+      ast::Expr::Ident(ident) => {
+        if let Some(binding) = self.bindings.get(&ident.sym) {
+          *expr = *binding.clone();
+        }
+      }
+      // import('./chunk.js') -> Promise.resolve().then(function () { return require('./chunk.js'); })
+      // Namespaces of external modules are created by the interop helper.
+      ast::Expr::Call(ast::CallExpr {
+        callee: ast::Callee::Import(_),
+        args,
+        ..
+      }) if self.format != CjsLinksFormat::Factory && args.len() == 1 => {
+        let source = args.remove(0).expr;
+        let interop = match source.as_ref() {
+          ast::Expr::Lit(ast::Lit::Str(source)) if (self.is_external)(&source.value) => {
+            (self.interop)(Some(&*source.value))
+          }
+          ast::Expr::Lit(ast::Lit::Str(_)) => InteropMode::EsModule,
+          _ => (self.interop)(None),
+        };
+        let helper = InteropHelper::namespace_of(interop);
+        if let Some(helper) = helper {
+          helper.mark_used(self.runtime_helpers);
+        }
+        let wrap = |module: Box<ast::Expr>| match helper {
+          Some(helper) => call(ident(helper.name()), vec![module]),
+          None => module,
+        };
+        *expr = match self.format {
+          CjsLinksFormat::CommonJs => *call(
+            member(call(member(ident("Promise"), "resolve"), vec![]), "then"),
+            vec![fn_expr(
+              vec![],
+              vec![ast::Stmt::Return(ast::ReturnStmt {
+                span: DUMMY_SP,
+                arg: Some(wrap(call(ident("require"), vec![source]))),
+              })],
+            )],
+          ),
+          CjsLinksFormat::Amd => {
+            self.requires_lazily = true;
+            amd_dynamic_import(source, wrap(ident("m")))
+          }
+          CjsLinksFormat::Factory => unreachable!(),
+        };
+      }
+      _ => {}
+    }
+  }
+
+  fn visit_mut_prop(&mut self, prop: &mut ast::Prop) {
+    prop.visit_mut_children_with(self);
+    if let ast::Prop::Shorthand(ident) = prop
+      && let Some(binding) = self.bindings.get(&ident.sym)
+    {
+      *prop = ast::Prop::KeyValue(ast::KeyValueProp {
+        key: ast::PropName::Ident(ident.clone()),
+        value: binding.clone(),
+      });
+    }
+  }
+}
+
+/// ```js
+/// new Promise(function (resolve, reject) {
+///   require(['./chunk'], function (m) { resolve(m); }, reject);
+/// })
+/// ```
+fn amd_dynamic_import(source: Box<ast::Expr>, resolved: Box<ast::Expr>) -> ast::Expr {
+  let source = match *source {
+    ast::Expr::Lit(ast::Lit::Str(source)) => str_lit(amd_id_of(&source.value)),
+    source => box source,
+  };
+  let load = call(
+    ident("require"),
+    vec![
+      box ast::Expr::Array(ast::ArrayLit {
+        span: DUMMY_SP,
+        elems: vec![Some(ast::ExprOrSpread {
+          spread: None,
+          expr: source,
+        })],
+      }),
+      fn_expr(
+        vec![quote_ident!("m")],
+        vec![expr_stmt(call(ident("resolve"), vec![resolved]))],
+      ),
+      ident("reject"),
+    ],
+  );
+  ast::Expr::New(ast::NewExpr {
+    span: DUMMY_SP,
+    callee: ident("Promise"),
+    args: Some(vec![ast::ExprOrSpread {
+      spread: None,
+      expr: fn_expr(
+        vec![quote_ident!("resolve"), quote_ident!("reject")],
+        vec![expr_stmt(load)],
+      ),
+    }]),
+    type_args: None,
+  })
+}
@@ -0,0 +1,160 @@
+use rustc_hash::{FxHashMap, FxHashSet};
+use swc_core::{
+  common::{Span, DUMMY_SP},
+  ecma::{
+    ast::{self, Expr, Lit},
+    atoms::JsWord,
+    visit::{VisitMut, VisitMutWith},
+  },
+};
+
+/// Values of members of exported `const enum`s. Key is the name of the enum.
+pub type ConstEnums = FxHashMap<JsWord, FxHashMap<JsWord, Lit>>;
+
+/// Collect members of `export const enum Foo {}` whose values are known statically. It should be done
+/// before TypeScript is stripped.
+pub fn collect_exported_const_enums(ast: &ast::Module) -> ConstEnums {
+  ast
+    .body
+    .iter()
+    .filter_map(|module_item| match module_item {
+      ast::ModuleItem::ModuleDecl(ast::ModuleDecl::ExportDecl(ast::ExportDecl {
+        decl: ast::Decl::TsEnum(decl),
+        ..
+      }))
+        if decl.is_const && !decl.declare =>
+      {
+        Some(decl)
+      }
+      _ => None,
+    })
+    .map(|decl| (decl.id.sym.clone(), evaluate_enum_members(decl)))
+    .collect()
+}
+
+fn evaluate_enum_members(decl: &ast::TsEnumDecl) -> FxHashMap<JsWord, Lit> {
+  let mut values = FxHashMap::default();
+  // Members without initializers increase from the previous numeric member.
+  let mut next_value = Some(0.0);
+  for member in &decl.members {
+    let name = match &member.id {
+      ast::TsEnumMemberId::Ident(ident) => ident.sym.clone(),
+      ast::TsEnumMemberId::Str(string) => string.value.clone(),
+    };
+    let value = match &member.init {
+      Some(init) => evaluate(init, &values),
+      None => next_value.map(build_num),
+    };
+    next_value = match &value {
+      Some(Lit::Num(num)) => Some(num.value + 1.0),
+      _ => None,
+    };
+    if let Some(value) = value {
+      values.insert(name, value);
+    }
+  }
+  values
+}
+
+fn evaluate(expr: &Expr, values: &FxHashMap<JsWord, Lit>) -> Option<Lit> {
+  match expr {
+    Expr::Lit(lit @ (Lit::Num(_) | Lit::Str(_))) => Some(lit.clone()),
+    Expr::Paren(paren) => evaluate(&paren.expr, values),
+    Expr::Unary(ast::UnaryExpr {
+      op: ast::UnaryOp::Minus,
+      arg,
+      ..
+    }) => match evaluate(arg, values)? {
+      Lit::Num(num) => Some(build_num(-num.value)),
+      _ => None,
+    },
+    // Previous members, such as `B = A`
+    Expr::Ident(ident) => values.get(&ident.sym).cloned(),
+    _ => None,
+  }
+}
+
+fn build_num(value: f64) -> Lit {
+  Lit::Num(ast::Number {
+    span: DUMMY_SP,
+    value,
+    raw: None,
+  })
+}
+
+/// Replace `Foo.Bar` and `Foo['Bar']` with the value of the member, where `Foo` is a `const enum`
+/// imported from another module. Returns enums which are not referenced anymore.
+pub fn inline_const_enums(
+  ast: &mut ast::Module,
+  enums: &FxHashMap<ast::Id, FxHashMap<JsWord, Lit>>,
+) -> FxHashSet<ast::Id> {
+  let mut inliner = ConstEnumInliner {
+    enums,
+    referenced: Default::default(),
+  };
+  ast.visit_mut_with(&mut inliner);
+  enums
+    .keys()
+    .filter(|id| !inliner.referenced.contains(*id))
+    .cloned()
+    .collect()
+}
+
+struct ConstEnumInliner<'a> {
+  enums: &'a FxHashMap<ast::Id, FxHashMap<JsWord, Lit>>,
+  referenced: FxHashSet<ast::Id>,
+}
+
+impl<'a> ConstEnumInliner<'a> {
+  fn find_value(&self, member: &ast::MemberExpr) -> Option<&Lit> {
+    let Expr::Ident(obj) = member.obj.as_ref() else {
+      return None;
+    };
+    let members = self.enums.get(&obj.to_id())?;
+    match &member.prop {
+      ast::MemberProp::Ident(prop) => members.get(&prop.sym),
+      ast::MemberProp::Computed(ast::ComputedPropName {
+        expr: box Expr::Lit(Lit::Str(prop)),
+        ..
+      }) => members.get(&prop.value),
+      _ => None,
+    }
+  }
+}
+
+impl<'a> VisitMut for ConstEnumInliner<'a> {
+  // Specifiers of imports are not references
+  fn visit_mut_import_decl(&mut self, _: &mut ast::ImportDecl) {}
+
+  fn visit_mut_ident(&mut self, ident: &mut ast::Ident) {
+    let id = ident.to_id();
+    if self.enums.contains_key(&id) {
+      self.referenced.insert(id);
+    }
+  }
+
+  fn visit_mut_expr(&mut self, expr: &mut Expr) {
+    if let Expr::Member(member) = expr && let Some(value) = self.find_value(member) {
+      *expr = build_inlined_value(value.clone(), member.span);
+      return;
+    }
+    expr.visit_mut_children_with(self);
+  }
+}
+
+/// Negative numbers are built as unary expressions, which is the same as what the parser produces for `-1`.
+fn build_inlined_value(mut value: Lit, span: Span) -> Expr {
+  match &mut value {
+    Lit::Num(num) if num.value < 0.0 => {
+      return Expr::Unary(ast::UnaryExpr {
+        span,
+        op: ast::UnaryOp::Minus,
+        arg: box Expr::Lit(build_num(-num.value)),
+      });
+    }
+    Lit::Num(num) => num.span = span,
+    Lit::Str(string) => string.span = span,
+    _ => {}
+  }
+  Expr::Lit(value)
+}
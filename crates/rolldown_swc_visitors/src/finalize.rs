@@ -55,6 +55,8 @@ pub struct FinalizeContext<'me> {
   pub top_level_id_to_final_name: &'me HashMap<Id, JsWord>,
   pub split_point_id_to_chunk_id: &'me HashMap<ModuleId, ChunkId>,
   pub top_level_names: &'me HashSet<&'me JsWord>,
+  /// Expressions rendered by plugins to replace `import()`, keyed by the specifier in the source code.
+  pub rendered_dynamic_imports: &'me HashMap<JsWord, Box<ast::Expr>>,
 }
 
 #[instrument(skip_all)]
@@ -156,6 +158,11 @@ impl<'a> Finalizer<'a> {
       {
         *raw = None;
         let module_id = self.resolve_module_id(local_module_id)?;
+        // Plugins might resolve the specifier to another external id, such as an URL
+        if module_id.is_external() {
+          *local_module_id = module_id.id().clone();
+          return Some(());
+        }
         let chunk_id = self.ctx.split_point_id_to_chunk_id.get(module_id)?;
         let filename = self.ctx.chunk_filename_by_id.get(chunk_id)?;
         *local_module_id = format!("./{}", filename.clone()).into();
@@ -165,6 +172,21 @@ impl<'a> Finalizer<'a> {
     Some(())
   }
 
+  fn rendered_dynamic_import(&self, node: &ast::Expr) -> Option<ast::Expr> {
+    let call = node.as_call()?;
+    if !call.callee.is_import() {
+      return None;
+    }
+    match call.args.get(0)?.expr.as_lit()? {
+      ast::Lit::Str(specifier) => self
+        .ctx
+        .rendered_dynamic_imports
+        .get(&specifier.value)
+        .map(|rendered| *rendered.clone()),
+      _ => None,
+    }
+  }
+
   fn resolve_module_id(&self, local_module_id: &JsWord) -> Option<&ModuleId> {
     let resolved_id = self.ctx.resolved_ids.get(local_module_id)?;
     Some(resolved_id)
@@ -343,6 +365,15 @@ impl<'a> VisitMut for Finalizer<'a> {
     }
   }
 
+  fn visit_mut_expr(&mut self, node: &mut ast::Expr) {
+    // The rendered expression is provided by plugins. There's nothing to finalize in it.
+    if let Some(rendered) = self.rendered_dynamic_import(node) {
+      *node = rendered;
+      return;
+    }
+    node.visit_mut_children_with(self);
+  }
+
   fn visit_mut_call_expr(&mut self, node: &mut ast::CallExpr) {
     self.rewrite_dynamic_import(node);
     node.visit_mut_children_with(self);
@@ -0,0 +1,35 @@
+use std::sync::Arc;
+
+pub use react::{Options as JsxOptions, Runtime as JsxRuntime};
+use swc_core::{
+  common::{chain, comments::Comments, util::take::Take, Mark, SourceMap, GLOBALS},
+  ecma::{
+    ast::Module,
+    transforms::{
+      base::{fixer::fixer, hygiene::hygiene, resolver},
+      react::{self, react},
+    },
+    visit::FoldWith,
+  },
+};
+
+/// Lower JSX to function calls. Imports of the automatic runtime, such as `react/jsx-runtime`, are
+/// added to the module, so they are scanned as normal dependencies later.
+pub fn jsx_to_js<C>(ast: &mut Module, cm: Arc<SourceMap>, comments: Option<C>, options: JsxOptions)
+where
+  C: Comments + Clone,
+{
+  // It's ok to use a new GLOBALS here.
+  GLOBALS.set(&Default::default(), || {
+    let unresolved_mark = Mark::new();
+    let top_level_mark = Mark::new();
+
+    let mut chained = chain!(
+      resolver(unresolved_mark, top_level_mark, false),
+      react(cm, comments, options, top_level_mark, unresolved_mark),
+      hygiene(),
+      fixer(None)
+    );
+    *ast = ast.take().fold_with(&mut chained);
+  });
+}
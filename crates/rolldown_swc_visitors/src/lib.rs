@@ -9,6 +9,14 @@ mod remove_export_and_import;
 pub use remove_export_and_import::*;
 mod finalize;
 pub use finalize::*;
+mod rewrite_dynamic_import;
+pub use rewrite_dynamic_import::*;
+mod rewrite_require;
+pub use rewrite_require::*;
+mod wrap_esm;
+pub use wrap_esm::*;
+mod const_enum;
+pub use const_enum::*;
 mod resolve;
 pub use resolve::*;
 mod treeshake;
@@ -17,12 +25,15 @@ use swc_core::{
   ecma::visit::VisitMut,
 };
 pub use treeshake::*;
-mod to_cjs;
-pub use to_cjs::*;
-mod export_mode_shimer;
-pub use export_mode_shimer::*;
+mod cjs_links;
+mod module_wrapper;
+pub use cjs_links::*;
+mod system_links;
+pub use system_links::*;
 mod ts_to_js;
 pub use ts_to_js::*;
+mod jsx_to_js;
+pub use jsx_to_js::*;
 
 struct ClearSyntaxContext;
 
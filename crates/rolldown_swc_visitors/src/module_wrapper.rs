@@ -0,0 +1,146 @@
+//! AST helpers for linking chunks in output other than ES modules, such as CommonJS, IIFE, AMD, UMD and SystemJS.
+
+use rustc_hash::FxHashSet;
+use swc_core::{
+  common::{util::take::Take, DUMMY_SP},
+  ecma::{
+    ast,
+    atoms::JsWord,
+    utils::{self as swc_ecma_utils, quote_str},
+    visit::Visit,
+  },
+};
+use swc_ecma_utils::quote_ident;
+
+pub(crate) fn fn_expr(params: Vec<ast::Ident>, stmts: Vec<ast::Stmt>) -> Box<ast::Expr> {
+  box ast::Expr::Fn(ast::FnExpr {
+    ident: None,
+    function: box ast::Function {
+      params: params
+        .into_iter()
+        .map(|param| ast::Param {
+          span: DUMMY_SP,
+          decorators: vec![],
+          pat: ast::Pat::Ident(param.into()),
+        })
+        .collect(),
+      body: Some(ast::BlockStmt {
+        span: DUMMY_SP,
+        stmts,
+      }),
+      ..ast::Function::dummy()
+    },
+  })
+}
+
+pub(crate) fn call(callee: Box<ast::Expr>, args: Vec<Box<ast::Expr>>) -> Box<ast::Expr> {
+  box ast::Expr::Call(ast::CallExpr {
+    callee: ast::Callee::Expr(callee),
+    args: args
+      .into_iter()
+      .map(|expr| ast::ExprOrSpread { spread: None, expr })
+      .collect(),
+    ..ast::CallExpr::dummy()
+  })
+}
+
+pub(crate) fn ident(name: &str) -> Box<ast::Expr> {
+  box ast::Expr::Ident(quote_ident!(name))
+}
+
+pub(crate) fn str_lit(value: &str) -> Box<ast::Expr> {
+  box ast::Expr::Lit(ast::Lit::Str(quote_str!(value)))
+}
+
+pub(crate) fn member(obj: Box<ast::Expr>, prop: &str) -> Box<ast::Expr> {
+  box ast::Expr::Member(ast::MemberExpr {
+    span: DUMMY_SP,
+    obj,
+    prop: ast::MemberProp::Ident(quote_ident!(prop)),
+  })
+}
+
+pub(crate) fn assign(left: Box<ast::Expr>, right: Box<ast::Expr>) -> Box<ast::Expr> {
+  box ast::Expr::Assign(ast::AssignExpr {
+    span: DUMMY_SP,
+    op: ast::AssignOp::Assign,
+    left: ast::PatOrExpr::Expr(left),
+    right,
+  })
+}
+
+pub(crate) fn expr_stmt(expr: Box<ast::Expr>) -> ast::Stmt {
+  ast::Stmt::Expr(ast::ExprStmt {
+    span: DUMMY_SP,
+    expr,
+  })
+}
+
+pub(crate) fn export_name_of(name: &ast::ModuleExportName) -> JsWord {
+  match name {
+    ast::ModuleExportName::Ident(ident) => ident.sym.clone(),
+    ast::ModuleExportName::Str(string) => string.value.clone(),
+  }
+}
+
+/// `obj.prop`, or `obj["prop"]` for names that aren't identifiers.
+pub(crate) fn prop_of(obj: Box<ast::Expr>, prop: &str) -> Box<ast::Expr> {
+  let is_identifier_name = !prop.starts_with(|c: char| c.is_ascii_digit())
+    && !prop.is_empty()
+    && prop
+      .chars()
+      .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$');
+  if is_identifier_name {
+    member(obj, prop)
+  } else {
+    box ast::Expr::Member(ast::MemberExpr {
+      span: DUMMY_SP,
+      obj,
+      prop: ast::MemberProp::Computed(ast::ComputedPropName {
+        span: DUMMY_SP,
+        expr: str_lit(prop),
+      }),
+    })
+  }
+}
+
+/// Collects names of all identifiers, which new names should never conflict with.
+#[derive(Default)]
+pub(crate) struct IdentCollector {
+  pub(crate) names: FxHashSet<JsWord>,
+}
+
+impl Visit for IdentCollector {
+  fn visit_ident(&mut self, ident: &ast::Ident) {
+    self.names.insert(ident.sym.clone());
+  }
+}
+
+/// `base`, `base1`, `base2`, ... The returned name is marked as used.
+pub(crate) fn conflictless_name(base: &str, used_names: &mut FxHashSet<JsWord>) -> JsWord {
+  let mut name = JsWord::from(base);
+  let mut count = 0;
+  while used_names.contains(&name) {
+    count += 1;
+    name = format!("{base}{count}").into();
+  }
+  used_names.insert(name.clone());
+  name
+}
+
+/// `_jquery` for `jquery` and `_chunk` for `./chunk.js`. The leading underscore keeps it away from reserved words.
+pub(crate) fn legal_name_of(source: &str) -> String {
+  let base = source.rsplit('/').next().unwrap_or_default();
+  let base = base.strip_suffix(".js").unwrap_or(base);
+  let legal = base
+    .chars()
+    .map(|c| {
+      if c.is_ascii_alphanumeric() || c == '_' || c == '$' {
+        c
+      } else {
+        '_'
+      }
+    })
+    .collect::<String>();
+  format!("_{legal}")
+}
@@ -78,8 +78,16 @@ impl VisitMut for ExportRemover {
           ast::Decl::Var(var) => {
             vec![ModuleItem::Stmt(ast::Stmt::Decl(ast::Decl::Var(var)))]
           }
-          _ => unreachable!(),
+          decl @ (ast::Decl::TsEnum(_) | ast::Decl::TsModule(_)) => {
+            vec![ModuleItem::Stmt(ast::Stmt::Decl(decl))]
+          }
+          // Types are erased
+          ast::Decl::TsInterface(_) | ast::Decl::TsTypeAlias(_) => vec![],
         },
+        ModuleItem::ModuleDecl(ast::ModuleDecl::ExportDefaultDecl(ExportDefaultDecl {
+          decl: ast::DefaultDecl::TsInterfaceDecl(_),
+          ..
+        })) => vec![],
         ModuleItem::ModuleDecl(ast::ModuleDecl::ExportDefaultDecl(decl)) => {
           vec![self.rewrite_default_export_decl_to_stmt(decl)]
         }
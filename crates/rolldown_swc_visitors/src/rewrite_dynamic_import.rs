@@ -0,0 +1,40 @@
+use rustc_hash::FxHashMap;
+use swc_core::{
+  common::{Span, Spanned},
+  ecma::{
+    ast::{self, Callee, Expr},
+    atoms::JsWord,
+    utils::quote_str,
+    visit::{VisitMut, VisitMutWith},
+  },
+};
+
+/// Replace non-literal arguments of `import()` with string literals, keyed by the span of the argument.
+///
+/// After rewriting, `` import(`./locales/${lang}.js`) `` resolved by plugins is the same as a literal dynamic import.
+pub fn rewrite_dynamic_import_expressions(
+  ast: &mut ast::Module,
+  specifier_by_span: &FxHashMap<Span, JsWord>,
+) {
+  if specifier_by_span.is_empty() {
+    return;
+  }
+  ast.visit_mut_with(&mut DynamicImportRewriter { specifier_by_span })
+}
+
+struct DynamicImportRewriter<'a> {
+  specifier_by_span: &'a FxHashMap<Span, JsWord>,
+}
+
+impl<'a> VisitMut for DynamicImportRewriter<'a> {
+  fn visit_mut_call_expr(&mut self, node: &mut ast::CallExpr) {
+    if let Callee::Import(_) = node.callee
+      && let Some(arg) = node.args.get_mut(0)
+      && arg.spread.is_none()
+      && let Some(specifier) = self.specifier_by_span.get(&arg.expr.span())
+    {
+      arg.expr = Box::new(Expr::Lit(quote_str!(specifier.clone()).into()));
+    }
+    node.visit_mut_children_with(self);
+  }
+}
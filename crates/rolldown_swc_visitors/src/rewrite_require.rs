@@ -0,0 +1,52 @@
+use rustc_hash::FxHashMap;
+use swc_core::{
+  common::SyntaxContext,
+  ecma::{
+    ast::{self, Callee, Expr, Lit},
+    atoms::JsWord,
+    visit::{VisitMut, VisitMutWith},
+  },
+};
+
+/// Replace `require('./foo')` with a call of the function returning `module.exports` of the required module,
+/// such as `require_foo()`. Specifiers not in `wrapper_by_specifier` are kept as they are.
+pub fn rewrite_require_calls(
+  ast: &mut ast::Module,
+  unresolved_ctxt: SyntaxContext,
+  wrapper_by_specifier: &FxHashMap<JsWord, ast::Id>,
+) {
+  if wrapper_by_specifier.is_empty() {
+    return;
+  }
+  ast.visit_mut_with(&mut RequireRewriter {
+    unresolved_ctxt,
+    wrapper_by_specifier,
+  })
+}
+
+struct RequireRewriter<'a> {
+  unresolved_ctxt: SyntaxContext,
+  wrapper_by_specifier: &'a FxHashMap<JsWord, ast::Id>,
+}
+
+impl<'a> VisitMut for RequireRewriter<'a> {
+  fn visit_mut_expr(&mut self, node: &mut Expr) {
+    node.visit_mut_children_with(self);
+    if let Expr::Call(call) = node
+      && let Callee::Expr(box Expr::Ident(callee)) = &call.callee
+      && &callee.sym == "require"
+      && callee.span.ctxt == self.unresolved_ctxt
+      && call.args.len() == 1
+      && call.args[0].spread.is_none()
+      && let Expr::Lit(Lit::Str(required)) = call.args[0].expr.as_ref()
+      && let Some(wrapper) = self.wrapper_by_specifier.get(&required.value)
+    {
+      *node = Expr::Call(ast::CallExpr {
+        span: call.span,
+        callee: Callee::Expr(box Expr::Ident(wrapper.clone().into())),
+        args: vec![],
+        type_args: None,
+      });
+    }
+  }
+}
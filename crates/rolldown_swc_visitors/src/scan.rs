@@ -1,7 +1,7 @@
 use std::sync::atomic::AtomicBool;
 
 use ast::{CallExpr, Callee, ExportSpecifier, Expr, Id, Ident, Lit, ModuleDecl, ModuleItem, Stmt};
-use hashlink::LinkedHashSet;
+use hashlink::{LinkedHashMap, LinkedHashSet};
 use rolldown_common::{ExportedSpecifier, ImportedSpecifier, Symbol};
 use rolldown_common::{ModuleId, ReExportedSpecifier};
 use rolldown_swc_utils::{ExportNamedSpecifierExt, ImportNamedSpecifierExt, ModuleExportNameExt};
@@ -36,6 +36,8 @@ pub fn scan(
 pub struct ScanResult {
   pub dependencies: LinkedHashSet<JsWord>,
   pub dyn_dependencies: HashSet<JsWord>,
+  /// Non-literal arguments of `import()`, such as `` import(`./locales/${lang}.js`) ``
+  pub dyn_import_expressions: Vec<Expr>,
   // pub imported_ids: HashMap<JsWord, HashSet<SpecifierInfo>>,
   // Representations of special cases
   // `export * from './src'        => alias: ("*", SyntaxContext) origin: "*"
@@ -53,6 +55,11 @@ pub struct ScanResult {
   pub statement_parts: Vec<StatementPart>,
   pub imports: FxHashMap<JsWord, Vec<ImportedSpecifier>>,
   pub suggested_names: FxHashMap<JsWord, JsWord>,
+  /// `require('./foo')` calls with a string literal. Each specifier is referenced through a placeholder
+  /// symbol, which is linked to the required module in the linking phase.
+  pub required: LinkedHashMap<JsWord, Symbol>,
+  /// The module has no `import`/`export` but refers to `module` or `exports`.
+  pub is_commonjs: bool,
 }
 
 /// Notices
@@ -66,6 +73,8 @@ struct Scanner {
   top_level_ctxt: SyntaxContext,
   statement_part: StatementPart,
   imported_namespaces: HashMap<Symbol, NamespaceInfo>,
+  has_module_syntax: bool,
+  refers_commonjs_variables: bool,
 }
 
 struct NamespaceInfo {
@@ -90,6 +99,8 @@ impl Scanner {
       exported_names: Default::default(),
       statement_part: Default::default(),
       imported_namespaces: Default::default(),
+      has_module_syntax: false,
+      refers_commonjs_variables: false,
     }
   }
 
@@ -149,12 +160,39 @@ impl Scanner {
         if dyn_imported.spread.is_none() {
           if let Expr::Lit(Lit::Str(imported)) = dyn_imported.expr.as_ref() {
             self.result.dyn_dependencies.insert(imported.value.clone());
+          } else {
+            self
+              .result
+              .dyn_import_expressions
+              .push(dyn_imported.expr.as_ref().clone());
           }
         }
       }
     }
   }
 
+  fn add_required(&mut self, node: &CallExpr) {
+    if let Callee::Expr(box Expr::Ident(callee)) = &node.callee
+      && &callee.sym == "require"
+      && callee.span.ctxt == self.unresolved_ctxt
+      && node.args.len() == 1
+      && node.args[0].spread.is_none()
+      && let Expr::Lit(Lit::Str(required)) = node.args[0].expr.as_ref()
+    {
+      let top_level_ctxt = self.top_level_ctxt;
+      let placeholder = self
+        .result
+        .required
+        .entry(required.value.clone())
+        .or_insert_with(|| {
+          // The invalid name avoids conflicts with local names
+          Symbol::new(format!("require#{}", required.value).into(), top_level_ctxt)
+        })
+        .clone();
+      self.statement_part.referenced.insert(placeholder);
+    }
+  }
+
   fn add_imported_specifier(
     &mut self,
     local_module_id: JsWord,
@@ -271,10 +309,13 @@ impl Scanner {
             .map(|id| (id.0.clone(), id))
             .for_each(|(name, id)| self.add_local_export(name, id.into()));
         }
-        ast::Decl::TsInterface(_) => unreachable!(),
-        ast::Decl::TsTypeAlias(_) => unreachable!(),
-        ast::Decl::TsEnum(_) => unreachable!(),
-        ast::Decl::TsModule(_) => unreachable!(),
+        ast::Decl::TsEnum(_) | ast::Decl::TsModule(_) => {
+          if let Some(ident) = ts_runtime_binding(&decl.decl) {
+            self.add_local_export(ident.sym.clone(), ident.to_id().into());
+          }
+        }
+        // Types are erased
+        ast::Decl::TsInterface(_) | ast::Decl::TsTypeAlias(_) => {}
       },
       ModuleDecl::ExportDefaultDecl(node) => match &node.decl {
         // We will make sure that the default export always has a name.
@@ -290,7 +331,7 @@ impl Scanner {
             node.ident.clone().map(|i| i.to_id()).unwrap().into(),
           );
         }
-        ast::DefaultDecl::TsInterfaceDecl(_) => unreachable!(),
+        ast::DefaultDecl::TsInterfaceDecl(_) => {}
       },
       ModuleDecl::ExportDefaultExpr(node) => match node.expr.as_ref() {
         Expr::Ident(ident) => {
@@ -323,10 +364,12 @@ impl Scanner {
           // Because we might missing some top level variable declarations
           // which is in the block statement.
         }
-        ast::Decl::TsInterface(_) => unreachable!(),
-        ast::Decl::TsTypeAlias(_) => unreachable!(),
-        ast::Decl::TsEnum(_) => unreachable!(),
-        ast::Decl::TsModule(_) => unreachable!(),
+        ast::Decl::TsEnum(_) | ast::Decl::TsModule(_) => {
+          if let Some(ident) = ts_runtime_binding(decl) {
+            self.add_declared_id(ident.to_id().into());
+          }
+        }
+        ast::Decl::TsInterface(_) | ast::Decl::TsTypeAlias(_) => {}
       },
       ModuleItem::ModuleDecl(module_decl) => match module_decl {
         ModuleDecl::ExportDecl(decl) => match &decl.decl {
@@ -345,10 +388,12 @@ impl Scanner {
               .map(|i| i.to_id())
               .for_each(|id| self.add_declared_id(id.into()));
           }
-          ast::Decl::TsInterface(_) => todo!(),
-          ast::Decl::TsTypeAlias(_) => todo!(),
-          ast::Decl::TsEnum(_) => todo!(),
-          ast::Decl::TsModule(_) => todo!(),
+          ast::Decl::TsEnum(_) | ast::Decl::TsModule(_) => {
+            if let Some(ident) = ts_runtime_binding(&decl.decl) {
+              self.add_declared_id(ident.to_id().into());
+            }
+          }
+          ast::Decl::TsInterface(_) | ast::Decl::TsTypeAlias(_) => {}
         },
         ModuleDecl::ExportDefaultDecl(node) => match &node.decl {
           ast::DefaultDecl::Class(cls) => {
@@ -363,7 +408,7 @@ impl Scanner {
               self.add_declared_id(self.facade_default_symbol())
             }
           }
-          ast::DefaultDecl::TsInterfaceDecl(_) => todo!(),
+          ast::DefaultDecl::TsInterfaceDecl(_) => {}
         },
         _ => {}
       },
@@ -426,6 +471,9 @@ impl Scanner {
       info.is_used_dynamically = true;
     }
     if variable.span.ctxt == self.unresolved_ctxt {
+      if &variable.sym == "module" || &variable.sym == "exports" {
+        self.refers_commonjs_variables = true;
+      }
       self
         .result
         .visited_global_names
@@ -481,7 +529,7 @@ impl Scanner {
             }
             break;
           }
-          ast::DefaultDecl::TsInterfaceDecl(_) => unreachable!(),
+          ast::DefaultDecl::TsInterfaceDecl(_) => {}
         },
         _ => {}
       };
@@ -533,9 +581,12 @@ impl VisitMut for Scanner {
         self.scan_export(module_decl);
       });
 
+    self.has_module_syntax = node.iter().any(|module_item| module_item.is_module_decl());
+
     node.visit_mut_children_with(self);
 
     self.add_imported_namespaces_to_imports();
+    self.result.is_commonjs = !self.has_module_syntax && self.refers_commonjs_variables;
   }
 
   fn visit_mut_module_item(&mut self, node: &mut ModuleItem) {
@@ -556,6 +607,7 @@ impl VisitMut for Scanner {
 
   fn visit_mut_call_expr(&mut self, node: &mut CallExpr) {
     self.add_dynamic_import(node);
+    self.add_required(node);
     node.visit_mut_children_with(self);
   }
 
@@ -624,6 +676,19 @@ pub struct StatementPart {
   pub side_effect: bool,
 }
 
+/// `enum Foo {}` and `namespace Foo {}` declare runtime bindings, while other TypeScript declarations
+/// are types only.
+fn ts_runtime_binding(decl: &ast::Decl) -> Option<&Ident> {
+  match decl {
+    ast::Decl::TsEnum(decl) if !decl.declare => Some(&decl.id),
+    ast::Decl::TsModule(decl) if !decl.declare => match &decl.id {
+      ast::TsModuleName::Ident(ident) => Some(ident),
+      ast::TsModuleName::Str(_) => None,
+    },
+    _ => None,
+  }
+}
+
 #[derive(Default, Debug)]
 struct ParamsCollector {
   pub collected: HashMap<JsWord, Id>,
@@ -0,0 +1,327 @@
+use hashlink::LinkedHashMap;
+use rustc_hash::FxHashMap;
+use swc_core::{
+  common::{util::take::Take, DUMMY_SP},
+  ecma::{
+    ast,
+    atoms::JsWord,
+    utils::{self as swc_ecma_utils},
+    visit::{VisitMut, VisitMutWith},
+  },
+};
+use swc_ecma_utils::quote_ident;
+
+use crate::module_wrapper::{
+  assign, call, export_name_of, expr_stmt, fn_expr, ident, member, prop_of, str_lit,
+};
+
+pub struct SystemLinks {
+  /// `exports` calls of exported functions, which come before modules of the chunk in `execute`.
+  pub before: Vec<ast::ModuleItem>,
+  /// Dependencies of `System.register` in the order of imports.
+  pub dependencies: Vec<SystemDependency>,
+  /// Imported bindings, which are declared out of `execute` and assigned by setters.
+  pub bindings: Vec<JsWord>,
+}
+
+pub struct SystemDependency {
+  pub source: JsWord,
+  /// `function (module) { a = module.a; }`. It's `None` for dependencies only imported for side effects.
+  pub setter: Option<Box<ast::Expr>>,
+}
+
+/// Turns imports and exports of a finalized chunk into SystemJS without printing and parsing the chunk again.
+///
+/// - `import { a } from './chunk.js'` declares `a` out of `execute`, which is assigned by the setter
+///   `function (module) { a = module.a; }` of the dependency `./chunk.js`. SystemJS calls setters again once
+///   bindings of the dependency are updated.
+/// - `export { a as b }` becomes `exports('b', a)` after the declaration of `a`, and assignments of `a` in
+///   `modules` are wrapped in `exports('b', ...)` to keep live bindings.
+/// - `import('./chunk.js')` becomes `module.import('./chunk.js')`, and `import.meta` becomes `module.meta`.
+pub fn links_to_system(
+  imports: Vec<ast::ModuleItem>,
+  exports: Vec<ast::ModuleItem>,
+  modules: &mut [&mut ast::Module],
+) -> SystemLinks {
+  let mut decls_by_source: LinkedHashMap<JsWord, Vec<ast::ModuleDecl>> = LinkedHashMap::new();
+  imports.into_iter().for_each(|item| {
+    let source = match &item {
+      ast::ModuleItem::ModuleDecl(ast::ModuleDecl::Import(ast::ImportDecl { src, .. }))
+      | ast::ModuleItem::ModuleDecl(ast::ModuleDecl::ExportAll(ast::ExportAll { src, .. })) => {
+        src.value.clone()
+      }
+      ast::ModuleItem::ModuleDecl(ast::ModuleDecl::ExportNamed(ast::NamedExport {
+        src: Some(src),
+        ..
+      })) => src.value.clone(),
+      _ => return,
+    };
+    if let ast::ModuleItem::ModuleDecl(decl) = item {
+      decls_by_source
+        .entry(source)
+        .or_insert_with(Vec::new)
+        .push(decl);
+    }
+  });
+
+  // Names exported by the chunk by their local bindings
+  let mut exported: FxHashMap<JsWord, Vec<JsWord>> = Default::default();
+  exports.into_iter().for_each(|item| {
+    if let ast::ModuleItem::ModuleDecl(ast::ModuleDecl::ExportNamed(export)) = item {
+      export.specifiers.into_iter().for_each(|spec| {
+        if let ast::ExportSpecifier::Named(named) = spec
+          && let ast::ModuleExportName::Ident(local) = named.orig
+        {
+          let name = named.exported.as_ref().map_or_else(|| local.sym.clone(), export_name_of);
+          exported.entry(local.sym).or_default().push(name);
+        }
+      });
+    }
+  });
+  exported.values_mut().for_each(|names| names.sort());
+
+  let mut bindings = vec![];
+  let mut dependencies = vec![];
+  for (source, decls) in decls_by_source {
+    let module = || ident("module");
+    let mut stmts = vec![];
+    let mut export_all = false;
+    for decl in decls {
+      match decl {
+        ast::ModuleDecl::Import(import) => {
+          import.specifiers.into_iter().for_each(|spec| {
+            let (local, value) = match spec {
+              ast::ImportSpecifier::Named(named) => {
+                let imported = named
+                  .imported
+                  .as_ref()
+                  .map_or_else(|| named.local.sym.clone(), export_name_of);
+                (named.local.sym, prop_of(module(), &imported))
+              }
+              ast::ImportSpecifier::Default(default) => {
+                (default.local.sym, prop_of(module(), "default"))
+              }
+              ast::ImportSpecifier::Namespace(ns) => (ns.local.sym, module()),
+            };
+            stmts.push(expr_stmt(assign(ident(&local), value)));
+            // Imported bindings exported by the chunk
+            if let Some(names) = exported.remove(&local) {
+              names
+                .iter()
+                .for_each(|name| stmts.push(expr_stmt(export_call(name, ident(&local)))));
+            }
+            bindings.push(local);
+          })
+        }
+        ast::ModuleDecl::ExportNamed(export) => {
+          export.specifiers.into_iter().for_each(|spec| match spec {
+            ast::ExportSpecifier::Named(named) => {
+              let orig = export_name_of(&named.orig);
+              let name = named
+                .exported
+                .as_ref()
+                .map_or_else(|| orig.clone(), export_name_of);
+              stmts.push(expr_stmt(export_call(&name, prop_of(module(), &orig))));
+            }
+            ast::ExportSpecifier::Namespace(ns) => {
+              stmts.push(expr_stmt(export_call(&export_name_of(&ns.name), module())));
+            }
+            ast::ExportSpecifier::Default(_) => {}
+          })
+        }
+        ast::ModuleDecl::ExportAll(_) => export_all = true,
+        _ => {}
+      }
+    }
+    if export_all {
+      stmts.push(export_all_stmt());
+    }
+    dependencies.push(SystemDependency {
+      source,
+      setter: (!stmts.is_empty()).then(|| fn_expr(vec![quote_ident!("module")], stmts)),
+    });
+  }
+
+  // Functions are hoisted, so they're exported before modules are executed.
+  let mut before = vec![];
+  modules.iter().for_each(|module| {
+    module.body.iter().for_each(|item| {
+      if let ast::ModuleItem::Stmt(ast::Stmt::Decl(ast::Decl::Fn(ast::FnDecl { ident: local, .. }))) = item
+        && let Some(names) = exported.get(&local.sym)
+      {
+        names
+          .iter()
+          .for_each(|name| before.push(expr_stmt(export_call(name, ident(&local.sym)))));
+      }
+    })
+  });
+
+  let mut rewriter = ExportBindingRewriter {
+    exported: &exported,
+  };
+  modules
+    .iter_mut()
+    .for_each(|module| module.visit_mut_with(&mut rewriter));
+
+  SystemLinks {
+    before: before.into_iter().map(ast::ModuleItem::Stmt).collect(),
+    dependencies,
+    bindings,
+  }
+}
+
+/// `exports('name', value)`, which returns `value`.
+fn export_call(name: &str, value: Box<ast::Expr>) -> Box<ast::Expr> {
+  call(ident("exports"), vec![str_lit(name), value])
+}
+
+/// ```js
+/// Object.keys(module).forEach(function (k) {
+///   if (k !== 'default') exports(k, module[k]);
+/// });
+/// ```
+fn export_all_stmt() -> ast::Stmt {
+  let key = || ident("k");
+  let value = box ast::Expr::Member(ast::MemberExpr {
+    span: DUMMY_SP,
+    obj: ident("module"),
+    prop: ast::MemberProp::Computed(ast::ComputedPropName {
+      span: DUMMY_SP,
+      expr: key(),
+    }),
+  });
+  let body = ast::Stmt::If(ast::IfStmt {
+    span: DUMMY_SP,
+    test: box ast::Expr::Bin(ast::BinExpr {
+      span: DUMMY_SP,
+      op: ast::BinaryOp::NotEqEq,
+      left: key(),
+      right: str_lit("default"),
+    }),
+    cons: box expr_stmt(call(ident("exports"), vec![key(), value])),
+    alt: None,
+  });
+  expr_stmt(call(
+    member(
+      call(member(ident("Object"), "keys"), vec![ident("module")]),
+      "forEach",
+    ),
+    vec![fn_expr(vec![quote_ident!("k")], vec![body])],
+  ))
+}
+
+/// Updates exports once exported bindings are declared or assigned. After finalizing, scoped bindings never share
+/// names with top-level ones, so bindings are matched by names.
+struct ExportBindingRewriter<'a> {
+  exported: &'a FxHashMap<JsWord, Vec<JsWord>>,
+}
+
+impl ExportBindingRewriter<'_> {
+  fn wrap(&self, local: &JsWord, mut expr: Box<ast::Expr>) -> Box<ast::Expr> {
+    for name in &self.exported[local] {
+      expr = export_call(name, expr);
+    }
+    expr
+  }
+
+  /// Exported bindings declared by a top-level statement other than functions.
+  fn declared_exports(&self, stmt: &ast::Stmt) -> Vec<JsWord> {
+    let ids: Vec<ast::Id> = match stmt {
+      ast::Stmt::Decl(ast::Decl::Var(var)) => swc_ecma_utils::find_pat_ids(&var.decls),
+      ast::Stmt::Decl(ast::Decl::Class(class)) => vec![class.ident.to_id()],
+      _ => vec![],
+    };
+    ids
+      .into_iter()
+      .map(|(sym, _)| sym)
+      .filter(|sym| self.exported.contains_key(sym))
+      .collect()
+  }
+}
+
+impl VisitMut for ExportBindingRewriter<'_> {
+  fn visit_mut_module_items(&mut self, items: &mut Vec<ast::ModuleItem>) {
+    items.visit_mut_children_with(self);
+    // let count = 0; -> let count = 0; exports('count', count);
+    let mut new_items = Vec::with_capacity(items.len());
+    for item in items.drain(..) {
+      let declared = match &item {
+        ast::ModuleItem::Stmt(stmt) => self.declared_exports(stmt),
+        ast::ModuleItem::ModuleDecl(_) => vec![],
+      };
+      new_items.push(item);
+      declared.iter().for_each(|local| {
+        new_items.push(ast::ModuleItem::Stmt(expr_stmt(
+          self.wrap(local, ident(local)),
+        )))
+      });
+    }
+    *items = new_items;
+  }
+
+  fn visit_mut_expr(&mut self, expr: &mut ast::Expr) {
+    expr.visit_mut_children_with(self);
+    match expr {
+      // count = 1 -> exports('count', count = 1)
+      ast::Expr::Assign(ast::AssignExpr {
+        left:
+          ast::PatOrExpr::Pat(box ast::Pat::Ident(ast::BindingIdent { id: local, .. }))
+          | ast::PatOrExpr::Expr(box ast::Expr::Ident(local))
+          | ast::PatOrExpr::Pat(box ast::Pat::Expr(box ast::Expr::Ident(local))),
+        ..
+      }) if self.exported.contains_key(&local.sym) => {
+        let local = local.sym.clone();
+        *expr = *self.wrap(&local, box expr.take());
+      }
+      // ++count -> exports('count', ++count)
+      // count++ -> (exports('count', count + 1), count++)
+      ast::Expr::Update(ast::UpdateExpr {
+        op,
+        prefix,
+        arg: box ast::Expr::Ident(local),
+        ..
+      }) if self.exported.contains_key(&local.sym) => {
+        let local = local.sym.clone();
+        if *prefix {
+          *expr = *self.wrap(&local, box expr.take());
+        } else {
+          let updated = box ast::Expr::Bin(ast::BinExpr {
+            span: DUMMY_SP,
+            op: match op {
+              ast::UpdateOp::PlusPlus => ast::BinaryOp::Add,
+              ast::UpdateOp::MinusMinus => ast::BinaryOp::Sub,
+            },
+            left: ident(&local),
+            right: box ast::Expr::Lit(ast::Lit::Num(ast::Number {
+              span: DUMMY_SP,
+              value: 1.0,
+              raw: None,
+            })),
+          });
+          *expr = ast::Expr::Paren(ast::ParenExpr {
+            span: DUMMY_SP,
+            expr: box ast::Expr::Seq(ast::SeqExpr {
+              span: DUMMY_SP,
+              exprs: vec![self.wrap(&local, updated), box expr.take()],
+            }),
+          });
+        }
+      }
+      // import('./chunk.js') -> module.import('./chunk.js')
+      ast::Expr::Call(ast::CallExpr {
+        callee: callee @ ast::Callee::Import(_),
+        ..
+      }) => {
+        *callee = ast::Callee::Expr(member(ident("module"), "import"));
+      }
+      // import.meta -> module.meta
+      ast::Expr::MetaProp(ast::MetaPropExpr {
+        kind: ast::MetaPropKind::ImportMeta,
+        ..
+      }) => {
+        *expr = *member(ident("module"), "meta");
+      }
+      _ => {}
+    }
+  }
+}
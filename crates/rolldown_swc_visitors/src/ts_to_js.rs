@@ -1,3 +1,4 @@
+pub use decorators::Config as DecoratorsConfig;
 use swc_core::{
   common::{chain, util::take::Take, Mark, GLOBALS},
   ecma::{
@@ -9,14 +10,27 @@ use swc_core::{
         hygiene::hygiene,
         resolver,
       },
-      typescript::{self, strip_with_config},
+      proposal::decorators,
+      typescript::{self, strip_with_config, TsEnumConfig},
     },
-    visit::FoldWith,
+    visit::{FoldWith, Optional},
   },
 };
-pub use typescript::Config as TsConfig;
+pub use typescript::{Config as TsConfig, ImportsNotUsedAsValues};
 
-pub fn ts_to_js(ast: &mut Module, config: TsConfig) {
+/// `decorators` is set if decorators should be transformed, such as `experimentalDecorators` of
+/// `tsconfig.json` is enabled.
+pub fn ts_to_js(ast: &mut Module, config: TsConfig, decorators: Option<DecoratorsConfig>) {
+  // `const enum`s are kept as objects, since they could be imported by other modules. Their members
+  // are inlined in linking if possible.
+  let config = TsConfig {
+    ts_enum_config: TsEnumConfig {
+      treat_const_enum_as_enum: true,
+      ..config.ts_enum_config
+    },
+    ..config
+  };
+  let transforms_decorators = decorators.is_some();
   // It's ok to use a new GLOBALS here.
   GLOBALS.set(&Default::default(), || {
     let unresolved_mark = Mark::new();
@@ -25,13 +39,10 @@ pub fn ts_to_js(ast: &mut Module, config: TsConfig) {
     // Optionally transforms decorators here before the resolver pass
     // as it might produce runtime declarations.
     let mut chained = chain!(
-      // TODO: should we transpile the decorators in this phase?
-      // decorators::decorators(DecoratorConfig {
-      //   use_define_for_class_fields: config.use_define_for_class_fields,
-      // TODO: whats the correct default value?
-      //   legacy: false,
-      //   ..Default::default()
-      // }),
+      Optional::new(
+        decorators::decorators(decorators.unwrap_or_default()),
+        transforms_decorators
+      ),
       // Conduct identifier scope analysis
       resolver(unresolved_mark, top_level_mark, true),
       // Remove typescript types
@@ -0,0 +1,118 @@
+use swc_core::{
+  common::DUMMY_SP,
+  ecma::{
+    ast::{self, Decl, Expr, ModuleItem, Stmt},
+    utils::find_pat_ids,
+  },
+};
+
+/// Split the body of a module to be wrapped by `__esm`. Top-level declarations are kept in the
+/// module, so other modules could still access them, and the rest statements are returned to be
+/// evaluated lazily.
+/// ```js
+/// let foo = bar();
+/// class Foo {}
+/// function baz() {}
+/// console.log(foo);
+/// ```
+/// will be split into
+/// ```js
+/// var foo;
+/// var Foo;
+/// function baz() {}
+/// ```
+/// and the returned
+/// ```js
+/// foo = bar();
+/// Foo = class Foo {};
+/// console.log(foo);
+/// ```
+/// The declaration of `namespace` is kept as it is, since the namespace object is only a set of getters.
+pub fn split_lazy_statements(ast: &mut ast::Module, namespace: &ast::Id) -> Vec<Stmt> {
+  let mut lazy_stmts = vec![];
+  for module_item in std::mem::take(&mut ast.body) {
+    let stmt = match module_item {
+      ModuleItem::Stmt(stmt) => stmt,
+      ModuleItem::ModuleDecl(_) => unreachable!("Exports and imports should be removed before"),
+    };
+    match stmt {
+      Stmt::Decl(Decl::Fn(_)) => ast.body.push(ModuleItem::Stmt(stmt)),
+      Stmt::Decl(Decl::Var(var))
+        if var.decls.iter().any(
+          |decl| matches!(&decl.name, ast::Pat::Ident(ident) if &ident.to_id() == namespace),
+        ) =>
+      {
+        ast.body.push(ModuleItem::Stmt(Stmt::Decl(Decl::Var(var))))
+      }
+      Stmt::Decl(Decl::Var(var)) => {
+        let ids = var
+          .decls
+          .iter()
+          .flat_map(|decl| find_pat_ids::<_, ast::Id>(&decl.name))
+          .collect::<Vec<_>>();
+        if !ids.is_empty() {
+          ast.body.push(build_hoisted_var_decl(ids));
+        }
+        lazy_stmts.extend(
+          var
+            .decls
+            .into_iter()
+            .filter_map(|decl| decl.init.map(|init| build_assign_stmt(decl.name, init))),
+        );
+      }
+      Stmt::Decl(Decl::Class(class_decl)) => {
+        ast
+          .body
+          .push(build_hoisted_var_decl(vec![class_decl.ident.to_id()]));
+        lazy_stmts.push(build_assign_stmt(
+          ast::Pat::Ident(class_decl.ident.clone().into()),
+          box Expr::Class(ast::ClassExpr {
+            ident: Some(class_decl.ident),
+            class: class_decl.class,
+          }),
+        ));
+      }
+      _ => lazy_stmts.push(stmt),
+    }
+  }
+  lazy_stmts
+}
+
+/// `var foo, bar;`
+fn build_hoisted_var_decl(ids: Vec<ast::Id>) -> ModuleItem {
+  ModuleItem::Stmt(Stmt::Decl(Decl::Var(box ast::VarDecl {
+    span: DUMMY_SP,
+    kind: ast::VarDeclKind::Var,
+    declare: false,
+    decls: ids
+      .into_iter()
+      .map(|id| ast::VarDeclarator {
+        span: DUMMY_SP,
+        name: ast::Pat::Ident(ast::Ident::from(id).into()),
+        init: None,
+        definite: false,
+      })
+      .collect(),
+  })))
+}
+
+/// `foo = value;`, or `({ foo } = value);` for object patterns which can't start a statement.
+fn build_assign_stmt(pat: ast::Pat, value: Box<Expr>) -> Stmt {
+  let is_object_pat = matches!(pat, ast::Pat::Object(_));
+  let mut expr = box Expr::Assign(ast::AssignExpr {
+    span: DUMMY_SP,
+    op: ast::AssignOp::Assign,
+    left: ast::PatOrExpr::Pat(box pat),
+    right: value,
+  });
+  if is_object_pat {
+    expr = box Expr::Paren(ast::ParenExpr {
+      span: DUMMY_SP,
+      expr,
+    });
+  }
+  Stmt::Expr(ast::ExprStmt {
+    span: DUMMY_SP,
+    expr,
+  })
+}
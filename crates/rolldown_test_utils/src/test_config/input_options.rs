@@ -29,6 +29,9 @@ pub struct InputOptions {
   #[serde(default)]
   pub shim_missing_exports: bool,
 
+  #[serde(default)]
+  pub strict_execution_order: bool,
+
   #[serde(default)]
   pub builtins: Builtins,
 }
@@ -51,7 +54,7 @@ pub struct Builtins {
 #[serde(rename_all = "camelCase", deny_unknown_fields)]
 pub struct TsConfig {
   #[serde(default)]
-  pub use_define_for_class_fields: bool,
+  pub use_define_for_class_fields: Option<bool>,
 }
 
 impl_serde_default!(InputOptions);
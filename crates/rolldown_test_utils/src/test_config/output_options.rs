@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+
 use schemars::JsonSchema;
 use serde::Deserialize;
 
@@ -11,6 +13,10 @@ fn auto_by_default() -> String {
   "auto".to_string()
 }
 
+fn define_by_default() -> String {
+  "define".to_string()
+}
+
 #[derive(Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase", deny_unknown_fields)]
 pub struct OutputOptions {
@@ -18,6 +24,24 @@ pub struct OutputOptions {
   pub format: String,
   #[serde(default = "auto_by_default")]
   pub export_mode: String,
+  #[serde(default)]
+  pub name: Option<String>,
+  #[serde(default)]
+  pub globals: HashMap<String, String>,
+  #[serde(default)]
+  pub amd: AmdOptions,
+  #[serde(default = "auto_by_default")]
+  pub interop: String,
+}
+
+#[derive(Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct AmdOptions {
+  #[serde(default)]
+  pub id: Option<String>,
+  #[serde(default = "define_by_default")]
+  pub define: String,
 }
 
 impl_serde_default!(OutputOptions);
+impl_serde_default!(AmdOptions);
@@ -68,6 +68,7 @@ impl Tester {
         ..Default::default()
       },
       shim_missing_exports: self.config.input.shim_missing_exports,
+      strict_execution_order: self.config.input.strict_execution_order,
     }
   }
 }
@@ -1,39 +1,120 @@
-import type { Plugin } from '../rollup-types'
-import type { BuildPluginOption } from '@rolldown/node-binding'
-import { unimplemented } from '../utils'
+import type { InputOptions, OutputBundle, Plugin } from '../rollup-types'
+import type {
+  BuildPluginOption,
+  FilterPatternOption,
+  HookFilterOption,
+} from '@rolldown/node-binding'
+import {
+  transformToPluginContext,
+  transformToRenderedChunk,
+  transformToRollupOutputChunkOrAsset,
+  unimplemented,
+} from '../utils'
 
-export function createBuildPluginAdapter(plugin: Plugin): BuildPluginOption {
-  // TODO: Need to investigate how to pass context to plugin.
-  const context: any = null
+type FilterPattern = string | RegExp | Array<string | RegExp>
+
+interface HookWithFilter {
+  filter?: {
+    id?: { include?: FilterPattern; exclude?: FilterPattern }
+  }
+}
+
+function toFilterPatterns(
+  pattern: FilterPattern | undefined,
+): FilterPatternOption[] | undefined {
+  if (pattern == null) {
+    return undefined
+  }
+  return (Array.isArray(pattern) ? pattern : [pattern]).map((p) =>
+    typeof p === 'string'
+      ? { glob: p }
+      : { regex: p.flags.includes('i') ? `(?i)${p.source}` : p.source },
+  )
+}
+
+// Hooks in the object form could declare `filter.id`, so they are not called for ids they don't care about.
+function toHookFilterOption(hook: unknown): HookFilterOption | undefined {
+  const filter = (hook as HookWithFilter | undefined)?.filter?.id
+  if (filter == null) {
+    return undefined
+  }
+  return {
+    include: toFilterPatterns(filter.include),
+    exclude: toFilterPatterns(filter.exclude),
+  }
+}
+
+export function createBuildPluginAdapter(
+  plugin: Plugin,
+  inputOptions: InputOptions,
+): BuildPluginOption {
   return {
     name: plugin.name ?? 'unknown',
-    transform: async (code, id) => {
-      const transform = plugin.transform
-      if (transform == null) {
-        return null
-      }
-      const handler = (function () {
-        if (typeof transform === 'function') {
-          return transform
-        } else {
-          return transform.handler
-        }
-      })()
-
-      const ret = await handler.call(context, code, id)
-
-      if (typeof ret === 'string' || ret == null) {
-        return ret ?? null
-      }
-
-      if ('code' in ret) {
-        // TODO: we don't supports source map yet.
-        return ret.code
-      }
-    },
+    // Vite-style plugins declare their order with `enforce`.
+    enforce: (plugin as { enforce?: 'pre' | 'post' }).enforce,
+    transformFilter: toHookFilterOption(plugin.transform),
+    resolveIdFilter: toHookFilterOption(plugin.resolveId),
+    loadFilter: toHookFilterOption(plugin.load),
+    buildStart: !plugin.buildStart
+      ? undefined
+      : async (ctx) => {
+          const buildStart = plugin.buildStart
+          if (buildStart == null) {
+            return
+          }
+
+          const handler = (function () {
+            if (typeof buildStart === 'function') {
+              return buildStart
+            } else {
+              return buildStart.handler
+            }
+          })()
+
+          // TODO: options are not normalized like rollup's `NormalizedInputOptions` yet.
+          await handler.call(
+            transformToPluginContext(ctx),
+            inputOptions as any,
+          )
+        },
+    transform: !plugin.transform
+      ? undefined
+      : async (ctx, code, id) => {
+          const transform = plugin.transform
+          if (transform == null) {
+            return null
+          }
+          const handler = (function () {
+            if (typeof transform === 'function') {
+              return transform
+            } else {
+              return transform.handler
+            }
+          })()
+
+          const ret = await handler.call(transformToPluginContext(ctx), code, id)
+
+          if (ret == null) {
+            return null
+          }
+
+          if (typeof ret === 'string') {
+            return { code: ret }
+          }
+
+          if (ret.code == null) {
+            return null
+          }
+
+          const map =
+            ret.map == null || typeof ret.map === 'string'
+              ? ret.map ?? undefined
+              : JSON.stringify(ret.map)
+          return { code: ret.code, map }
+        },
     resolveId: !plugin.resolveId
       ? undefined
-      : async (specifier, importer) => {
+      : async (ctx, specifier, importer) => {
           const resolveId = plugin.resolveId
           if (resolveId == null) {
             return null
@@ -47,12 +128,17 @@ export function createBuildPluginAdapter(plugin: Plugin): BuildPluginOption {
             }
           })()
 
-          const ret = await handler.call(context, specifier, importer, {
-            assertions: {},
-            get isEntry() {
-              return unimplemented()
+          const ret = await handler.call(
+            transformToPluginContext(ctx),
+            specifier,
+            importer,
+            {
+              assertions: {},
+              get isEntry() {
+                return unimplemented()
+              },
             },
-          })
+          )
           if (typeof ret === 'string') {
             return {
               id: ret,
@@ -72,5 +158,152 @@ export function createBuildPluginAdapter(plugin: Plugin): BuildPluginOption {
             }
           }
         },
+    load: !plugin.load
+      ? undefined
+      : async (ctx, id) => {
+          const load = plugin.load
+          if (load == null) {
+            return null
+          }
+
+          const handler = (function () {
+            if (typeof load === 'function') {
+              return load
+            } else {
+              return load.handler
+            }
+          })()
+
+          const ret = await handler.call(transformToPluginContext(ctx), id)
+          if (typeof ret === 'string') {
+            return {
+              code: ret,
+            }
+          } else if (ret == null) {
+            return null
+          } else {
+            // TODO: we don't supports source map yet.
+            return {
+              code: ret.code,
+            }
+          }
+        },
+    buildEnd: !plugin.buildEnd
+      ? undefined
+      : async (ctx, error) => {
+          const buildEnd = plugin.buildEnd
+          if (buildEnd == null) {
+            return
+          }
+
+          const handler = (function () {
+            if (typeof buildEnd === 'function') {
+              return buildEnd
+            } else {
+              return buildEnd.handler
+            }
+          })()
+
+          await handler.call(
+            transformToPluginContext(ctx),
+            error == null ? undefined : new Error(error),
+          )
+        },
+    renderChunk: !plugin.renderChunk
+      ? undefined
+      : async (ctx, code, chunk) => {
+          const renderChunk = plugin.renderChunk
+          if (renderChunk == null) {
+            return null
+          }
+
+          const handler = (function () {
+            if (typeof renderChunk === 'function') {
+              return renderChunk
+            } else {
+              return renderChunk.handler
+            }
+          })()
+
+          // TODO: output options are not passed for now.
+          const ret = await handler.call(
+            transformToPluginContext(ctx),
+            code,
+            transformToRenderedChunk(chunk),
+            {} as any,
+          )
+          if (typeof ret === 'string') {
+            return { code: ret }
+          }
+          if (ret == null) {
+            return null
+          }
+          const map =
+            ret.map == null || typeof ret.map === 'string'
+              ? ret.map ?? undefined
+              : JSON.stringify(ret.map)
+          return { code: ret.code, map }
+        },
+    generateBundle: !plugin.generateBundle
+      ? undefined
+      : async (ctx, outputs) => {
+          const generateBundle = plugin.generateBundle
+          if (generateBundle == null) {
+            return null
+          }
+
+          const handler = (function () {
+            if (typeof generateBundle === 'function') {
+              return generateBundle
+            } else {
+              return generateBundle.handler
+            }
+          })()
+
+          const bundle: OutputBundle = Object.fromEntries(
+            outputs.map((output) => [
+              output.fileName,
+              transformToRollupOutputChunkOrAsset(output),
+            ]),
+          )
+          // TODO: output options and `isWrite` are not passed for now.
+          await handler.call(
+            transformToPluginContext(ctx),
+            {} as any,
+            bundle,
+            false,
+          )
+          // Plugins could add, rename or delete outputs by mutating `bundle`.
+          return Object.values(bundle).map((output) => {
+            if (output.type === 'chunk') {
+              return { fileName: output.fileName, code: output.code }
+            }
+            return typeof output.source === 'string'
+              ? { fileName: output.fileName, code: output.source }
+              : {
+                  fileName: output.fileName,
+                  code: '',
+                  source: Buffer.from(output.source),
+                }
+          })
+        },
+    closeBundle: !plugin.closeBundle
+      ? undefined
+      : async (ctx) => {
+          const closeBundle = plugin.closeBundle
+          if (closeBundle == null) {
+            return
+          }
+
+          const handler = (function () {
+            if (typeof closeBundle === 'function') {
+              return closeBundle
+            } else {
+              return closeBundle.handler
+            }
+          })()
+
+          await handler.call(transformToPluginContext(ctx))
+        },
   }
 }
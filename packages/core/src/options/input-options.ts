@@ -1,5 +1,8 @@
 import { InputOptions as RollupInputOptions } from '../rollup-types'
-import { InputOptions as BindingInputOptions } from '@rolldown/node-binding'
+import {
+  InputOptions as BindingInputOptions,
+  JsxOptions,
+} from '@rolldown/node-binding'
 import path from 'path'
 import { arraify, noop, normalizePluginOption } from '../utils'
 import { createBuildPluginAdapter } from './create-build-plugin-adapter'
@@ -91,6 +94,16 @@ export interface InputOptions extends RollupInputOptions {
   // --- Extra
 
   cwd?: string
+  /**
+   * Wrap every module in a lazy initializer so evaluation order matches native ESM.
+   * Modules in import cycles or reached through `require` are always wrapped.
+   */
+  strictExecutionOrder?: boolean
+  /**
+   * How JSX is lowered. Unset options follow `jsx`, `jsxFactory`, `jsxFragmentFactory` and `jsxImportSource`
+   * of the nearest `tsconfig.json`.
+   */
+  jsx?: JsxOptions
 }
 
 function normalizeInput(
@@ -150,9 +163,12 @@ export function normalizeExternal(
 
 async function normalizePlugins(
   option: InputOptions['plugins'],
+  inputOptions: InputOptions,
 ): Promise<BindingInputOptions['plugins']> {
   const plugins = await normalizePluginOption(option)
-  const adapters = plugins.map((plugin) => createBuildPluginAdapter(plugin))
+  const adapters = plugins.map((plugin) =>
+    createBuildPluginAdapter(plugin, inputOptions),
+  )
   return adapters
 }
 
@@ -167,6 +183,8 @@ export async function normalizeInputOptions(
     cwd,
     preserveSymlinks,
     shimMissingExports,
+    strictExecutionOrder,
+    jsx,
     ...rest
   } = input_opts
 
@@ -179,13 +197,15 @@ export async function normalizeInputOptions(
     input: normalizeInput(input),
     treeshake: treeshake,
     external: normalizeExternal(external),
-    plugins: await normalizePlugins(plugins),
+    plugins: await normalizePlugins(plugins, input_opts),
     cwd: cwd ?? process.cwd(),
     shimMissingExports: shimMissingExports ?? false,
+    strictExecutionOrder: strictExecutionOrder ?? false,
     builtins: {
       nodeResolve: {
         extensions: ['.js', '.ts', '.tsx', 'jsx'],
       },
+      jsx,
     },
     preserveSymlinks: preserveSymlinks ?? false,
   }
@@ -5,11 +5,6 @@ import { unimplemented } from '../utils'
 export interface OutputOptions extends RollupOutputOptions {
   // --- NotGoingToSupports
 
-  /**
-   * @deprecated
-   * TODO: Rolldown might supports this in a long term. Need to investigate.
-   */
-  amd?: never
   /**
    * @deprecated
    * TODO: Rolldown might supports this in a long term. Need to investigate.
@@ -110,40 +105,70 @@ export interface OutputOptions extends RollupOutputOptions {
   footer?: never
   freeze?: never
   generatedCode?: never
-  globals?: never
   inlineDynamicImports?: never
   intro?: never
-  name?: never
   outro?: never
   paths?: never
   plugins?: never
-  sourcemap?: never
   sourcemapBaseUrl?: never
-  sourcemapExcludeSources?: never
   sourcemapFile?: never
   sourcemapPathTransform?: never
   strict?: never
-  interop?: never
 
   // Rewritten
 
   file?: never // TODO: Rolldown might supports this in a long term. Need to investigate.
+  globals?: Record<string, string>
+  amd?: {
+    id?: string
+    define?: string
+  }
 }
 
 function normalizeFormat(
   format: OutputOptions['format'],
 ): BindingOutputOptions['format'] {
-  if (format === 'esm' || format === 'cjs') {
+  if (
+    format === 'esm' ||
+    format === 'cjs' ||
+    format === 'iife' ||
+    format === 'amd' ||
+    format === 'umd' ||
+    format === 'system'
+  ) {
     return format
   } else {
     return unimplemented(`output.format: ${format}`)
   }
 }
 
+function normalizeInterop(
+  interop: OutputOptions['interop'],
+): BindingOutputOptions['interop'] {
+  if (interop instanceof Function) {
+    return {
+      function: (id) => interop(id),
+    }
+  } else {
+    return { mode: interop }
+  }
+}
+
 export function normalizeOutputOptions(
   opts: OutputOptions,
 ): BindingOutputOptions {
-  const { dir, format, exports, ...rest } = opts
+  const {
+    dir,
+    format,
+    exports,
+    sourcemap,
+    sourcemapExcludeSources,
+    name,
+    globals,
+    amd,
+    interop,
+    ...rest
+  } = opts
   // Make sure all fields of RollupInputOptions are handled.
   // @ts-expect-error
   const _empty: never = undefined as unknown as NonNullable<
@@ -153,5 +178,11 @@ export function normalizeOutputOptions(
     dir: dir,
     format: normalizeFormat(format),
     exports,
+    sourcemap,
+    sourcemapExcludeSources,
+    name,
+    globals,
+    amd,
+    interop: normalizeInterop(interop),
   }
 }
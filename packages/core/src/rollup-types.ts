@@ -6,5 +6,9 @@ export type {
   InputOptions,
   Plugin,
   OutputPlugin,
+  PluginContext,
   OutputChunk,
+  OutputAsset,
+  OutputBundle,
+  RenderedChunk,
 } from 'rollup'
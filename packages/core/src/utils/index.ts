@@ -1,5 +1,7 @@
 export * from './async-flatten'
 export * from './transformToRollupOutput'
+export * from './transformToRenderedChunk'
+export * from './transformToPluginContext'
 export * from './normalizePluginOption'
 
 export function arraify<T>(value: T | T[]): T[] {
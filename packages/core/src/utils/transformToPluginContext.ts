@@ -0,0 +1,58 @@
+import type {
+  PluginContext as BindingPluginContext,
+} from '@rolldown/node-binding'
+import type { PluginContext } from '../rollup-types'
+import { unimplemented } from '.'
+
+// Only the methods rolldown supports are implemented. Others throw when they are called.
+export function transformToPluginContext(
+  ctx: BindingPluginContext,
+): PluginContext {
+  const context: Partial<PluginContext> = {
+    warn(warning) {
+      ctx.warn(typeof warning === 'string' ? warning : warning.message)
+    },
+    async resolve(source, importer, options) {
+      const ret = await ctx.resolve(
+        source,
+        importer,
+        options?.skipSelf ?? true,
+      )
+      if (ret == null) {
+        return null
+      }
+      return {
+        id: ret.id,
+        external: ret.external,
+        assertions: {},
+        meta: {},
+        moduleSideEffects: true,
+        syntheticNamedExports: false,
+      }
+    },
+    getModuleInfo(id) {
+      const info = ctx.getModuleInfo(id)
+      if (info == null) {
+        return null
+      }
+      return {
+        id: info.id,
+        isEntry: info.isEntry,
+        isExternal: info.isExternal,
+        importedIds: info.importedIds,
+        dynamicallyImportedIds: info.dynamicallyImportedIds,
+      } as any
+    },
+    addWatchFile(id) {
+      ctx.addWatchFile(id)
+    },
+  }
+  return new Proxy(context, {
+    get(target, prop, receiver) {
+      if (prop in target) {
+        return Reflect.get(target, prop, receiver)
+      }
+      return unimplemented(`this.${String(prop)} of plugins`)
+    },
+  }) as PluginContext
+}
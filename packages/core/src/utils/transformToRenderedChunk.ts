@@ -0,0 +1,41 @@
+import type { ChunkInfo } from '@rolldown/node-binding'
+import type { RenderedChunk } from '../rollup-types'
+import { unimplemented } from '.'
+
+export function transformToRenderedChunk(chunk: ChunkInfo): RenderedChunk {
+  return {
+    type: 'chunk',
+    name: chunk.name,
+    fileName: chunk.fileName,
+    isEntry: chunk.isEntry,
+    isDynamicEntry: chunk.isDynamicEntry,
+    isImplicitEntry: false,
+    facadeModuleId: chunk.facadeModuleId ?? null,
+    moduleIds: chunk.moduleIds,
+    exports: chunk.exports,
+    get dynamicImports() {
+      throw unimplemented()
+      return unimplemented()
+    },
+    get implicitlyLoadedBefore() {
+      throw unimplemented()
+      return unimplemented()
+    },
+    get importedBindings() {
+      throw unimplemented()
+      return unimplemented()
+    },
+    get imports() {
+      throw unimplemented()
+      return unimplemented()
+    },
+    get modules() {
+      throw unimplemented()
+      return unimplemented()
+    },
+    get referencedFiles() {
+      throw unimplemented()
+      return unimplemented()
+    },
+  }
+}
@@ -2,11 +2,12 @@ import type { AsyncReturnType } from 'type-fest'
 import { Bundler, OutputChunk } from '@rolldown/node-binding'
 import type {
   RollupOutput,
+  OutputAsset as RollupOutputAsset,
   OutputChunk as RollupOutputChunk,
 } from '../rollup-types'
 import { unimplemented } from '.'
 
-function transformToRollupOutputChunk(chunk: OutputChunk): RollupOutputChunk {
+export function transformToRollupOutputChunk(chunk: OutputChunk): RollupOutputChunk {
   return {
     type: 'chunk',
     code: chunk.code,
@@ -70,6 +71,27 @@ function transformToRollupOutputChunk(chunk: OutputChunk): RollupOutputChunk {
   }
 }
 
+function transformToRollupOutputAsset(
+  chunk: OutputChunk & { source: Buffer },
+): RollupOutputAsset {
+  return {
+    type: 'asset',
+    fileName: chunk.fileName,
+    source: chunk.source,
+    name: undefined,
+    needsCodeReference: false,
+  }
+}
+
+// Binary assets emitted by plugins are transformed to `OutputAsset`s, others are `OutputChunk`s.
+export function transformToRollupOutputChunkOrAsset(
+  chunk: OutputChunk,
+): RollupOutputChunk | RollupOutputAsset {
+  return chunk.source == null
+    ? transformToRollupOutputChunk(chunk)
+    : transformToRollupOutputAsset({ ...chunk, source: chunk.source })
+}
+
 export function transformToRollupOutput(
   output: AsyncReturnType<Bundler['write']>,
 ): RollupOutput {
@@ -77,7 +99,7 @@ export function transformToRollupOutput(
   return {
     output: [
       transformToRollupOutputChunk(first),
-      ...rest.map(transformToRollupOutputChunk),
+      ...rest.map(transformToRollupOutputChunkOrAsset),
     ],
   }
 }
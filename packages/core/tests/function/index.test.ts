@@ -44,7 +44,11 @@ async function runSampleTest(sampleConfigPath: string) {
     ...testConfig.options,
   })
   fs.rmSync(distDir, { recursive: true, force: true })
-  await build.write({ dir: distDir, format: 'cjs' })
+  await build.write({
+    ...testConfig.outputOptions,
+    dir: distDir,
+    format: 'cjs',
+  })
   const exports = await import(path.join(distDir, 'main.js'))
   await testConfig.exports?.(exports)
 }
@@ -0,0 +1,13 @@
+import { defineTestConfig } from '@/utils'
+import * as t from 'vitest'
+
+export default defineTestConfig({
+  exports(exports) {
+    t.expect(exports.default).toEqual({
+      add: 3,
+      greeting: 'hello',
+      fromEsm: 'esm',
+      esModuleDefault: 'default of esModule',
+    })
+  },
+})
@@ -0,0 +1,10 @@
+import math, { add } from './math.js'
+import { greeting } from './greeting.js'
+import esModule from './es-module.js'
+
+export default {
+  add: add(1, 2),
+  greeting: math.greeting === greeting ? greeting : 'unexpected',
+  fromEsm: require('./esm.js').value,
+  esModuleDefault: esModule,
+}
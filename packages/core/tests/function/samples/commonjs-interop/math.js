@@ -0,0 +1,4 @@
+exports.add = function (a, b) {
+  return a + b
+}
+exports.greeting = require('./greeting.js').greeting
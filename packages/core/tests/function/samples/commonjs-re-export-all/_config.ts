@@ -0,0 +1,15 @@
+import { defineTestConfig } from '@/utils'
+import * as t from 'vitest'
+
+export default defineTestConfig({
+  exports(exports) {
+    const main = exports.default
+    // export * from './lib.js'
+    t.expect(main.fromLib).toBe('lib')
+    // export * from './reexporter.js', which re-exports all names of './nested.js'
+    t.expect(main.fromNested).toBe('nested')
+    // Names of CommonJS modules are also available on the namespace and by named imports
+    t.expect(main.viaNamespace).toBe('indirect')
+    t.expect(main.viaNamedImport).toBe('indirect')
+  },
+})
@@ -0,0 +1 @@
+exports.fromLib = 'lib'
@@ -0,0 +1,7 @@
+import * as indirect from './indirect.js'
+import { fromIndirect } from './indirect.js'
+export * from './lib.js'
+export * from './reexporter.js'
+
+export const viaNamespace = indirect.fromIndirect
+export const viaNamedImport = fromIndirect
@@ -0,0 +1 @@
+exports.fromNested = 'nested'
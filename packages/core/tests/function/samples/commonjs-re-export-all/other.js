@@ -0,0 +1 @@
+exports.fromIndirect = 'indirect'
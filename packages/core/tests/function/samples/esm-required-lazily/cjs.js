@@ -0,0 +1,7 @@
+const { log } = require('./log.js')
+
+log.push('cjs')
+
+module.exports = {
+  getLazy: () => require('./lazy.js').value,
+}
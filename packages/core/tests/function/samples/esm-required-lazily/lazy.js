@@ -0,0 +1,5 @@
+import { log } from './log.js'
+
+log.push('lazy')
+
+export const value = 'lazy'
@@ -0,0 +1,7 @@
+import { log } from './log.js'
+import { getLazy } from './cjs.js'
+
+log.push('main')
+const value = getLazy()
+
+export default { log, value }
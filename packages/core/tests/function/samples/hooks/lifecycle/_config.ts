@@ -0,0 +1,26 @@
+import { defineTestConfig } from '@/utils'
+import * as t from 'vitest'
+
+const calls: string[] = []
+
+export default defineTestConfig({
+  options: {
+    plugins: [
+      {
+        name: 'lifecycle',
+        buildStart() {
+          calls.push('buildStart')
+        },
+        buildEnd(error) {
+          calls.push(error == null ? 'buildEnd' : 'buildEnd with error')
+        },
+        closeBundle() {
+          calls.push('closeBundle')
+        },
+      },
+    ],
+  },
+  exports() {
+    t.expect(calls).toEqual(['buildStart', 'buildEnd', 'closeBundle'])
+  },
+})
@@ -0,0 +1,46 @@
+import { defineTestConfig } from '@/utils'
+import fs from 'fs'
+import path from 'path'
+import * as t from 'vitest'
+
+export default defineTestConfig({
+  options: {
+    plugins: [
+      {
+        name: 'chunk-info',
+        renderChunk(code, chunk) {
+          const info = {
+            fileName: chunk.fileName,
+            isEntry: chunk.isEntry,
+            exports: chunk.exports,
+          }
+          return `${code}\nexports.chunkInfo = ${JSON.stringify(info)}`
+        },
+      },
+      {
+        name: 'manifest',
+        generateBundle(_options, bundle) {
+          bundle['manifest.json'] = {
+            type: 'asset',
+            fileName: 'manifest.json',
+            name: undefined,
+            needsCodeReference: false,
+            source: JSON.stringify(Object.keys(bundle)),
+          }
+        },
+      },
+    ],
+  },
+  exports(exports) {
+    t.expect(exports.chunkInfo).toEqual({
+      fileName: 'main.js',
+      isEntry: true,
+      exports: ['foo'],
+    })
+    const manifest = fs.readFileSync(
+      path.join(__dirname, 'dist/manifest.json'),
+      'utf-8',
+    )
+    t.expect(JSON.parse(manifest)).toEqual(['main.js'])
+  },
+})
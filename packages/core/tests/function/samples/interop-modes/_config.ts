@@ -0,0 +1,41 @@
+import { defineTestConfig } from '@/utils'
+import fs from 'fs'
+import os from 'os'
+import path from 'path'
+import url from 'url'
+import util from 'util'
+import * as t from 'vitest'
+
+const modes = {
+  path: 'auto',
+  url: 'compat',
+  fs: 'compat',
+  os: 'default',
+  util: 'defaultOnly',
+} as const
+
+export default defineTestConfig({
+  options: {
+    external: Object.keys(modes),
+  },
+  outputOptions: {
+    interop: (id) => modes[id as keyof typeof modes] ?? 'auto',
+  },
+  exports(exports) {
+    const imported = exports.default
+    // __interopDefault
+    t.expect(imported.path).toBe(path)
+    // __interopDefaultCompat
+    t.expect(imported.url).toBe(url)
+    // __interopNamespaceCompat
+    t.expect(imported.fs.default).toBe(fs)
+    t.expect(imported.fs.readFileSync).toBe(fs.readFileSync)
+    t.expect(Object.isFrozen(imported.fs)).toBe(true)
+    // __interopNamespaceDefault
+    t.expect(imported.os.default).toBe(os)
+    t.expect(imported.os.platform).toBe(os.platform)
+    // __interopNamespaceDefaultOnly
+    t.expect(Object.keys(imported.util)).toEqual(['default'])
+    t.expect(imported.util.default).toBe(util)
+  },
+})
@@ -0,0 +1,7 @@
+import path from 'path'
+import url from 'url'
+import * as fs from 'fs'
+import * as os from 'os'
+import * as util from 'util'
+
+export default { path, url, fs, os, util }
@@ -0,0 +1,17 @@
+import { defineTestConfig } from '@/utils'
+import fs from 'fs'
+import path from 'path'
+import * as t from 'vitest'
+
+export default defineTestConfig({
+  options: {
+    external: ['path', 'fs'],
+  },
+  outputOptions: {
+    interop: (id) => (id === 'path' ? 'default' : 'esModule'),
+  },
+  exports(exports) {
+    t.expect(exports.default.path).toBe(path)
+    t.expect(exports.default.fs).toBe(fs)
+  },
+})
@@ -0,0 +1,4 @@
+import path from 'path'
+import * as fs from 'fs'
+
+export default { path, fs }
@@ -0,0 +1,13 @@
+import { defineTestConfig } from '@/utils'
+import * as t from 'vitest'
+
+export default defineTestConfig({
+  exports(exports) {
+    t.expect(exports.default.name).toEqual('rolldown')
+    t.expect(exports.default.data).toEqual({
+      name: 'rolldown',
+      version: '0.0.1',
+      'dev-dependencies': { vitest: '*' },
+    })
+  },
+})
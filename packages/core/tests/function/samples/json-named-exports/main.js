@@ -0,0 +1,3 @@
+import data, { name } from './data.json'
+
+export default { data, name }
@@ -0,0 +1,19 @@
+import { defineTestConfig } from '@/utils'
+import * as t from 'vitest'
+
+export default defineTestConfig({
+  options: {
+    jsx: {
+      runtime: 'automatic',
+    },
+  },
+  exports(exports) {
+    t.expect(exports.default).toEqual({
+      type: 'div',
+      props: {
+        id: 'app',
+        children: { type: 'span', props: { children: 'hi' } },
+      },
+    })
+  },
+})
@@ -0,0 +1,5 @@
+export default (
+  <div id="app">
+    <span>hi</span>
+  </div>
+)
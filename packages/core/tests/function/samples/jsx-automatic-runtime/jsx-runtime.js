@@ -0,0 +1,7 @@
+export function jsx(type, props) {
+  return { type, props }
+}
+
+export const jsxs = jsx
+
+export const Fragment = 'Fragment'
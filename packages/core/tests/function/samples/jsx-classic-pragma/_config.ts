@@ -0,0 +1,19 @@
+import { defineTestConfig } from '@/utils'
+import * as t from 'vitest'
+
+export default defineTestConfig({
+  options: {
+    jsx: {
+      runtime: 'classic',
+      pragma: 'h',
+      pragmaFrag: 'Fragment',
+    },
+  },
+  exports(exports) {
+    t.expect(exports.default).toEqual({
+      type: 'Fragment',
+      props: null,
+      children: [{ type: 'b', props: null, children: ['x'] }],
+    })
+  },
+})
@@ -0,0 +1,7 @@
+import { Fragment, h } from './h.js'
+
+export default (
+  <>
+    <b>x</b>
+  </>
+)
@@ -0,0 +1,5 @@
+export function h(type, props, ...children) {
+  return { type, props, children }
+}
+
+export const Fragment = 'Fragment'
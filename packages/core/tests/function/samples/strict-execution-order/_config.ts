@@ -0,0 +1,14 @@
+import { defineTestConfig } from '@/utils'
+import * as t from 'vitest'
+
+export default defineTestConfig({
+  options: {
+    strictExecutionOrder: true,
+  },
+  exports(exports) {
+    t.expect(exports.default).toEqual({
+      log: ['b', 'a', 'main'],
+      name: 'Child',
+    })
+  },
+})
@@ -0,0 +1,6 @@
+import { log } from './log.js'
+import { Base } from './b.js'
+
+log.push('a')
+
+export class Child extends Base {}
@@ -0,0 +1,10 @@
+import { log } from './log.js'
+import { Child } from './a.js'
+
+log.push('b')
+
+export class Base {}
+
+export function create() {
+  return new Child()
+}
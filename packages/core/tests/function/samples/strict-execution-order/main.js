@@ -0,0 +1,7 @@
+import { log } from './log.js'
+import { Child } from './a.js'
+import { create } from './b.js'
+
+log.push('main')
+
+export default { log, name: create() instanceof Child ? 'Child' : 'unexpected' }
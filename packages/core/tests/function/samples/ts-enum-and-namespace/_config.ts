@@ -0,0 +1,12 @@
+import { defineTestConfig } from '@/utils'
+import * as t from 'vitest'
+
+export default defineTestConfig({
+  exports(exports) {
+    t.expect(exports.default).toEqual({
+      directions: [1, 2, -1, 'right'],
+      color: 'Green',
+      version: '1.0.0',
+    })
+  },
+})
@@ -0,0 +1,26 @@
+export const enum Direction {
+  Up = 1,
+  Down,
+  Left = -1,
+  Right = 'right',
+}
+
+export enum Color {
+  Red,
+  Green,
+}
+
+export namespace Meta {
+  export const version = '1.0.0'
+}
+
+export interface Point {
+  x: number
+  y: number
+}
+
+export type Name = string
+
+export default interface Options {
+  name: Name
+}
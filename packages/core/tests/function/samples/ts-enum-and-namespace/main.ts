@@ -0,0 +1,11 @@
+import type Options from './enums'
+import { Color, Direction, Meta, Point } from './enums'
+
+const point: Point = { x: 0, y: 0 }
+const options: Options = { name: 'options' }
+
+export default {
+  directions: [Direction.Up, Direction.Down, Direction.Left, Direction['Right']],
+  color: Color[Color.Green],
+  version: Meta.version,
+}
@@ -0,0 +1,11 @@
+import { defineTestConfig } from '@/utils'
+import * as t from 'vitest'
+
+export default defineTestConfig({
+  exports(exports) {
+    t.expect(exports.default).toEqual({
+      registered: ['Service'],
+      hasDeclaredField: false,
+    })
+  },
+})
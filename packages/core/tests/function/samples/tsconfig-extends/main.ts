@@ -0,0 +1,15 @@
+const registered: string[] = []
+
+function register(target: Function) {
+  registered.push(target.name)
+}
+
+@register
+class Service {
+  declaredField: string | undefined
+}
+
+export default {
+  registered,
+  hasDeclaredField: 'declaredField' in new Service(),
+}
@@ -0,0 +1,7 @@
+import { add } from '@app/utils'
+import { name } from 'config'
+
+export default {
+  add: add(1, 2),
+  config: name,
+}
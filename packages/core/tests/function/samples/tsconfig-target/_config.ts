@@ -0,0 +1,12 @@
+import { defineTestConfig } from '@/utils'
+import * as t from 'vitest'
+
+export default defineTestConfig({
+  exports(exports) {
+    // `useDefineForClassFields` defaults to `true` only for `ES2022` or newer
+    t.expect(exports.default).toEqual({
+      modern: { hasDeclaredField: true },
+      legacy: { hasDeclaredField: false },
+    })
+  },
+})
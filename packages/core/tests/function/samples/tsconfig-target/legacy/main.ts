@@ -0,0 +1,7 @@
+class Legacy {
+  declaredField: string | undefined
+}
+
+export default {
+  hasDeclaredField: 'declaredField' in new Legacy(),
+}
@@ -0,0 +1,10 @@
+import legacy from './legacy/main'
+
+class Modern {
+  declaredField: string | undefined
+}
+
+export default {
+  modern: { hasDeclaredField: 'declaredField' in new Modern() },
+  legacy,
+}
@@ -1,7 +1,8 @@
-import type { InputOptions } from '@rolldown/core'
+import type { InputOptions, OutputOptions } from '@rolldown/core'
 
 export interface TestConfig {
   options?: InputOptions
+  outputOptions?: OutputOptions
   exports?(exports: any): void | Promise<void>
 }
 